@@ -1,6 +1,9 @@
+use std::{fmt, io::Write, sync::Mutex};
+
 use async_tungstenite::tungstenite::{handshake, Message};
+use flate2::{write::ZlibEncoder, Compression};
 use futures::channel::oneshot::Sender;
-use revolt_quark::{Error, Result};
+use revolt_quark::{events::intents::Intent, Error, Result};
 use serde::{Deserialize, Serialize};
 
 /// Enumeration of supported protocol formats
@@ -10,12 +13,78 @@ pub enum ProtocolFormat {
     Msgpack,
 }
 
+/// Enumeration of supported transport compression methods
+#[derive(Debug, Clone, Copy)]
+pub enum ProtocolCompression {
+    ZlibStream,
+    ZstdStream,
+}
+
+/// Streaming compressor shared by every frame sent over a connection
+enum Compressor {
+    Zlib(ZlibEncoder<Vec<u8>>),
+    Zstd(zstd::Encoder<'static, Vec<u8>>),
+}
+
+impl Compressor {
+    /// Create a new compressor for the given method
+    fn new(compression: ProtocolCompression) -> Self {
+        match compression {
+            ProtocolCompression::ZlibStream => {
+                Compressor::Zlib(ZlibEncoder::new(vec![], Compression::default()))
+            }
+            ProtocolCompression::ZstdStream => Compressor::Zstd(
+                zstd::Encoder::new(vec![], 0).expect("Failed to create zstd encoder."),
+            ),
+        }
+    }
+
+    /// Compress a frame and flush it so that the client can decode it straight away
+    ///
+    /// Frames are not self-contained, the client must feed every frame
+    /// it receives into a single decompression context in order.
+    fn compress(&mut self, data: &[u8]) -> Vec<u8> {
+        match self {
+            Compressor::Zlib(encoder) => {
+                encoder
+                    .write_all(data)
+                    .and_then(|_| encoder.flush())
+                    .expect("Failed to compress (as zlib).");
+
+                std::mem::take(encoder.get_mut())
+            }
+            Compressor::Zstd(encoder) => {
+                encoder
+                    .write_all(data)
+                    .and_then(|_| encoder.flush())
+                    .expect("Failed to compress (as zstd).");
+
+                std::mem::take(encoder.get_mut())
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Compressor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Compressor::Zlib(_) => write!(f, "Compressor::Zlib"),
+            Compressor::Zstd(_) => write!(f, "Compressor::Zstd"),
+        }
+    }
+}
+
 /// User-provided protocol configuration
 #[derive(Debug)]
 pub struct ProtocolConfiguration {
     protocol_version: i32,
     format: ProtocolFormat,
+    compression: Option<ProtocolCompression>,
+    compressor: Option<Mutex<Compressor>>,
     session_token: Option<String>,
+    resume: Option<(String, u64)>,
+    lazy: bool,
+    intents: Option<u32>,
 }
 
 impl ProtocolConfiguration {
@@ -23,12 +92,21 @@ impl ProtocolConfiguration {
     pub fn from(
         protocol_version: i32,
         format: ProtocolFormat,
+        compression: Option<ProtocolCompression>,
         session_token: Option<String>,
+        resume: Option<(String, u64)>,
+        lazy: bool,
+        intents: Option<u32>,
     ) -> Self {
         Self {
             protocol_version,
             format,
+            compression,
+            compressor: compression.map(|x| Mutex::new(Compressor::new(x))),
             session_token,
+            resume,
+            lazy,
+            intents,
         }
     }
 
@@ -53,14 +131,27 @@ impl ProtocolConfiguration {
     }
 
     /// Encode T: Serialize into a WebSocket message using the client's specified protocol format
+    ///
+    /// If the client asked for compression, the message is always sent as binary.
     pub fn encode<T: Serialize>(&self, data: &T) -> Message {
-        match self.format {
+        let message = match self.format {
             ProtocolFormat::Json => {
                 Message::Text(serde_json::to_string(data).expect("Failed to serialise (as json)."))
             }
             ProtocolFormat::Msgpack => Message::Binary(
                 rmp_serde::to_vec_named(data).expect("Failed to serialise (as msgpack)."),
             ),
+        };
+
+        if let Some(compressor) = &self.compressor {
+            Message::Binary(
+                compressor
+                    .lock()
+                    .expect("Compressor lock poisoned.")
+                    .compress(&message.into_data()),
+            )
+        } else {
+            message
         }
     }
 
@@ -74,6 +165,26 @@ impl ProtocolConfiguration {
         &self.session_token
     }
 
+    /// Set the session id and last received sequence number to resume from
+    pub fn set_resume(&mut self, session: String, seq: u64) {
+        self.resume.replace((session, seq));
+    }
+
+    /// Get the session id and last received sequence number to resume from
+    pub fn get_resume(&self) -> &Option<(String, u64)> {
+        &self.resume
+    }
+
+    /// Whether member lists should be left out and loaded on demand
+    pub fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    /// Get the intents declared by the client, if any
+    pub fn get_intents(&self) -> &Option<u32> {
+        &self.intents
+    }
+
     /// Get the protocol version specified
     pub fn get_protocol_version(&self) -> i32 {
         self.protocol_version
@@ -83,6 +194,11 @@ impl ProtocolConfiguration {
     pub fn get_protocol_format(&self) -> &ProtocolFormat {
         &self.format
     }
+
+    /// Get the transport compression specified
+    pub fn get_protocol_compression(&self) -> &Option<ProtocolCompression> {
+        &self.compression
+    }
 }
 
 /// Object holding one side of a channel for receiving the parsed information
@@ -111,7 +227,12 @@ impl handshake::server::Callback for WebsocketHandshakeCallback {
         // Set default values for the protocol.
         let mut protocol_version = 1;
         let mut format = ProtocolFormat::Json;
+        let mut compression = None;
         let mut session_token = None;
+        let mut session = None;
+        let mut seq = None;
+        let mut lazy = false;
+        let mut intents = None;
 
         // Parse and map parameters from key-value to known variables.
         for (key, value) in params {
@@ -126,7 +247,16 @@ impl handshake::server::Callback for WebsocketHandshakeCallback {
                     "msgpack" => format = ProtocolFormat::Msgpack,
                     _ => {}
                 },
+                "compress" => match value {
+                    "zlib-stream" => compression = Some(ProtocolCompression::ZlibStream),
+                    "zstd-stream" => compression = Some(ProtocolCompression::ZstdStream),
+                    _ => {}
+                },
                 "token" => session_token = Some(value.into()),
+                "session" => session = Some(value.into()),
+                "seq" => seq = value.parse().ok(),
+                "lazy" => lazy = value == "true",
+                "intents" => intents = Some(Intent::parse_list(value)),
                 _ => {}
             }
         }
@@ -135,11 +265,15 @@ impl handshake::server::Callback for WebsocketHandshakeCallback {
         // We have to use a channel as this function does not borrow mutably.
         if self
             .sender
-            .send(ProtocolConfiguration {
+            .send(ProtocolConfiguration::from(
                 protocol_version,
                 format,
+                compression,
                 session_token,
-            })
+                session.zip(seq),
+                lazy,
+                intents,
+            ))
             .is_ok()
         {
             Ok(response)
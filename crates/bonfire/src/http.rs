@@ -0,0 +1,530 @@
+use std::{collections::HashMap, env, net::SocketAddr, sync::Mutex, time::Duration};
+
+use async_std::{
+    future,
+    io::{ReadExt, WriteExt},
+    net::TcpStream,
+    task,
+};
+use futures::StreamExt;
+use once_cell::sync::Lazy;
+use revolt_quark::{
+    events::{client::EventV1, intents::Intent, server::ClientMessage},
+    metrics::{render, EVENTS_DELIVERED, EVENT_CONNECTIONS},
+    models::{user::UserHint, User},
+    Database, Error, Result,
+};
+use serde::Serialize;
+
+use crate::{
+    listener::{handle_message, start_session},
+    session::{Attachment, Sequenced, Session},
+};
+
+/// How long a long-poll request is held open while waiting for events
+const POLL_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often idle event streams are written to, so proxies don't close them
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Largest request head we are willing to read
+const MAX_HEAD_SIZE: usize = 16 * 1024;
+
+/// Largest request body we are willing to read
+const MAX_BODY_SIZE: usize = 16 * 1024;
+
+/// How long a client may take to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Most event streams and polls a user may have open on this node at once
+const MAX_STREAMS_PER_USER: usize = 8;
+
+/// Origins which browsers may connect from
+///
+/// Taken from `HTTP_CORS_ORIGINS` (comma separated), falling back to the app's own origin.
+static CORS_ORIGINS: Lazy<Vec<String>> = Lazy::new(|| {
+    env::var("HTTP_CORS_ORIGINS")
+        .or_else(|_| env::var("REVOLT_APP_URL"))
+        .map(|origins| {
+            origins
+                .split(',')
+                .map(|origin| origin.trim().trim_end_matches('/').to_string())
+                .filter(|origin| !origin.is_empty())
+                .collect()
+        })
+        .unwrap_or_default()
+});
+
+/// Number of open event streams and polls by user
+static OPEN_STREAMS: Lazy<Mutex<HashMap<String, usize>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Place held by an open event stream or poll, released when dropped
+struct StreamSlot {
+    user_id: String,
+}
+
+impl StreamSlot {
+    /// Take up a place for the given user, unless they already have too many open
+    fn acquire(user_id: &str) -> Result<StreamSlot> {
+        let mut open = OPEN_STREAMS.lock().unwrap();
+        let count = open.entry(user_id.to_string()).or_default();
+        if *count >= MAX_STREAMS_PER_USER {
+            return Err(Error::TooManyConnections {
+                max: MAX_STREAMS_PER_USER,
+            });
+        }
+
+        *count += 1;
+        Ok(StreamSlot {
+            user_id: user_id.to_string(),
+        })
+    }
+}
+
+impl Drop for StreamSlot {
+    fn drop(&mut self) {
+        let mut open = OPEN_STREAMS.lock().unwrap();
+        if let Some(count) = open.get_mut(&self.user_id) {
+            *count -= 1;
+
+            if *count == 0 {
+                open.remove(&self.user_id);
+            }
+        }
+    }
+}
+
+/// Minimal HTTP request, just enough to serve the fallback transports
+struct Request {
+    method: String,
+    path: String,
+    params: HashMap<String, String>,
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl Request {
+    /// Read a single request from the stream
+    async fn read(stream: &mut TcpStream) -> Option<Request> {
+        let mut buf = Vec::new();
+        let mut chunk = [0; 1024];
+
+        loop {
+            let read = stream.read(&mut chunk).await.ok()?;
+            if read == 0 {
+                return None;
+            }
+
+            buf.extend_from_slice(&chunk[..read]);
+
+            let mut headers = [httparse::EMPTY_HEADER; 32];
+            let mut request = httparse::Request::new(&mut headers);
+            match request.parse(&buf).ok()? {
+                httparse::Status::Complete(head) => {
+                    let (path, query) =
+                        request.path?.split_once('?').unwrap_or((request.path?, ""));
+
+                    let headers: HashMap<String, String> = request
+                        .headers
+                        .iter()
+                        .filter_map(|header| {
+                            Some((
+                                header.name.to_lowercase(),
+                                std::str::from_utf8(header.value).ok()?.to_string(),
+                            ))
+                        })
+                        .collect();
+
+                    let length: usize = headers
+                        .get("content-length")
+                        .and_then(|length| length.parse().ok())
+                        .unwrap_or_default();
+
+                    if length > MAX_BODY_SIZE {
+                        return None;
+                    }
+
+                    let mut body = buf[head..].to_vec();
+                    if body.len() < length {
+                        let mut rest = vec![0; length - body.len()];
+                        stream.read_exact(&mut rest).await.ok()?;
+                        body.extend(rest);
+                    }
+
+                    body.truncate(length);
+
+                    return Some(Request {
+                        method: request.method?.to_string(),
+                        path: path.to_string(),
+                        params: querystring::querify(query)
+                            .into_iter()
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .collect(),
+                        headers,
+                        body,
+                    });
+                }
+                httparse::Status::Partial => {
+                    if buf.len() > MAX_HEAD_SIZE {
+                        return None;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get a query parameter
+    fn param(&self, key: &str) -> Option<&str> {
+        self.params.get(key).map(String::as_str)
+    }
+
+    /// Get a header by its lowercase name
+    fn header(&self, key: &str) -> Option<&str> {
+        self.headers.get(key).map(String::as_str)
+    }
+
+    /// Whether member lists should be left out and loaded on demand
+    fn is_lazy(&self) -> bool {
+        self.param("lazy") == Some("true")
+    }
+
+    /// Intents declared by the client, if any
+    fn intents(&self) -> Option<u32> {
+        self.param("intents").map(Intent::parse_list)
+    }
+
+    /// Authenticate the user making this request
+    ///
+    /// The token is read from the `Authorization` or `X-Session-Token` header.
+    /// `EventSource` can't set headers, so the event stream also accepts it in the query.
+    async fn authenticate(&self, db: &'static Database) -> Result<User> {
+        let token = self
+            .header("authorization")
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .or_else(|| self.header("x-session-token"))
+            .or_else(|| match self.path.as_str() {
+                "/events" => self.param("token"),
+                _ => None,
+            })
+            .ok_or(Error::InvalidSession)?;
+
+        User::from_token(db, token, UserHint::Any).await
+    }
+
+    /// Headers allowing browsers to read the response, if the request came from an allowed origin
+    fn cors_headers(&self) -> String {
+        match self
+            .header("origin")
+            .filter(|origin| CORS_ORIGINS.iter().any(|allowed| allowed == origin))
+        {
+            Some(origin) => format!(
+                "Access-Control-Allow-Origin: {origin}\r\n\
+                Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+                Access-Control-Allow-Headers: Authorization, Content-Type, Last-Event-ID, X-Session-Token\r\n\
+                Vary: Origin\r\n"
+            ),
+            None => "Vary: Origin\r\n".to_string(),
+        }
+    }
+}
+
+/// Write a complete response with a JSON body
+async fn respond(stream: &mut TcpStream, request: &Request, status: &str, body: &str) {
+    let response = format!(
+        "HTTP/1.1 {status}\r\n\
+        Content-Type: application/json\r\n\
+        Content-Length: {}\r\n\
+        Cache-Control: no-store\r\n\
+        Connection: close\r\n\
+        {}\r\n\
+        {body}",
+        body.len(),
+        request.cors_headers()
+    );
+
+    stream.write_all(response.as_bytes()).await.ok();
+}
+
+/// Write a complete response with some serialisable data as the body
+async fn respond_json<T: Serialize>(
+    stream: &mut TcpStream,
+    request: &Request,
+    status: &str,
+    data: &T,
+) {
+    respond(
+        stream,
+        request,
+        status,
+        &serde_json::to_string(data).expect("Failed to serialise (as json)."),
+    )
+    .await;
+}
+
+/// Write an error response, picking a status code appropriate for the error
+async fn respond_error(stream: &mut TcpStream, request: &Request, err: Error) {
+    let status = match err {
+        Error::InvalidSession | Error::InvalidCredentials => "401 Unauthorized",
+        Error::NotFound => "404 Not Found",
+        Error::InvalidOperation => "400 Bad Request",
+        Error::TooManyConnections { .. } => "429 Too Many Requests",
+        _ => "500 Internal Server Error",
+    };
+
+    respond_json(stream, request, status, &err).await;
+}
+
+/// Write a single Server-Sent Event, returns whether the client is still there
+async fn send_event<T: Serialize>(stream: &mut TcpStream, data: &T, id: Option<&str>) -> bool {
+    let data = serde_json::to_string(data).expect("Failed to serialise (as json).");
+    let frame = match id {
+        Some(id) => format!("id: {id}\ndata: {data}\n\n"),
+        None => format!("data: {data}\n\n"),
+    };
+
+    stream.write_all(frame.as_bytes()).await.is_ok()
+}
+
+/// Spawn a new HTTP client worker given access to the database,
+/// the relevant TCP stream and the remote address of the client.
+pub fn spawn_client(db: &'static Database, mut stream: TcpStream, addr: SocketAddr) {
+    task::spawn(async move {
+        // Don't let clients hold on to the connection without ever finishing their request.
+        if let Ok(Some(request)) =
+            future::timeout(REQUEST_TIMEOUT, Request::read(&mut stream)).await
+        {
+            info!(
+                "User {addr:?} requested {} {}",
+                request.method, request.path
+            );
+
+            match (request.method.as_str(), request.path.as_str()) {
+                ("GET", "/events") => stream_events(db, &mut stream, &request).await,
+                ("GET", "/poll") => poll_events(db, &mut stream, &request).await,
+                ("POST", "/send") => send_message(db, &mut stream, &request).await,
+                ("OPTIONS", _) => {
+                    stream
+                        .write_all(
+                            format!("HTTP/1.1 204 No Content\r\n{}\r\n", request.cors_headers())
+                                .as_bytes(),
+                        )
+                        .await
+                        .ok();
+                }
+                _ => respond_error(&mut stream, &request, Error::NotFound).await,
+            }
+        }
+
+        info!("User disconnected from {addr:?}");
+    });
+}
+
+/// Spawn a new worker serving metrics to a client of the internal listener.
+pub fn spawn_metrics_client(mut stream: TcpStream) {
+    task::spawn(async move {
+        if let Ok(Some(request)) =
+            future::timeout(REQUEST_TIMEOUT, Request::read(&mut stream)).await
+        {
+            match (request.method.as_str(), request.path.as_str()) {
+                ("GET", "/metrics") => {
+                    let body = render();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\n\
+                        Content-Type: text/plain; version=0.0.4\r\n\
+                        Content-Length: {}\r\n\
+                        Connection: close\r\n\r\n\
+                        {body}",
+                        body.len()
+                    );
+
+                    stream.write_all(response.as_bytes()).await.ok();
+                }
+                _ => respond_error(&mut stream, &request, Error::NotFound).await,
+            }
+        }
+    });
+}
+
+/// Stream events to the client as Server-Sent Events
+///
+/// Each sequenced event carries `<session>:<seq>` as its id, so clients
+/// reconnecting with `Last-Event-ID` pick up the same session where they left off.
+async fn stream_events(db: &'static Database, stream: &mut TcpStream, request: &Request) {
+    let user = match request.authenticate(db).await {
+        Ok(user) => user,
+        Err(err) => return respond_error(stream, request, err).await,
+    };
+
+    let _slot = match StreamSlot::acquire(&user.id) {
+        Ok(slot) => slot,
+        Err(err) => return respond_error(stream, request, err).await,
+    };
+
+    let resume = request
+        .header("last-event-id")
+        .or_else(|| request.param("last_event_id"))
+        .and_then(|id| id.split_once(':'))
+        .and_then(|(session, seq)| Some((session.to_string(), seq.parse().ok()?)));
+
+    let resumed = match resume {
+        Some((id, seq)) => Session::resume(&id, &user.id, seq).await,
+        None => None,
+    };
+
+    let head = format!(
+        "HTTP/1.1 200 OK\r\n\
+        Content-Type: text/event-stream\r\n\
+        Cache-Control: no-cache\r\n\
+        X-Accel-Buffering: no\r\n\
+        {}\r\n",
+        request.cors_headers()
+    );
+
+    let mut attachment = if let Some(attachment) = resumed {
+        let id = attachment.session.id.clone();
+        if stream.write_all(head.as_bytes()).await.is_err()
+            || !send_event(stream, &EventV1::Resumed { id }, None).await
+        {
+            return attachment.detach().await;
+        }
+
+        attachment
+    } else if let Some((attachment, ready_payload)) =
+        start_session(db, user, request.is_lazy(), request.intents()).await
+    {
+        let id = attachment.session.id.clone();
+        if stream.write_all(head.as_bytes()).await.is_err()
+            || !send_event(stream, &EventV1::Session { id: id.clone() }, None).await
+            || !send_event(stream, &ready_payload, Some(&format!("{id}:0"))).await
+        {
+            return attachment.detach().await;
+        }
+
+        attachment
+    } else {
+        return respond_error(stream, request, Error::InternalError).await;
+    };
+
+    let id = attachment.session.id.clone();
+    EVENT_CONNECTIONS.with_label_values(&["sse"]).inc();
+
+    loop {
+        let connected = match future::timeout(KEEPALIVE_INTERVAL, attachment.events.next()).await {
+            Ok(Some((seq, event))) => {
+                EVENTS_DELIVERED.with_label_values(&["sse"]).inc();
+                send_event(
+                    stream,
+                    &Sequenced { event: &event, seq },
+                    Some(&format!("{id}:{seq}")),
+                )
+                .await
+            }
+            // Session was closed.
+            Ok(None) => break,
+            // Nothing happened for a while, send a comment to keep the connection alive.
+            Err(_) => stream.write_all(b": keepalive\n\n").await.is_ok(),
+        };
+
+        if !connected {
+            break;
+        }
+    }
+
+    EVENT_CONNECTIONS.with_label_values(&["sse"]).dec();
+
+    // Keep the session around so the client can reconnect.
+    attachment.detach().await;
+}
+
+/// Respond with any events the client missed, waiting for some to arrive if needed
+///
+/// Without a session, a new one is started and the Ready payload is returned straight away.
+/// Otherwise, the client passes its session along with the last `seq` it received.
+async fn poll_events(db: &'static Database, stream: &mut TcpStream, request: &Request) {
+    let user = match request.authenticate(db).await {
+        Ok(user) => user,
+        Err(err) => return respond_error(stream, request, err).await,
+    };
+
+    let _slot = match StreamSlot::acquire(&user.id) {
+        Ok(slot) => slot,
+        Err(err) => return respond_error(stream, request, err).await,
+    };
+
+    let resume = request
+        .param("session")
+        .zip(request.param("seq").and_then(|seq| seq.parse().ok()));
+
+    let mut attachment: Attachment = if let Some((id, seq)) = resume {
+        match Session::resume(id, &user.id, seq).await {
+            Some(attachment) => attachment,
+            None => return respond_error(stream, request, Error::NotFound).await,
+        }
+    } else {
+        match start_session(db, user, request.is_lazy(), request.intents()).await {
+            Some((attachment, ready_payload)) => {
+                let session = EventV1::Session {
+                    id: attachment.session.id.clone(),
+                };
+
+                attachment.detach().await;
+                return respond_json(stream, request, "200 OK", &[session, ready_payload]).await;
+            }
+            None => return respond_error(stream, request, Error::InternalError).await,
+        }
+    };
+
+    let mut events = vec![];
+    if let Ok(Some(event)) = future::timeout(POLL_TIMEOUT, attachment.events.next()).await {
+        events.push(event);
+
+        // Take anything else which is already waiting.
+        while let Ok(Some(event)) = attachment.events.try_next() {
+            events.push(event);
+        }
+    }
+
+    attachment.detach().await;
+
+    EVENTS_DELIVERED
+        .with_label_values(&["poll"])
+        .inc_by(events.len() as u64);
+
+    let events = events
+        .iter()
+        .map(|(seq, event)| Sequenced { event, seq: *seq })
+        .collect::<Vec<Sequenced>>();
+
+    respond_json(stream, request, "200 OK", &events).await;
+}
+
+/// Accept a message from a client which can't send anything over its event stream
+async fn send_message(db: &'static Database, stream: &mut TcpStream, request: &Request) {
+    let user = match request.authenticate(db).await {
+        Ok(user) => user,
+        Err(err) => return respond_error(stream, request, err).await,
+    };
+
+    let session = match request.param("session") {
+        Some(id) => Session::find(id, &user.id).await,
+        None => None,
+    };
+
+    let session = match session {
+        Some(session) => session,
+        None => return respond_error(stream, request, Error::NotFound).await,
+    };
+
+    match serde_json::from_slice::<ClientMessage>(&request.body) {
+        Ok(message) => {
+            handle_message(&session, message).await;
+            stream
+                .write_all(
+                    format!("HTTP/1.1 204 No Content\r\n{}\r\n", request.cors_headers()).as_bytes(),
+                )
+                .await
+                .ok();
+        }
+        Err(_) => respond_error(stream, request, Error::InvalidOperation).await,
+    }
+}
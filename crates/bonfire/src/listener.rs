@@ -0,0 +1,230 @@
+use std::{sync::Arc, time::Duration};
+
+use async_std::task;
+use futures::{channel::mpsc::UnboundedReceiver, pin_mut, select, FutureExt, StreamExt};
+use revolt_quark::{
+    events::{
+        bus::event_bus,
+        client::EventV1,
+        server::ClientMessage,
+        state::{State, SubscriptionStateChange},
+    },
+    models::User,
+    presence::{
+        presence_create_session, presence_delete_session, presence_update_session, SessionPresence,
+    },
+    Database,
+};
+
+use crate::session::{Attachment, Session};
+
+/// How often an idle session listener checks whether it has expired
+const EXPIRY_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Create a new session for an authenticated user
+///
+/// Returns the attachment along with the Ready payload, which the
+/// transport should send after announcing the session.
+pub async fn start_session(
+    db: &'static Database,
+    user: User,
+    lazy: bool,
+    intents: Option<u32>,
+) -> Option<(Attachment, EventV1)> {
+    // Create local state.
+    let is_bot = user.bot.is_some();
+    let mut state = State::from(user);
+    state.lazy = lazy;
+
+    // Only bots may opt out of events.
+    if is_bot {
+        if let Some(intents) = intents {
+            state.intents = intents;
+        }
+    }
+    let user_id = state.cache.user_id.clone();
+
+    // Download required data to local cache and build Ready payload.
+    if let Ok(ready_payload) = state.generate_ready_payload(db).await {
+        // Create presence session, notifying other users if we just came online.
+        let (_, presence_id) = state
+            .change_presence(presence_create_session(&user_id, 0))
+            .await;
+
+        let (attachment, requests) = Session::create(&user_id).await;
+
+        task::spawn(listen(
+            db,
+            attachment.session.clone(),
+            state,
+            presence_id,
+            requests,
+        ));
+
+        Some((attachment, ready_payload))
+    } else {
+        None
+    }
+}
+
+/// Act on a message sent by the client, regardless of transport
+///
+/// Transport specific messages such as pings are ignored.
+pub async fn handle_message(session: &Session, message: ClientMessage) {
+    match message {
+        ClientMessage::BeginTyping { channel } => {
+            EventV1::ChannelStartTyping {
+                id: channel.clone(),
+                user: session.user_id.clone(),
+            }
+            .p(channel.clone())
+            .await;
+        }
+        ClientMessage::EndTyping { channel } => {
+            EventV1::ChannelStopTyping {
+                id: channel.clone(),
+                user: session.user_id.clone(),
+            }
+            .p(channel.clone())
+            .await;
+        }
+        ClientMessage::SetPresence { .. }
+        | ClientMessage::SetIdle { .. }
+        | ClientMessage::SubscribeMemberList { .. } => session.request(message),
+        _ => {}
+    }
+}
+
+/// Listen for events and requests relevant to a session until it is closed or expires
+async fn listen(
+    db: &'static Database,
+    session: Arc<Session>,
+    mut state: State,
+    presence_id: u8,
+    mut requests: UnboundedReceiver<ClientMessage>,
+) {
+    // Presence chosen by the client for this session.
+    let mut session_presence = SessionPresence::default();
+
+    // Subscribed member lists let us know when they change.
+    let mut member_list_changes = state.watch_member_lists();
+
+    // Create a subscriber to poll on.
+    if let Ok(mut conn) = event_bus().subscriber().await {
+        loop {
+            // Check for state changes for subscriptions.
+            match state.apply_state() {
+                SubscriptionStateChange::Reset => {
+                    for id in state.iter_subscriptions() {
+                        conn.subscribe(id).await;
+                    }
+
+                    #[cfg(debug_assertions)]
+                    info!("Session {} has reset their subscriptions", session.id);
+                }
+                SubscriptionStateChange::Change { add, remove } => {
+                    for id in remove {
+                        #[cfg(debug_assertions)]
+                        info!("Session {} unsubscribing from {id}", session.id);
+
+                        conn.unsubscribe(&id).await;
+                    }
+
+                    for id in add {
+                        #[cfg(debug_assertions)]
+                        info!("Session {} subscribing to {id}", session.id);
+
+                        conn.subscribe(&id).await;
+                    }
+                }
+                SubscriptionStateChange::None => {}
+            }
+
+            // Handle incoming events and requests, periodically waking up
+            // to check whether the client has been gone for too long.
+            {
+                let message = conn.next().fuse();
+                let request = requests.next().fuse();
+                let member_list = member_list_changes.next().fuse();
+                let tick = task::sleep(EXPIRY_CHECK_INTERVAL).fuse();
+                pin_mut!(message, request, member_list, tick);
+
+                select!(
+                    item = message => match item {
+                        Some((_, mut event)) => {
+                            if state.handle_incoming_event_v1(db, &mut event).await {
+                                session.push(event).await;
+                            }
+                        }
+                        // No more data, assume we disconnected or otherwise
+                        // something bad occurred, so disconnect user.
+                        None => break,
+                    },
+                    request = request => match request {
+                        Some(ClientMessage::SetPresence { presence }) => {
+                            session_presence.presence = presence;
+                            state
+                                .change_presence(
+                                    presence_update_session(
+                                        &session.user_id,
+                                        presence_id,
+                                        &session_presence,
+                                    ),
+                                )
+                                .await;
+                        }
+                        Some(ClientMessage::SetIdle { idle }) => {
+                            session_presence.idle = idle;
+                            state
+                                .change_presence(
+                                    presence_update_session(
+                                        &session.user_id,
+                                        presence_id,
+                                        &session_presence,
+                                    ),
+                                )
+                                .await;
+                        }
+                        Some(ClientMessage::SubscribeMemberList { server, ranges }) => {
+                            match state.subscribe_member_list(db, &server, ranges).await {
+                                Ok(Some(event)) => session.push(event).await,
+                                Ok(None) => {}
+                                Err(err) => warn!(
+                                    "Session {} failed to subscribe to member list of {server}: {err:?}",
+                                    session.id
+                                ),
+                            }
+                        }
+                        // The session was terminated, which the expiry check below picks up.
+                        None => {}
+                        _ => {}
+                    },
+                    server = member_list => if let Some(server) = server {
+                        match state.sync_member_list(db, &server).await {
+                            Ok(Some(event)) => session.push(event).await,
+                            Ok(None) => {}
+                            Err(err) => warn!(
+                                "Session {} failed to update member list of {server}: {err:?}",
+                                session.id
+                            ),
+                        }
+                    },
+                    () = tick => {}
+                );
+            }
+
+            if session.expired().await {
+                info!("Session {} expired without being resumed", session.id);
+                break;
+            }
+        }
+    }
+
+    // Drop the session along with any connection still attached to it.
+    session.close().await;
+
+    // Clean up presence session, notifying other users if we just went offline.
+    state
+        .change_presence(presence_delete_session(&session.user_id, presence_id))
+        .await;
+}
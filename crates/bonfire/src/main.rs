@@ -1,6 +1,6 @@
 use std::env;
 
-use async_std::net::TcpListener;
+use async_std::{net::TcpListener, task};
 use revolt_quark::presence::presence_clear_region;
 
 #[macro_use]
@@ -9,17 +9,49 @@ extern crate log;
 pub mod config;
 
 mod database;
+mod http;
+mod listener;
+mod session;
 mod websocket;
 
 #[async_std::main]
 async fn main() {
     // Configure requirements for Bonfire.
     revolt_quark::configure!();
+    revolt_quark::events::bus::configure_event_bus();
+    revolt_quark::presence::configure_presence_store();
     database::connect().await;
 
     // Clean up the current region information.
     presence_clear_region(None).await;
 
+    // Setup a TCP listener for clients which can't use WebSockets,
+    // serving events over Server-Sent Events and long-polling instead.
+    // By default, we bind to port 9001 on all interfaces.
+    let http_bind = env::var("HTTP_HOST").unwrap_or_else(|_| "0.0.0.0:9001".into());
+    info!("Listening for HTTP clients on host {http_bind}");
+    let http_listener = TcpListener::bind(http_bind).await.expect("Failed to bind");
+
+    task::spawn(async move {
+        while let Ok((stream, addr)) = http_listener.accept().await {
+            http::spawn_client(database::get_db(), stream, addr);
+        }
+    });
+
+    // Serve metrics on a separate listener which should not be publicly reachable.
+    // By default, we bind to port 9002 on the loopback interface.
+    let metrics_bind = env::var("METRICS_HOST").unwrap_or_else(|_| "127.0.0.1:9002".into());
+    info!("Serving metrics on host {metrics_bind}");
+    let metrics_listener = TcpListener::bind(metrics_bind)
+        .await
+        .expect("Failed to bind");
+
+    task::spawn(async move {
+        while let Ok((stream, _)) = metrics_listener.accept().await {
+            http::spawn_metrics_client(stream);
+        }
+    });
+
     // Setup a TCP listener to accept WebSocket connections on.
     // By default, we bind to port 9000 on all interfaces.
     let bind = env::var("HOST").unwrap_or_else(|_| "0.0.0.0:9000".into());
@@ -0,0 +1,223 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use async_std::sync::Mutex;
+use futures::channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender};
+use once_cell::sync::Lazy;
+use revolt_quark::events::{client::EventV1, server::ClientMessage};
+use serde::Serialize;
+use ulid::Ulid;
+
+/// How long a session can be resumed for after its connection drops
+pub const RESUME_WINDOW: Duration = Duration::from_secs(120);
+
+/// Maximum number of events kept around for replay
+pub const REPLAY_BUFFER_SIZE: usize = 1_000;
+
+/// Event along with its position in the session
+pub type SequencedEvent = (u64, EventV1);
+
+/// Event tagged with its sequence number for sending to the client
+#[derive(Serialize)]
+pub struct Sequenced<'a> {
+    #[serde(flatten)]
+    pub event: &'a EventV1,
+    pub seq: u64,
+}
+
+/// Mutable session information
+struct Inner {
+    /// Sequence number of the latest event
+    seq: u64,
+    /// Most recent events, oldest first
+    buffer: VecDeque<SequencedEvent>,
+    /// Connection currently receiving events, if any
+    sink: Option<UnboundedSender<SequencedEvent>>,
+    /// Incremented whenever a new connection takes over the session
+    generation: u64,
+    /// Time at which the last connection went away
+    detached_at: Option<Instant>,
+    /// Whether the session was given up on and should not be resumed
+    terminated: bool,
+}
+
+/// Session which outlives individual connections for a short period of time
+pub struct Session {
+    pub id: String,
+    pub user_id: String,
+    inner: Mutex<Inner>,
+    requests: UnboundedSender<ClientMessage>,
+}
+
+/// Connection attached to a session
+pub struct Attachment {
+    pub session: Arc<Session>,
+    pub events: UnboundedReceiver<SequencedEvent>,
+    generation: u64,
+}
+
+/// All sessions currently held by this node
+static SESSIONS: Lazy<Mutex<HashMap<String, Arc<Session>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+impl Session {
+    /// Create a new session for a user and attach to it
+    ///
+    /// Also returns the receiving end for requests made by connections to this session.
+    pub async fn create(user_id: &str) -> (Attachment, UnboundedReceiver<ClientMessage>) {
+        let (sink, events) = unbounded();
+        let (requests, receiver) = unbounded();
+        let session = Arc::new(Session {
+            id: Ulid::new().to_string(),
+            user_id: user_id.to_string(),
+            inner: Mutex::new(Inner {
+                seq: 0,
+                buffer: VecDeque::new(),
+                sink: Some(sink),
+                generation: 0,
+                detached_at: None,
+                terminated: false,
+            }),
+            requests,
+        });
+
+        SESSIONS
+            .lock()
+            .await
+            .insert(session.id.clone(), session.clone());
+
+        (
+            Attachment {
+                session,
+                events,
+                generation: 0,
+            },
+            receiver,
+        )
+    }
+
+    /// Take over an existing session, replaying any events after `seq`
+    ///
+    /// Fails if the session doesn't exist, belongs to someone else
+    /// or no longer holds every event the client missed.
+    pub async fn resume(id: &str, user_id: &str, seq: u64) -> Option<Attachment> {
+        let session = SESSIONS.lock().await.get(id).cloned()?;
+        if session.user_id != user_id {
+            return None;
+        }
+
+        let mut inner = session.inner.lock().await;
+        if inner.terminated {
+            return None;
+        }
+
+        let oldest = inner.buffer.front().map_or(inner.seq + 1, |(seq, _)| *seq);
+        if seq > inner.seq || seq + 1 < oldest {
+            return None;
+        }
+
+        let (sink, events) = unbounded();
+        for (position, event) in &inner.buffer {
+            if *position > seq {
+                sink.unbounded_send((*position, event.clone())).ok();
+            }
+        }
+
+        inner.sink = Some(sink);
+        inner.generation += 1;
+        inner.detached_at = None;
+
+        let generation = inner.generation;
+        drop(inner);
+
+        Some(Attachment {
+            session,
+            events,
+            generation,
+        })
+    }
+
+    /// Find an existing session belonging to the given user without attaching to it
+    pub async fn find(id: &str, user_id: &str) -> Option<Arc<Session>> {
+        SESSIONS
+            .lock()
+            .await
+            .get(id)
+            .filter(|session| session.user_id == user_id)
+            .cloned()
+    }
+
+    /// Record a new event and forward it to the current connection
+    pub async fn push(&self, event: EventV1) {
+        let mut inner = self.inner.lock().await;
+        inner.seq += 1;
+
+        let seq = inner.seq;
+        if let Some(sink) = &inner.sink {
+            if sink.unbounded_send((seq, event.clone())).is_err() {
+                inner.sink = None;
+                inner.detached_at = Some(Instant::now());
+            }
+        }
+
+        inner.buffer.push_back((seq, event));
+        if inner.buffer.len() > REPLAY_BUFFER_SIZE {
+            inner.buffer.pop_front();
+        }
+    }
+
+    /// Pass a request from the connection on to the session's listener
+    pub fn request(&self, message: ClientMessage) {
+        self.requests.unbounded_send(message).ok();
+    }
+
+    /// Check whether this session has gone without a connection for too long
+    pub async fn expired(&self) -> bool {
+        let inner = self.inner.lock().await;
+        inner.terminated
+            || inner
+                .detached_at
+                .map_or(false, |at| at.elapsed() > RESUME_WINDOW)
+    }
+
+    /// Remove this session, disconnecting any attached connection
+    pub async fn close(&self) {
+        SESSIONS.lock().await.remove(&self.id);
+        self.inner.lock().await.sink = None;
+    }
+}
+
+impl Attachment {
+    /// Detach this connection from its session
+    ///
+    /// Does nothing if another connection has since taken over.
+    pub async fn detach(self) {
+        let mut inner = self.session.inner.lock().await;
+        if inner.generation == self.generation {
+            inner.sink = None;
+            inner.detached_at = Some(Instant::now());
+        }
+    }
+
+    /// Detach this connection and give up on the session entirely
+    ///
+    /// Used for connections that closed or stopped responding, the session's
+    /// listener is woken up to clean it up straight away instead of waiting
+    /// for it to be resumed.
+    /// Does nothing if another connection has since taken over.
+    pub async fn terminate(self) {
+        let mut inner = self.session.inner.lock().await;
+        if inner.generation == self.generation {
+            inner.sink = None;
+            inner.detached_at = Some(Instant::now());
+            inner.terminated = true;
+
+            // Closing the request channel wakes the listener, which then sees
+            // the session has expired.
+            self.session.requests.close_channel();
+        }
+    }
+}
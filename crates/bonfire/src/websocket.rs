@@ -1,20 +1,48 @@
-use std::net::SocketAddr;
+use std::{
+    net::SocketAddr,
+    time::{Duration, Instant},
+};
 
+use async_tungstenite::tungstenite::{
+    protocol::{frame::coding::CloseCode, CloseFrame},
+    Message,
+};
 use futures::{channel::oneshot, pin_mut, select, FutureExt, SinkExt, StreamExt, TryStreamExt};
 use revolt_quark::{
-    events::{
-        client::EventV1,
-        server::ClientMessage,
-        state::{State, SubscriptionStateChange},
-    },
+    events::{client::EventV1, server::ClientMessage},
+    metrics::{EVENTS_DELIVERED, EVENT_CONNECTIONS},
     models::{user::UserHint, User},
-    presence::{presence_create_session, presence_delete_session},
-    redis_kiss, Database,
+    Database,
+};
+
+use async_std::{future::timeout, net::TcpStream, sync::Mutex, task};
+
+use crate::{
+    config::WebsocketHandshakeCallback,
+    listener::{handle_message, start_session},
+    session::{Sequenced, Session},
 };
 
-use async_std::{net::TcpStream, sync::Mutex, task};
+/// How often clients are asked to send a ping
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How long a connection may go without pinging before it is considered dead
+///
+/// Leaves some headroom over the advertised interval for latency.
+const HEARTBEAT_TIMEOUT: Duration = Duration::from_secs(45);
 
-use crate::config::WebsocketHandshakeCallback;
+/// Close code sent to connections which stopped sending heartbeats or never authenticated
+const HEARTBEAT_TIMEOUT_CODE: u16 = 4000;
+
+/// Reason a connection attached to a session went away
+enum Disconnect {
+    /// Client sent a close frame
+    Closed,
+    /// Connection dropped or failed without closing
+    Dropped,
+    /// Client stopped sending heartbeats
+    TimedOut,
+}
 
 /// Spawn a new WebSocket client worker given access to the database,
 /// the relevant TCP stream and the remote address of the client.
@@ -25,7 +53,7 @@ pub fn spawn_client(db: &'static Database, stream: TcpStream, addr: SocketAddr)
 
         // Upgrade the TCP connection to a WebSocket connection.
         // In this process, we also parse any additional parameters given.
-        // e.g. wss://example.com?format=json&version=1
+        // e.g. wss://example.com?format=json&version=1&compress=zlib-stream
         let (sender, receiver) = oneshot::channel();
         if let Ok(ws) = async_tungstenite::accept_hdr_async_with_config(
             stream,
@@ -34,12 +62,15 @@ pub fn spawn_client(db: &'static Database, stream: TcpStream, addr: SocketAddr)
         )
         .await
         {
+            EVENT_CONNECTIONS.with_label_values(&["websocket"]).inc();
+
             // Verify we've received a valid config, otherwise we should just drop the connection.
             if let Ok(mut config) = receiver.await {
                 info!(
-                    "User {addr:?} provided protocol configuration (version = {}, format = {:?})",
+                    "User {addr:?} provided protocol configuration (version = {}, format = {:?}, compression = {:?})",
                     config.get_protocol_version(),
-                    config.get_protocol_format()
+                    config.get_protocol_format(),
+                    config.get_protocol_compression()
                 );
 
                 // Split the socket for simultaneously read and write.
@@ -47,14 +78,42 @@ pub fn spawn_client(db: &'static Database, stream: TcpStream, addr: SocketAddr)
                 let write = Mutex::new(write);
 
                 // If the user has not provided authentication, request information.
+                // A closed or failed stream leaves us without a token, which disconnects the client.
                 if config.get_session_token().is_none() {
-                    'outer: while let Ok(message) = read.try_next().await {
-                        if let Ok(ClientMessage::Authenticate { token }) =
-                            config.decode(message.as_ref().unwrap())
-                        {
-                            config.set_session_token(token);
-                            break 'outer;
+                    let identified = timeout(HEARTBEAT_TIMEOUT, async {
+                        while let Ok(Some(message)) = read.try_next().await {
+                            match config.decode(&message) {
+                                Ok(ClientMessage::Authenticate { token }) => {
+                                    config.set_session_token(token);
+                                    break;
+                                }
+                                Ok(ClientMessage::Resume {
+                                    token,
+                                    session,
+                                    seq,
+                                }) => {
+                                    config.set_session_token(token);
+                                    config.set_resume(session, seq);
+                                    break;
+                                }
+                                _ => {}
+                            }
                         }
+                    })
+                    .await;
+
+                    if identified.is_err() {
+                        info!("User {addr:?} did not authenticate in time");
+
+                        write
+                            .lock()
+                            .await
+                            .send(Message::Close(Some(CloseFrame {
+                                code: CloseCode::from(HEARTBEAT_TIMEOUT_CODE),
+                                reason: "Authentication timed out".into(),
+                            })))
+                            .await
+                            .ok();
                     }
                 }
 
@@ -64,172 +123,180 @@ pub fn spawn_client(db: &'static Database, stream: TcpStream, addr: SocketAddr)
                         Ok(user) => {
                             info!("User {addr:?} authenticated as @{}", user.username);
 
-                            // Create local state.
-                            let mut state = State::from(user);
-                            let user_id = state.cache.user_id.clone();
-
-                            // Create presence session.
-                            let (first_session, session_id) =
-                                presence_create_session(&user_id, 0).await;
-
                             // Notify socket we have authenticated.
                             write
                                 .lock()
                                 .await
-                                .send(config.encode(&EventV1::Authenticated))
+                                .send(config.encode(&EventV1::Authenticated {
+                                    heartbeat: HEARTBEAT_INTERVAL.as_secs(),
+                                }))
                                 .await
                                 .ok();
 
-                            // Download required data to local cache and send Ready payload.
-                            if let Ok(ready_payload) = state.generate_ready_payload(db).await {
+                            // Pick up where a previous connection left off if possible,
+                            // otherwise start over with a fresh session.
+                            let resumed = match config.get_resume() {
+                                Some((id, seq)) => Session::resume(id, &user.id, *seq).await,
+                                None => None,
+                            };
+
+                            let attachment = if let Some(attachment) = resumed {
+                                info!("User {addr:?} resumed session {}", attachment.session.id);
+
                                 write
                                     .lock()
                                     .await
-                                    .send(config.encode(&ready_payload))
+                                    .send(config.encode(&EventV1::Resumed {
+                                        id: attachment.session.id.clone(),
+                                    }))
                                     .await
                                     .ok();
 
-                                // If this was the first session, notify other users that we just went online.
-                                if first_session {
-                                    state.broadcast_presence_change(true).await;
-                                }
+                                Some(attachment)
+                            } else if let Some((attachment, ready_payload)) =
+                                start_session(db, user, config.is_lazy(), *config.get_intents())
+                                    .await
+                            {
+                                let mut write = write.lock().await;
+                                write
+                                    .send(config.encode(&EventV1::Session {
+                                        id: attachment.session.id.clone(),
+                                    }))
+                                    .await
+                                    .ok();
 
-                                // Create a PubSub connection to poll on.
-                                let listener = async {
-                                    if let Ok(mut conn) = redis_kiss::open_pubsub_connection().await
-                                    {
-                                        loop {
-                                            // Check for state changes for subscriptions.
-                                            match state.apply_state() {
-                                                SubscriptionStateChange::Reset => {
-                                                    for id in state.iter_subscriptions() {
-                                                        conn.subscribe(id).await.unwrap();
-                                                    }
+                                write.send(config.encode(&ready_payload)).await.ok();
+                                Some(attachment)
+                            } else {
+                                None
+                            };
 
-                                                    #[cfg(debug_assertions)]
-                                                    info!("{addr:?} has reset their subscriptions");
-                                                }
-                                                SubscriptionStateChange::Change { add, remove } => {
-                                                    for id in remove {
-                                                        #[cfg(debug_assertions)]
-                                                        info!("{addr:?} unsubscribing from {id}");
+                            if let Some(mut attachment) = attachment {
+                                let session = attachment.session.clone();
+                                let last_heartbeat = Mutex::new(Instant::now());
 
-                                                        conn.unsubscribe(id).await.unwrap();
-                                                    }
+                                let disconnect = {
+                                    let events = &mut attachment.events;
 
-                                                    for id in add {
-                                                        #[cfg(debug_assertions)]
-                                                        info!("{addr:?} subscribing to {id}");
+                                    // Forward session events to the socket.
+                                    let listener = async {
+                                        while let Some((seq, event)) = events.next().await {
+                                            if write
+                                                .lock()
+                                                .await
+                                                .send(
+                                                    config
+                                                        .encode(&Sequenced { event: &event, seq }),
+                                                )
+                                                .await
+                                                .is_err()
+                                            {
+                                                break;
+                                            }
 
-                                                        conn.subscribe(id).await.unwrap();
-                                                    }
-                                                }
-                                                SubscriptionStateChange::None => {}
+                                            EVENTS_DELIVERED
+                                                .with_label_values(&["websocket"])
+                                                .inc();
+                                        }
+                                    }
+                                    .fuse();
+
+                                    // Read from WebSocket stream.
+                                    let worker = async {
+                                        while let Ok(Some(msg)) = read.try_next().await {
+                                            if msg.is_close() {
+                                                return true;
                                             }
 
-                                            // * Debug logging of current subscriptions.
-                                            /*#[cfg(debug_assertions)]
-                                            info!(
-                                                "User {addr:?} is subscribed to {:?}",
-                                                state
-                                                    .iter_subscriptions()
-                                                    .collect::<Vec<&String>>()
-                                            );*/
-
-                                            // Handle incoming events.
-                                            match conn.on_message().next().await.map(|item| {
-                                                (
-                                                    item.get_channel_name().to_string(),
-                                                    redis_kiss::decode_payload::<EventV1>(&item),
-                                                )
-                                            }) {
-                                                Some((channel, item)) => {
-                                                    if let Ok(mut event) = item {
-                                                        if state
-                                                            .handle_incoming_event_v1(
-                                                                db, &mut event,
+                                            if let Ok(payload) = config.decode(&msg) {
+                                                if let ClientMessage::Ping { data, responded } =
+                                                    payload
+                                                {
+                                                    *last_heartbeat.lock().await = Instant::now();
+
+                                                    if responded.is_none() {
+                                                        write
+                                                            .lock()
+                                                            .await
+                                                            .send(
+                                                                config.encode(&EventV1::Pong {
+                                                                    data,
+                                                                }),
                                                             )
                                                             .await
-                                                            && write.lock().await
-                                                                .send(config.encode(&event))
-                                                                .await
-                                                                .is_err()
-                                                        {
-                                                            break;
-                                                        }
-                                                    } else {
-                                                        warn!("Failed to deserialise an event for {channel}!");
+                                                            .ok();
                                                     }
+                                                } else {
+                                                    handle_message(&session, payload).await;
                                                 }
-                                                // No more data, assume we disconnected or otherwise
-                                                // something bad occurred, so disconnect user.
-                                                None => break,
                                             }
                                         }
+
+                                        false
                                     }
-                                }
-                                .fuse();
+                                    .fuse();
 
-                                // Read from WebSocket stream.
-                                let worker =
-                                    async {
-                                        while let Ok(Some(msg)) = read.try_next().await {
-                                            if let Ok(payload) = config.decode(&msg) {
-                                                match payload {
-                                                    ClientMessage::BeginTyping { channel } => {
-                                                        EventV1::ChannelStartTyping {
-                                                            id: channel.clone(),
-                                                            user: user_id.clone(),
-                                                        }
-                                                        .p(channel.clone())
-                                                        .await;
-                                                    }
-                                                    ClientMessage::EndTyping { channel } => {
-                                                        EventV1::ChannelStopTyping {
-                                                            id: channel.clone(),
-                                                            user: user_id.clone(),
-                                                        }
-                                                        .p(channel.clone())
-                                                        .await;
-                                                    }
-                                                    ClientMessage::Ping { data, responded } => {
-                                                        if responded.is_none() {
-                                                            write
-                                                                .lock()
-                                                                .await
-                                                                .send(config.encode(
-                                                                    &EventV1::Pong { data },
-                                                                ))
-                                                                .await
-                                                                .ok();
-                                                        }
-                                                    }
-                                                    _ => {}
-                                                }
+                                    // Watch for the client going quiet.
+                                    let watchdog = async {
+                                        loop {
+                                            let elapsed = last_heartbeat.lock().await.elapsed();
+                                            if elapsed >= HEARTBEAT_TIMEOUT {
+                                                break;
                                             }
+
+                                            task::sleep(HEARTBEAT_TIMEOUT - elapsed).await;
                                         }
                                     }
                                     .fuse();
 
-                                // Pin both tasks.
-                                pin_mut!(listener, worker);
+                                    // Pin all tasks.
+                                    pin_mut!(listener, worker, watchdog);
 
-                                // Wait for either disconnect or for listener to die.
-                                select!(
-                                    () = listener => {},
-                                    () = worker => {}
-                                );
+                                    // Wait for either disconnect, for listener to die or for heartbeats to stop.
+                                    select!(
+                                        () = listener => Disconnect::Dropped,
+                                        closed = worker => if closed {
+                                            Disconnect::Closed
+                                        } else {
+                                            Disconnect::Dropped
+                                        },
+                                        () = watchdog => Disconnect::TimedOut
+                                    )
+                                };
 
-                                // * Combine the streams back once we are ready to disconnect.
-                                /* ws = read.reunite(write).unwrap(); */
-                            }
+                                match disconnect {
+                                    Disconnect::Closed => {
+                                        info!("User {addr:?} closed their connection");
 
-                            // Clean up presence session.
-                            let last_session = presence_delete_session(&user_id, session_id).await;
+                                        // The client is done with this session, so
+                                        // clean it up rather than wait for a resume.
+                                        attachment.terminate().await;
+                                    }
+                                    Disconnect::Dropped => {
+                                        // Keep the session around so the client can resume it.
+                                        attachment.detach().await;
+                                    }
+                                    Disconnect::TimedOut => {
+                                        info!("User {addr:?} stopped sending heartbeats");
 
-                            // If this was the last session, notify other users that we just went offline.
-                            if last_session {
-                                state.broadcast_presence_change(false).await;
+                                        write
+                                            .lock()
+                                            .await
+                                            .send(Message::Close(Some(CloseFrame {
+                                                code: CloseCode::from(HEARTBEAT_TIMEOUT_CODE),
+                                                reason: "Heartbeat timed out".into(),
+                                            })))
+                                            .await
+                                            .ok();
+
+                                        // The connection is most likely half-open, so
+                                        // clean up the session rather than wait for a resume.
+                                        attachment.terminate().await;
+                                    }
+                                }
+
+                                // * Combine the streams back once we are ready to disconnect.
+                                /* ws = read.reunite(write).unwrap(); */
                             }
                         }
                         Err(err) => {
@@ -239,6 +306,8 @@ pub fn spawn_client(db: &'static Database, stream: TcpStream, addr: SocketAddr)
                 }
             }
 
+            EVENT_CONNECTIONS.with_label_values(&["websocket"]).dec();
+
             // * Disconnect the WebSocket if it isn't already.
             /*ws.close(Some(CloseFrame {
                 code: CloseCode::Normal,
@@ -21,19 +21,36 @@ async fn rocket() -> _ {
     // Ensure environment variables are present
     revolt_quark::variables::delta::preflight_checks();
 
+    // Select where events are published and presence is kept
+    revolt_quark::events::bus::configure_event_bus();
+    revolt_quark::presence::configure_presence_store();
+
     // Setup database
     let db = DatabaseInfo::Auto.connect().await.unwrap();
+
+    // Accounts are stored through rAuth, which doesn't support every database.
+    let auth_database = match db.clone().try_into() {
+        Ok(database) => database,
+        Err(err) => {
+            log::error!("{err} Set MONGODB to run the API.");
+            std::process::exit(1);
+        }
+    };
+
     db.migrate_database().await.unwrap();
 
     // Setup rAuth
     let rauth = RAuth {
-        database: db.clone().into(),
+        database: auth_database,
         config: revolt_quark::util::rauth::config(),
     };
 
     // Launch background task workers.
     async_std::task::spawn(revolt_quark::tasks::start_workers(db.clone()));
 
+    // Serve metrics on a separate listener which should not be publicly reachable.
+    rocket::tokio::spawn(revolt_quark::web::metrics::serve());
+
     // Configure CORS
     let cors = revolt_quark::web::cors::new();
 
@@ -46,6 +63,7 @@ async fn rocket() -> _ {
         .manage(rauth)
         .manage(db)
         .manage(cors.clone())
+        .attach(revolt_quark::web::metrics::MetricsFairing)
         .attach(revolt_quark::web::ratelimiter::RatelimitFairing)
         .attach(cors)
 }
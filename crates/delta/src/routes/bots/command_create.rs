@@ -0,0 +1,87 @@
+use crate::util::regex::RE_COMMAND;
+
+use revolt_quark::{
+    models::{bot_command::CommandOption, BotCommand, User},
+    Db, Error, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+/// # Command Details
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct DataCreateCommand {
+    /// Command name
+    #[validate(length(min = 1, max = 32), regex = "RE_COMMAND")]
+    name: String,
+    /// Command description
+    #[validate(length(min = 1, max = 100))]
+    description: String,
+    /// Server to register this command in
+    ///
+    /// Leave empty to register a global command.
+    server: Option<String>,
+    /// Options accepted by this command
+    #[validate(length(min = 0, max = 25))]
+    options: Option<Vec<CommandOption>>,
+}
+
+/// # Create Command
+///
+/// Register a new command for a bot you own.
+#[openapi(tag = "Bots")]
+#[post("/<target>/commands", data = "<data>")]
+pub async fn create_command(
+    db: &Db,
+    user: User,
+    target: Ref,
+    data: Json<DataCreateCommand>,
+) -> Result<Json<BotCommand>> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let data = data.into_inner();
+    data.validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let options = data.options.unwrap_or_default();
+    for option in &options {
+        option
+            .validate()
+            .map_err(|error| Error::FailedValidation { error })?;
+    }
+
+    let bot = target.as_bot(db).await?;
+    if bot.owner != user.id {
+        return Err(Error::NotFound);
+    }
+
+    // Server commands may only be registered where the bot is present.
+    if let Some(server) = &data.server {
+        db.fetch_member(server, &bot.id).await?;
+    }
+
+    if db
+        .fetch_commands(&bot.id)
+        .await?
+        .iter()
+        .any(|command| command.name == data.name && command.server_id == data.server)
+    {
+        return Err(Error::InvalidOperation);
+    }
+
+    let command = BotCommand {
+        id: Ulid::new().to_string(),
+        bot_id: bot.id,
+        server_id: data.server,
+        name: data.name,
+        description: data.description,
+        options,
+    };
+
+    command.create(db).await?;
+    Ok(Json(command))
+}
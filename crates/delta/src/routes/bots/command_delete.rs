@@ -0,0 +1,29 @@
+use revolt_quark::{models::User, Db, EmptyResponse, Error, Ref, Result};
+
+/// # Delete Command
+///
+/// Delete a command registered by a bot you own.
+#[openapi(tag = "Bots")]
+#[delete("/<target>/commands/<command>")]
+pub async fn delete_command(
+    db: &Db,
+    user: User,
+    target: Ref,
+    command: Ref,
+) -> Result<EmptyResponse> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let bot = target.as_bot(db).await?;
+    if bot.owner != user.id {
+        return Err(Error::NotFound);
+    }
+
+    let command = command.as_command(db).await?;
+    if command.bot_id != bot.id {
+        return Err(Error::NotFound);
+    }
+
+    command.delete(db).await.map(|_| EmptyResponse)
+}
@@ -0,0 +1,56 @@
+use revolt_quark::{
+    models::{BotCommand, User},
+    Db, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+
+/// # Query Parameters
+#[derive(Serialize, Deserialize, JsonSchema, FromForm)]
+pub struct OptionsFetchCommands {
+    /// Include commands registered in this server
+    server: Option<String>,
+}
+
+/// # Fetch Commands
+///
+/// Fetch commands registered by a bot.
+///
+/// Owners receive every command when no server is given,
+/// otherwise only global commands and those registered in the given server are returned.
+#[openapi(tag = "Bots")]
+#[get("/<target>/commands?<options..>")]
+pub async fn fetch_commands(
+    db: &Db,
+    user: User,
+    target: Ref,
+    options: OptionsFetchCommands,
+) -> Result<Json<Vec<BotCommand>>> {
+    let bot = target.as_bot(db).await?;
+    let commands = db.fetch_commands(&bot.id).await?;
+
+    if let Some(server) = &options.server {
+        if bot.owner != user.id {
+            db.fetch_member(server, &user.id).await?;
+        }
+
+        Ok(Json(
+            commands
+                .into_iter()
+                .filter(|command| {
+                    command.server_id.is_none() || command.server_id.as_ref() == Some(server)
+                })
+                .collect(),
+        ))
+    } else if bot.owner == user.id {
+        Ok(Json(commands))
+    } else {
+        Ok(Json(
+            commands
+                .into_iter()
+                .filter(|command| command.server_id.is_none())
+                .collect(),
+        ))
+    }
+}
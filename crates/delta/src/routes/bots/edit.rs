@@ -66,6 +66,10 @@ pub async fn edit_bot(
         user.update_username(db, name).await?;
     }
 
+    if let Some(url) = &data.interactions_url {
+        Bot::validate_interactions_url(url).await?;
+    }
+
     if data.public.is_none()
         && data.analytics.is_none()
         && data.interactions_url.is_none()
@@ -1,6 +1,9 @@
 use rocket::Route;
 use rocket_okapi::okapi::openapi3::OpenApi;
 
+mod command_create;
+mod command_delete;
+mod command_fetch_all;
 mod create;
 mod delete;
 mod edit;
@@ -18,5 +21,8 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         fetch_owned::fetch_owned_bots,
         edit::edit_bot,
         delete::delete_bot,
+        command_create::create_command,
+        command_fetch_all::fetch_commands,
+        command_delete::delete_command,
     ]
 }
@@ -1,6 +1,12 @@
 use revolt_quark::{
-    models::{channel::PartialChannel, Channel, User},
-    perms, Db, EmptyResponse, Error, Permission, Ref, Result,
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        channel::PartialChannel,
+        AuditLogEntry, Channel, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, EmptyResponse, Error, Permission, Ref, Result,
 };
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -22,6 +28,7 @@ pub async fn req(
     user: User,
     target: Ref,
     options: OptionsChannelDelete,
+    reason: AuditLogReason,
 ) -> Result<EmptyResponse> {
     let mut channel = target.as_channel(db).await?;
     let mut perms = perms(&user).channel(&channel);
@@ -49,12 +56,28 @@ pub async fn req(
             )
             .await
             .map(|_| EmptyResponse),
-        Channel::TextChannel { .. } | Channel::VoiceChannel { .. } => {
+        Channel::TextChannel { .. } | Channel::VoiceChannel { .. } | Channel::Thread { .. } => {
             perms
                 .throw_permission(db, Permission::ManageChannel)
                 .await?;
 
-            channel.delete(db).await.map(|_| EmptyResponse)
+            let entry = channel.server_id().map(|server| {
+                AuditLogEntry::new(
+                    server,
+                    &user.id,
+                    AuditLogAction::ChannelDelete,
+                    channel.id(),
+                )
+                .with_changes(AuditLogChanges::channel(Some(&channel), None, vec![]))
+                .with_reason(reason.into_inner())
+            });
+
+            channel.delete(db).await?;
+            if let Some(entry) = entry {
+                entry.record(db).await;
+            }
+
+            Ok(EmptyResponse)
         }
     }
 }
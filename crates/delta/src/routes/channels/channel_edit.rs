@@ -1,10 +1,13 @@
 use revolt_quark::{
     models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
         channel::{Channel, FieldsChannel, PartialChannel},
         message::SystemMessage,
-        File, User,
+        AuditLogEntry, File, User,
     },
-    perms, Database, Error, Permission, Ref, Result,
+    perms,
+    web::audit_log::AuditLogReason,
+    Database, Error, Permission, Ref, Result,
 };
 
 use rocket::{serde::json::Json, State};
@@ -44,6 +47,7 @@ pub async fn req(
     db: &State<Database>,
     user: User,
     target: Ref,
+    reason: AuditLogReason,
     data: Json<DataEditChannel>,
 ) -> Result<Json<Channel>> {
     let data = data.into_inner();
@@ -61,11 +65,14 @@ pub async fn req(
         && data.icon.is_none()
         && data.nsfw.is_none()
         && data.owner.is_none()
+        && data.archived.is_none()
         && data.remove.is_none()
     {
         return Ok(Json(channel));
     }
 
+    let before = channel.clone();
+    let remove = data.remove.clone().unwrap_or_default();
     let mut partial: PartialChannel = Default::default();
 
     // Transfer group ownership
@@ -191,20 +198,50 @@ pub async fn req(
                 }
 
                 if partial.icon.is_some() {
-                    SystemMessage::ChannelIconChanged { by: user.id }
-                        .into_message(channel.id().to_string())
-                        .create(db, &channel, None)
-                        .await
-                        .ok();
+                    SystemMessage::ChannelIconChanged {
+                        by: user.id.clone(),
+                    }
+                    .into_message(channel.id().to_string())
+                    .create(db, &channel, None)
+                    .await
+                    .ok();
                 }
             }
 
-            channel
-                .update(db, partial, data.remove.unwrap_or_default())
-                .await?;
+            channel.update(db, partial, remove.clone()).await?;
+        }
+        Channel::Thread { name, archived, .. } => {
+            if let Some(new_name) = data.name {
+                *name = new_name.clone();
+                partial.name = Some(new_name);
+            }
+
+            if let Some(new_archived) = data.archived {
+                *archived = new_archived;
+                partial.archived = Some(new_archived);
+            }
+
+            channel.update(db, partial, vec![]).await?;
         }
         _ => return Err(Error::InvalidOperation),
     };
 
+    if let Some(server) = channel.server_id() {
+        AuditLogEntry::new(
+            server,
+            &user.id,
+            AuditLogAction::ChannelUpdate,
+            channel.id(),
+        )
+        .with_changes(AuditLogChanges::channel(
+            Some(&before),
+            Some(&channel),
+            remove,
+        ))
+        .with_reason(reason.into_inner())
+        .record(db)
+        .await;
+    }
+
     Ok(Json(channel))
 }
@@ -0,0 +1,135 @@
+use indexmap::IndexMap;
+use revolt_quark::{
+    models::{
+        bot_command::{CommandInteraction, CommandOptionValue},
+        Channel, Message, User,
+    },
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+/// # Command Invocation
+#[derive(Serialize, Deserialize, JsonSchema)]
+pub struct DataInvokeCommand {
+    /// Values for the command's options
+    #[serde(default)]
+    options: IndexMap<String, CommandOptionValue>,
+}
+
+/// # Invoke Command
+///
+/// Invoke a bot's command in this channel.
+///
+/// The interaction is sent to the bot's interactions URL
+/// and its response is sent as a message from the bot.
+#[openapi(tag = "Messaging")]
+#[post("/<target>/commands/<command>", data = "<data>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    command: Ref,
+    data: Json<DataInvokeCommand>,
+) -> Result<Json<Message>> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::SendMessage)
+        .await?;
+
+    let command = command.as_command(db).await?;
+    if !command.is_usable_in(&channel) {
+        return Err(Error::NotFound);
+    }
+
+    // The bot must be present in the channel to respond.
+    let present = match &channel {
+        Channel::DirectMessage { recipients, .. } | Channel::Group { recipients, .. } => {
+            recipients.contains(&command.bot_id)
+        }
+        Channel::TextChannel { server, .. }
+        | Channel::VoiceChannel { server, .. }
+        | Channel::Thread { server, .. } => db.fetch_member(server, &command.bot_id).await.is_ok(),
+        Channel::SavedMessages { .. } => false,
+    };
+
+    if !present {
+        return Err(Error::NotFound);
+    }
+
+    let data = data.into_inner();
+    command.validate_options(&data.options)?;
+
+    // The bot must also be able to post its response here.
+    let bot = db.fetch_bot(&command.bot_id).await?;
+    let sender = db.fetch_user(&bot.id).await?;
+    perms(&sender)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::SendMessage)
+        .await?;
+
+    let interaction = CommandInteraction {
+        id: Ulid::new().to_string(),
+        command_id: command.id,
+        command_name: command.name,
+        user_id: user.id,
+        channel_id: channel.id().to_string(),
+        server_id: channel.server_id().map(|id| id.to_string()),
+        options: data.options,
+    };
+
+    let response = interaction.dispatch(&bot).await?;
+    response.validate().map_err(|_| Error::InteractionFailed)?;
+
+    Message::validate_sum(&response.content, &response.embeds)
+        .map_err(|_| Error::InteractionFailed)?;
+
+    if (response.content.as_ref().map_or(true, |v| v.is_empty()))
+        && (response.embeds.as_ref().map_or(true, |v| v.is_empty()))
+    {
+        return Err(Error::InteractionFailed);
+    }
+
+    let message_id = Ulid::new().to_string();
+    let mut message = Message {
+        id: message_id.clone(),
+        channel: channel.id().to_string(),
+        author: bot.id.clone(),
+        content: response.content,
+        ..Default::default()
+    };
+
+    let mut embeds = vec![];
+    if let Some(sendable_embeds) = response.embeds {
+        for sendable_embed in sendable_embeds {
+            embeds.push(sendable_embed.into_embed(db, message_id.clone()).await?)
+        }
+    }
+
+    if !embeds.is_empty() {
+        message.embeds.replace(embeds);
+    }
+
+    message.create(db, &channel, Some(&sender)).await?;
+
+    // Queue up a task for processing embeds
+    if let Some(content) = &message.content {
+        revolt_quark::tasks::process_embeds::queue(
+            db,
+            channel.id().to_string(),
+            message.id.to_string(),
+            content.clone(),
+        )
+        .await;
+    }
+
+    Ok(Json(message))
+}
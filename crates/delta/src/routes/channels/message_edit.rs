@@ -1,6 +1,7 @@
 use revolt_quark::{
     models::message::{PartialMessage, SendableEmbed},
     models::{Message, User},
+    perms,
     types::january::Embed,
     Db, Error, Ref, Result, Timestamp,
 };
@@ -47,9 +48,8 @@ pub async fn req(
         return Err(Error::CannotEditMessage);
     }
 
-    message.edited = Some(Timestamp::now_utc());
     let mut partial = PartialMessage {
-        edited: message.edited,
+        edited: Some(Timestamp::now_utc()),
         ..Default::default()
     };
 
@@ -79,11 +79,27 @@ pub async fn req(
 
     partial.embeds = Some(new_embeds);
 
+    // 4. Run automod rules against the edited message
+    let channel = db.fetch_channel(&message.channel).await?;
+    let mut edited = message.clone();
+    edited.apply_options(partial.clone());
+    let delete = edited
+        .run_automod(db, &channel, &mut perms(&user).channel(&channel))
+        .await?;
+
+    // 5. Keep the previous version around for moderators
+    message.record_revision(db, &channel).await?;
     message.update(db, partial).await?;
 
+    if delete {
+        message.clone().delete(db).await?;
+        return Ok(Json(message));
+    }
+
     // Queue up a task for processing embeds
     if let Some(content) = edit.content {
         revolt_quark::tasks::process_embeds::queue(
+            db,
             message.channel.to_string(),
             message.id.to_string(),
             content,
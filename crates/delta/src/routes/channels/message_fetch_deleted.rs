@@ -0,0 +1,43 @@
+use revolt_quark::{
+    models::{DeletedMessage, User},
+    perms, Db, Error, Permission, Ref, Result,
+};
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// # Query Parameters
+#[derive(Validate, Serialize, Deserialize, JsonSchema, FromForm)]
+pub struct OptionsFetchDeleted {
+    /// Maximum number of messages to fetch
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<i64>,
+}
+
+/// # Fetch Deleted Messages
+///
+/// Fetch messages deleted from this channel which can still be restored, most recently deleted first.
+///
+/// Requires `ManageMessages` permission.
+#[openapi(tag = "Messaging")]
+#[get("/<target>/messages/deleted?<options..>", rank = 1)]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    options: OptionsFetchDeleted,
+) -> Result<Json<Vec<DeletedMessage>>> {
+    options
+        .validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ManageMessages)
+        .await?;
+
+    db.fetch_deleted_messages(channel.id(), options.limit.unwrap_or(50))
+        .await
+        .map(Json)
+}
@@ -0,0 +1,21 @@
+use revolt_quark::{
+    models::{Message, User},
+    perms, Db, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+
+/// # Fetch Pinned Messages
+///
+/// Fetch all messages pinned in this channel, newest first.
+#[openapi(tag = "Messaging")]
+#[get("/<target>/pins")]
+pub async fn req(db: &Db, user: User, target: Ref) -> Result<Json<Vec<Message>>> {
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ReadMessageHistory)
+        .await?;
+
+    db.fetch_pinned_messages(channel.id()).await.map(Json)
+}
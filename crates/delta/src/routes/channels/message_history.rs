@@ -0,0 +1,34 @@
+use revolt_quark::{
+    models::{MessageRevision, User},
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+
+/// # Fetch Message History
+///
+/// Fetch previous versions of a message, oldest first.
+///
+/// Available to the author of the message and anyone who can manage messages in this channel.
+#[openapi(tag = "Messaging")]
+#[get("/<target>/messages/<msg>/history")]
+pub async fn req(db: &Db, user: User, target: Ref, msg: Ref) -> Result<Json<Vec<MessageRevision>>> {
+    let channel = target.as_channel(db).await?;
+    let mut permissions = perms(&user).channel(&channel);
+    permissions
+        .throw_permission(db, Permission::ViewChannel)
+        .await?;
+
+    let message = msg.as_message(db).await?;
+    if message.channel != channel.as_id() {
+        return Err(Error::NotFound);
+    }
+
+    if message.author != user.id {
+        permissions
+            .throw_permission(db, Permission::ManageMessages)
+            .await?;
+    }
+
+    message.fetch_revisions(db, &channel).await.map(Json)
+}
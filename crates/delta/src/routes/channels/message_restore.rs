@@ -0,0 +1,44 @@
+use revolt_quark::{
+    models::{audit_log::AuditLogAction, AuditLogEntry, Message, User},
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Permission, Ref, Result,
+};
+use rocket::serde::json::Json;
+
+/// # Restore Message
+///
+/// Restore a message which was recently deleted from this channel.
+///
+/// Requires `ManageMessages` permission.
+#[openapi(tag = "Messaging")]
+#[post("/<target>/messages/<msg>/restore")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    msg: Ref,
+    reason: AuditLogReason,
+) -> Result<Json<Message>> {
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ManageMessages)
+        .await?;
+
+    let message = Message::restore(db, channel.id(), &msg.id).await?;
+
+    if let Some(server) = channel.server_id() {
+        AuditLogEntry::new(
+            server,
+            &user.id,
+            AuditLogAction::MessageRestore,
+            &message.id,
+        )
+        .with_reason(reason.into_inner())
+        .record(db)
+        .await;
+    }
+
+    Ok(Json(message))
+}
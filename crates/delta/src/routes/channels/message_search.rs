@@ -1,6 +1,6 @@
 use revolt_quark::{
     models::{
-        message::{BulkMessageResponse, MessageSort},
+        message::{BulkMessageResponse, MessageFilter, MessageSort},
         User,
     },
     perms, Db, Error, Permission, Ref, Result,
@@ -13,24 +13,17 @@ use validator::Validate;
 /// # Search Parameters
 #[derive(Validate, Serialize, Deserialize, JsonSchema)]
 pub struct OptionsMessageSearch {
-    /// Full-text search query
-    ///
-    /// See [MongoDB documentation](https://docs.mongodb.com/manual/text-search/#-text-operator) for more information.
-    #[validate(length(min = 1, max = 64))]
-    query: String,
+    /// Criteria which messages must match
+    #[serde(flatten)]
+    #[validate]
+    filter: MessageFilter,
 
     /// Maximum number of messages to fetch
     #[validate(range(min = 1, max = 100))]
     limit: Option<i64>,
-    /// Message id before which messages should be fetched
-    #[validate(length(min = 26, max = 26))]
-    before: Option<String>,
-    /// Message id after which messages should be fetched
-    #[validate(length(min = 26, max = 26))]
-    after: Option<String>,
     /// Message sort direction
     ///
-    /// By default, it will be sorted by relevance.
+    /// By default, it will be sorted by relevance, or latest first if no query is given.
     #[serde(default = "MessageSort::default")]
     sort: MessageSort,
     /// Whether to include user (and member, if server channel) objects
@@ -64,16 +57,14 @@ pub async fn req(
         .await?;
 
     let OptionsMessageSearch {
-        query,
+        filter,
         limit,
-        before,
-        after,
         sort,
         include_users,
     } = options;
 
     let messages = db
-        .search_messages(channel.id(), &query, limit, before, after, sort)
+        .search_messages(&[channel.id().to_string()], &filter, limit, sort)
         .await?;
 
     BulkMessageResponse::transform(db, &channel, messages, include_users)
@@ -52,6 +52,8 @@ lazy_static! {
 /// # Send Message
 ///
 /// Sends a message to the given channel.
+///
+/// Retrying with the same `Idempotency-Key` returns the original response.
 #[openapi(tag = "Messaging")]
 #[post("/<target>/messages", data = "<data>")]
 pub async fn message_send(
@@ -61,16 +63,33 @@ pub async fn message_send(
     data: Json<DataMessageSend>,
     mut idempotency: IdempotencyKey,
 ) -> Result<Json<Message>> {
-    let data = data.into_inner();
+    let mut data = data.into_inner();
     data.validate()
         .map_err(|error| Error::FailedValidation { error })?;
 
     Message::validate_sum(&data.content, &data.embeds)?;
 
-    idempotency.consume_nonce(data.nonce).await?;
+    idempotency.consume_nonce(data.nonce.take()).await?;
 
+    idempotency
+        .replay(
+            &user.id,
+            send_message(db, &user, target, data, idempotency.key().to_string()),
+        )
+        .await
+        .map(Json)
+}
+
+/// Create and send a message once the request has been validated
+async fn send_message(
+    db: &Db,
+    user: &User,
+    target: Ref,
+    data: DataMessageSend,
+    nonce: String,
+) -> Result<Message> {
     let channel = target.as_channel(db).await?;
-    let mut permissions = perms(&user).channel(&channel);
+    let mut permissions = perms(user).channel(&channel);
     permissions
         .throw_permission_and_view_channel(db, Permission::SendMessage)
         .await?;
@@ -193,13 +212,22 @@ pub async fn message_send(
     message.content = data.content;
 
     // 8. Pass-through nonce value for clients
-    message.nonce = Some(idempotency.into_key());
+    message.nonce = Some(nonce);
+
+    // 9. Run automod rules against the message
+    let delete = message.run_automod(db, &channel, &mut permissions).await?;
 
-    message.create(db, &channel, Some(&user)).await?;
+    message.create(db, &channel, Some(user)).await?;
+
+    if delete {
+        message.clone().delete(db).await?;
+        return Ok(message);
+    }
 
     // Queue up a task for processing embeds
     if let Some(content) = &message.content {
         revolt_quark::tasks::process_embeds::queue(
+            db,
             channel.id().to_string(),
             message.id.to_string(),
             content.clone(),
@@ -207,5 +235,5 @@ pub async fn message_send(
         .await;
     }
 
-    Ok(Json(message))
+    Ok(message)
 }
@@ -0,0 +1,19 @@
+use revolt_quark::{models::User, perms, Db, EmptyResponse, Permission, Ref, Result};
+
+/// # Unpin Message
+///
+/// Unpin a message from this channel.
+///
+/// Requires `ManageMessages` permission.
+#[openapi(tag = "Messaging")]
+#[delete("/<target>/messages/<msg>/pin")]
+pub async fn req(db: &Db, user: User, target: Ref, msg: Ref) -> Result<EmptyResponse> {
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ManageMessages)
+        .await?;
+
+    let mut message = msg.as_message_in(db, channel.id()).await?;
+    message.unpin(db).await.map(|_| EmptyResponse)
+}
@@ -5,6 +5,7 @@ mod channel_ack;
 mod channel_delete;
 mod channel_edit;
 mod channel_fetch;
+mod command_invoke;
 mod group_add_member;
 mod group_create;
 mod group_remove_member;
@@ -15,15 +16,25 @@ mod message_clear_reactions;
 mod message_delete;
 mod message_edit;
 mod message_fetch;
+mod message_fetch_deleted;
+mod message_fetch_pins;
+mod message_history;
+mod message_pin;
 mod message_query;
 mod message_query_stale;
 mod message_react;
+mod message_restore;
 mod message_search;
 mod message_send;
+mod message_unpin;
 mod message_unreact;
 mod permissions_set;
 mod permissions_set_default;
+mod thread_create;
+mod thread_fetch_all;
 mod voice_join;
+mod webhook_create;
+mod webhook_fetch_all;
 
 pub fn routes() -> (Vec<Route>, OpenApi) {
     openapi_get_routes_spec![
@@ -34,21 +45,32 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         channel_edit::req,
         invite_create::req,
         message_send::message_send,
+        command_invoke::req,
         message_query::req,
         message_search::req,
         message_query_stale::req,
         message_fetch::req,
+        message_fetch_deleted::req,
         message_edit::req,
+        message_history::req,
         message_bulk_delete::req,
         message_delete::req,
+        message_restore::req,
+        message_fetch_pins::req,
+        message_pin::req,
+        message_unpin::req,
         group_create::req,
         group_add_member::req,
         group_remove_member::req,
         voice_join::req,
+        thread_create::req,
+        thread_fetch_all::req,
         permissions_set::req,
         permissions_set_default::req,
         message_react::react_message,
         message_unreact::unreact_message,
-        message_clear_reactions::clear_reactions
+        message_clear_reactions::clear_reactions,
+        webhook_create::req,
+        webhook_fetch_all::req
     ]
 }
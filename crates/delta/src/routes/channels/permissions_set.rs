@@ -2,8 +2,13 @@ use rocket::serde::json::Json;
 use serde::Deserialize;
 
 use revolt_quark::{
-    models::{Channel, User},
-    perms, Db, Error, Override, Permission, Ref, Result,
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        AuditLogEntry, Channel, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Override, Permission, Ref, Result,
 };
 
 /// # Permission Value
@@ -25,6 +30,7 @@ pub async fn req(
     user: User,
     target: Ref,
     role_id: String,
+    reason: AuditLogReason,
     data: Json<Data>,
 ) -> Result<Json<Channel>> {
     let mut channel = target.as_channel(db).await?;
@@ -45,10 +51,27 @@ pub async fn req(
                 .throw_permission_override(db, current_value, data.permissions)
                 .await?;
 
+            let server_id = server.id.clone();
+            let before = channel.clone();
             channel
                 .set_role_permission(db, &role_id, data.permissions.into())
                 .await?;
 
+            AuditLogEntry::new(
+                &server_id,
+                &user.id,
+                AuditLogAction::ChannelUpdate,
+                channel.id(),
+            )
+            .with_changes(AuditLogChanges::channel(
+                Some(&before),
+                Some(&channel),
+                vec![],
+            ))
+            .with_reason(reason.into_inner())
+            .record(db)
+            .await;
+
             Ok(Json(channel))
         } else {
             Err(Error::NotFound)
@@ -2,8 +2,14 @@ use rocket::serde::json::Json;
 use serde::Deserialize;
 
 use revolt_quark::{
-    models::{channel::PartialChannel, Channel, User},
-    perms, Db, Error, Override, Permission, Ref, Result,
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        channel::PartialChannel,
+        AuditLogEntry, Channel, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Override, Permission, Ref, Result,
 };
 
 /// # Permission Value
@@ -31,6 +37,7 @@ pub async fn req(
     db: &Db,
     user: User,
     target: Ref,
+    reason: AuditLogReason,
     data: Json<DataDefaultChannelPermissions>,
 ) -> Result<Json<Channel>> {
     let data = data.into_inner();
@@ -41,6 +48,7 @@ pub async fn req(
     perm.throw_permission_and_view_channel(db, Permission::ManagePermissions)
         .await?;
 
+    let before = channel.clone();
     match &channel {
         Channel::Group { .. } => {
             if let DataDefaultChannelPermissions::Value { permissions } = data {
@@ -91,5 +99,22 @@ pub async fn req(
         _ => return Err(Error::InvalidOperation),
     }
 
+    if let Some(server) = channel.server_id() {
+        AuditLogEntry::new(
+            server,
+            &user.id,
+            AuditLogAction::ChannelUpdate,
+            channel.id(),
+        )
+        .with_changes(AuditLogChanges::channel(
+            Some(&before),
+            Some(&channel),
+            vec![],
+        ))
+        .with_reason(reason.into_inner())
+        .record(db)
+        .await;
+    }
+
     Ok(Json(channel))
 }
@@ -0,0 +1,77 @@
+use revolt_quark::{
+    models::{Channel, User},
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+/// # Thread Details
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct DataCreateThread {
+    /// Thread name
+    #[validate(length(min = 1, max = 32))]
+    name: String,
+    /// Id of the message to start this thread from
+    #[validate(length(min = 1, max = 128))]
+    message: String,
+}
+
+/// # Create Thread
+///
+/// Start a new thread from a message in this channel.
+///
+/// Channel must be a `TextChannel`.
+#[openapi(tag = "Threads")]
+#[post("/<target>/threads", data = "<data>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    data: Json<DataCreateThread>,
+) -> Result<Json<Channel>> {
+    let data = data.into_inner();
+    data.validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::SendMessage)
+        .await?;
+
+    let server = match &channel {
+        Channel::TextChannel { server, .. } => server.clone(),
+        _ => return Err(Error::InvalidOperation),
+    };
+
+    let message = Ref::from_unchecked(data.message)
+        .as_message_in(db, channel.id())
+        .await?;
+
+    // Only one thread may be started from any given message.
+    if db
+        .fetch_threads(&[channel.id().to_string()])
+        .await?
+        .iter()
+        .any(|thread| matches!(thread, Channel::Thread { message: id, .. } if id == &message.id))
+    {
+        return Err(Error::InvalidOperation);
+    }
+
+    let thread = Channel::Thread {
+        id: Ulid::new().to_string(),
+        server,
+        parent: channel.id().to_string(),
+        message: message.id,
+        owner: user.id,
+        name: data.name,
+        last_message_id: None,
+        archived: false,
+    };
+
+    thread.create(db).await?;
+    Ok(Json(thread))
+}
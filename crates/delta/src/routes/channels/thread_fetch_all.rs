@@ -0,0 +1,43 @@
+use revolt_quark::{
+    models::{Channel, User},
+    perms, Db, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+
+/// # Query Parameters
+#[derive(Serialize, Deserialize, JsonSchema, FromForm)]
+pub struct OptionsFetchThreads {
+    /// Only return threads which are (or are not) archived
+    archived: Option<bool>,
+}
+
+/// # Fetch Threads
+///
+/// Fetch all threads started in this channel.
+#[openapi(tag = "Threads")]
+#[get("/<target>/threads?<options..>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    options: OptionsFetchThreads,
+) -> Result<Json<Vec<Channel>>> {
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission(db, Permission::ViewChannel)
+        .await?;
+
+    Ok(Json(
+        db.fetch_threads(&[channel.id().to_string()])
+            .await?
+            .into_iter()
+            .filter(|thread| match (options.archived, thread) {
+                (Some(filter), Channel::Thread { archived, .. }) => filter == *archived,
+                _ => true,
+            })
+            .collect(),
+    ))
+}
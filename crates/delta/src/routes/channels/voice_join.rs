@@ -33,7 +33,7 @@ pub async fn req(db: &Db, user: User, target: Ref) -> Result<Json<CreateVoiceUse
     }
 
     match channel {
-        Channel::SavedMessages { .. } | Channel::TextChannel { .. } => {
+        Channel::SavedMessages { .. } | Channel::TextChannel { .. } | Channel::Thread { .. } => {
             return Err(Error::CannotJoinCall)
         }
         _ => {}
@@ -0,0 +1,71 @@
+use revolt_quark::{
+    models::{Channel, File, User, Webhook},
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use nanoid::nanoid;
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+/// # Webhook Details
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct DataCreateWebhook {
+    /// Webhook name
+    #[validate(length(min = 1, max = 32))]
+    name: String,
+    /// Autumn file id used for the webhook avatar
+    #[validate(length(min = 1, max = 128))]
+    avatar: Option<String>,
+}
+
+/// # Create Webhook
+///
+/// Creates a webhook which can be used to send messages to this channel.
+///
+/// Channel must be a `TextChannel` or `Group`.
+#[openapi(tag = "Webhooks")]
+#[post("/<target>/webhooks", data = "<data>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    data: Json<DataCreateWebhook>,
+) -> Result<Json<Webhook>> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let data = data.into_inner();
+    data.validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let channel = target.as_channel(db).await?;
+    if !matches!(channel, Channel::TextChannel { .. } | Channel::Group { .. }) {
+        return Err(Error::InvalidOperation);
+    }
+
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ManageWebhooks)
+        .await?;
+
+    let id = Ulid::new().to_string();
+    let avatar = match &data.avatar {
+        Some(avatar) => Some(File::use_webhook_avatar(db, avatar, &id).await?),
+        None => None,
+    };
+
+    let webhook = Webhook {
+        id,
+        name: data.name,
+        avatar,
+        channel_id: channel.id().to_string(),
+        creator_id: user.id,
+        token: Some(nanoid!(64)),
+    };
+
+    webhook.create(db).await?;
+    Ok(Json(webhook))
+}
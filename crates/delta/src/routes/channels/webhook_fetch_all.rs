@@ -0,0 +1,25 @@
+use revolt_quark::{
+    models::{User, Webhook},
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+
+/// # Fetch Webhooks
+///
+/// Fetch all webhooks belonging to this channel.
+#[openapi(tag = "Webhooks")]
+#[get("/<target>/webhooks")]
+pub async fn req(db: &Db, user: User, target: Ref) -> Result<Json<Vec<Webhook>>> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let channel = target.as_channel(db).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ManageWebhooks)
+        .await?;
+
+    db.fetch_webhooks_for_channel(channel.id()).await.map(Json)
+}
@@ -13,6 +13,7 @@ mod root;
 mod servers;
 mod sync;
 mod users;
+mod webhooks;
 
 pub fn mount(mut rocket: Rocket<Build>) -> Rocket<Build> {
     let settings = OpenApiSettings::default();
@@ -26,6 +27,7 @@ pub fn mount(mut rocket: Rocket<Build>) -> Rocket<Build> {
         "/channels" => channels::routes(),
         "/servers" => servers::routes(),
         "/invites" => invites::routes(),
+        "/webhooks" => webhooks::routes(),
         "/custom" => customisation::routes(),
         "/auth/account" => rocket_rauth::routes::account::routes(),
         "/auth/session" => rocket_rauth::routes::session::routes(),
@@ -82,7 +84,9 @@ fn custom_openapi_spec() -> OpenApi {
               "Messaging",
               "Interactions",
               "Groups",
-              "Voice"
+              "Threads",
+              "Voice",
+              "Webhooks"
             ]
           },
           {
@@ -90,7 +94,8 @@ fn custom_openapi_spec() -> OpenApi {
             "tags": [
               "Server Information",
               "Server Members",
-              "Server Permissions"
+              "Server Permissions",
+              "Server Automod"
             ]
           },
           {
@@ -218,11 +223,21 @@ fn custom_openapi_spec() -> OpenApi {
                 description: Some("Create, invite users and manipulate groups".to_owned()),
                 ..Default::default()
             },
+            Tag {
+                name: "Threads".to_owned(),
+                description: Some("Branch conversations off messages in a channel".to_owned()),
+                ..Default::default()
+            },
             Tag {
                 name: "Voice".to_owned(),
                 description: Some("Join and talk with other users".to_owned()),
                 ..Default::default()
             },
+            Tag {
+                name: "Webhooks".to_owned(),
+                description: Some("Send messages to channels from external services".to_owned()),
+                ..Default::default()
+            },
             Tag {
                 name: "Server Information".to_owned(),
                 description: Some("Query and fetch servers on Revolt".to_owned()),
@@ -238,6 +253,11 @@ fn custom_openapi_spec() -> OpenApi {
                 description: Some("Manage permissions for servers".to_owned()),
                 ..Default::default()
             },
+            Tag {
+                name: "Server Automod".to_owned(),
+                description: Some("Configure automatic moderation rules".to_owned()),
+                ..Default::default()
+            },
             Tag {
                 name: "Invites".to_owned(),
                 description: Some("View, join and delete invites".to_owned()),
@@ -0,0 +1,58 @@
+use revolt_quark::{
+    models::{audit_log::AuditLogAction, AuditLogEntry, User},
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// # Query Parameters
+#[derive(Validate, Serialize, Deserialize, JsonSchema, FromForm)]
+pub struct OptionsFetchAuditLog {
+    /// Maximum number of entries to fetch
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<i64>,
+    /// Entry id before which entries should be fetched
+    #[validate(length(min = 26, max = 26))]
+    before: Option<String>,
+    /// Only fetch entries for actions taken by this user
+    #[validate(length(min = 26, max = 26))]
+    actor: Option<String>,
+    /// Only fetch entries for this type of action
+    action: Option<AuditLogAction>,
+}
+
+/// # Fetch Audit Log
+///
+/// Fetch moderation and configuration actions taken in a server, newest first.
+///
+/// Requires `ViewAuditLog` permission.
+#[openapi(tag = "Server Information")]
+#[get("/<target>/audit?<options..>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    options: OptionsFetchAuditLog,
+) -> Result<Json<Vec<AuditLogEntry>>> {
+    options
+        .validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let server = target.as_server(db).await?;
+    perms(&user)
+        .server(&server)
+        .throw_permission(db, Permission::ViewAuditLog)
+        .await?;
+
+    db.fetch_audit_log(
+        &server.id,
+        options.limit.unwrap_or(50),
+        options.before,
+        options.actor,
+        options.action,
+    )
+    .await
+    .map(Json)
+}
@@ -0,0 +1,87 @@
+use revolt_quark::{
+    models::{
+        audit_log::AuditLogAction,
+        automod_rule::{AutomodAction, AutomodTrigger},
+        AuditLogEntry, AutomodRule, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+/// # Automod Rule Data
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct DataCreateAutomodRule {
+    /// Rule name
+    #[validate(length(min = 1, max = 32))]
+    name: String,
+    /// Whether this rule should be in effect
+    enabled: Option<bool>,
+    /// Condition which triggers this rule
+    trigger: AutomodTrigger,
+    /// Action taken when this rule is triggered
+    action: AutomodAction,
+    /// Roles which this rule doesn't apply to
+    #[validate(length(max = 100))]
+    exempt_roles: Option<Vec<String>>,
+    /// Channels which this rule doesn't apply to
+    #[validate(length(max = 100))]
+    exempt_channels: Option<Vec<String>>,
+}
+
+/// # Create Automod Rule
+///
+/// Create a new automatic moderation rule for a server.
+///
+/// Requires `ManageServer` permission.
+#[openapi(tag = "Server Automod")]
+#[post("/<target>/automod", data = "<data>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    data: Json<DataCreateAutomodRule>,
+    reason: AuditLogReason,
+) -> Result<Json<AutomodRule>> {
+    let data = data.into_inner();
+    data.validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let server = target.as_server(db).await?;
+    perms(&user)
+        .server(&server)
+        .throw_permission(db, Permission::ManageServer)
+        .await?;
+
+    let rule = AutomodRule {
+        id: Ulid::new().to_string(),
+        server: server.id.clone(),
+        name: data.name,
+        enabled: data.enabled.unwrap_or(true),
+        trigger: data.trigger,
+        action: data.action,
+        exempt_roles: data.exempt_roles.unwrap_or_default(),
+        exempt_channels: data.exempt_channels.unwrap_or_default(),
+        revision: 0,
+    };
+
+    rule.validate(&server)?;
+    db.insert_automod_rule(&rule).await?;
+
+    AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::AutomodRuleCreate,
+        &rule.id,
+    )
+    .with_reason(reason.into_inner())
+    .record(db)
+    .await;
+
+    Ok(Json(rule))
+}
@@ -0,0 +1,46 @@
+use revolt_quark::{
+    models::{audit_log::AuditLogAction, AuditLogEntry, User},
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, EmptyResponse, Error, Permission, Ref, Result,
+};
+
+/// # Delete Automod Rule
+///
+/// Delete an automatic moderation rule by its id.
+///
+/// Requires `ManageServer` permission.
+#[openapi(tag = "Server Automod")]
+#[delete("/<target>/automod/<rule_id>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    rule_id: String,
+    reason: AuditLogReason,
+) -> Result<EmptyResponse> {
+    let server = target.as_server(db).await?;
+    perms(&user)
+        .server(&server)
+        .throw_permission(db, Permission::ManageServer)
+        .await?;
+
+    let rule = db.fetch_automod_rule(&rule_id).await?;
+    if rule.server != server.id {
+        return Err(Error::NotFound);
+    }
+
+    rule.delete(db).await?;
+
+    AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::AutomodRuleDelete,
+        &rule.id,
+    )
+    .with_reason(reason.into_inner())
+    .record(db)
+    .await;
+
+    Ok(EmptyResponse)
+}
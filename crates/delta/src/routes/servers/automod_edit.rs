@@ -0,0 +1,101 @@
+use revolt_quark::{
+    models::{
+        audit_log::AuditLogAction,
+        automod_rule::{AutomodAction, AutomodTrigger, PartialAutomodRule},
+        AuditLogEntry, AutomodRule, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// # Automod Rule Data
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct DataEditAutomodRule {
+    /// Rule name
+    #[validate(length(min = 1, max = 32))]
+    name: Option<String>,
+    /// Whether this rule should be in effect
+    enabled: Option<bool>,
+    /// Condition which triggers this rule
+    trigger: Option<AutomodTrigger>,
+    /// Action taken when this rule is triggered
+    action: Option<AutomodAction>,
+    /// Roles which this rule doesn't apply to
+    #[validate(length(max = 100))]
+    exempt_roles: Option<Vec<String>>,
+    /// Channels which this rule doesn't apply to
+    #[validate(length(max = 100))]
+    exempt_channels: Option<Vec<String>>,
+}
+
+/// # Edit Automod Rule
+///
+/// Edit an automatic moderation rule by its id.
+///
+/// Requires `ManageServer` permission.
+#[openapi(tag = "Server Automod")]
+#[patch("/<target>/automod/<rule_id>", data = "<data>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    rule_id: String,
+    data: Json<DataEditAutomodRule>,
+    reason: AuditLogReason,
+) -> Result<Json<AutomodRule>> {
+    let data = data.into_inner();
+    data.validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let server = target.as_server(db).await?;
+    perms(&user)
+        .server(&server)
+        .throw_permission(db, Permission::ManageServer)
+        .await?;
+
+    let mut rule = db.fetch_automod_rule(&rule_id).await?;
+    if rule.server != server.id {
+        return Err(Error::NotFound);
+    }
+
+    let DataEditAutomodRule {
+        name,
+        enabled,
+        trigger,
+        action,
+        exempt_roles,
+        exempt_channels,
+    } = data;
+
+    let partial = PartialAutomodRule {
+        name,
+        enabled,
+        revision: trigger.as_ref().map(|_| rule.revision + 1),
+        trigger,
+        action,
+        exempt_roles,
+        exempt_channels,
+        ..Default::default()
+    };
+
+    rule.apply_options(partial.clone());
+    rule.validate(&server)?;
+    db.update_automod_rule(&rule.id, &partial).await?;
+
+    AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::AutomodRuleUpdate,
+        &rule.id,
+    )
+    .with_reason(reason.into_inner())
+    .record(db)
+    .await;
+
+    Ok(Json(rule))
+}
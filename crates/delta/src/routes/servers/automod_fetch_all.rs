@@ -0,0 +1,23 @@
+use revolt_quark::{
+    models::{AutomodRule, User},
+    perms, Db, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+
+/// # Fetch Automod Rules
+///
+/// Fetch all automatic moderation rules configured for a server.
+///
+/// Requires `ManageServer` permission.
+#[openapi(tag = "Server Automod")]
+#[get("/<target>/automod")]
+pub async fn req(db: &Db, user: User, target: Ref) -> Result<Json<Vec<AutomodRule>>> {
+    let server = target.as_server(db).await?;
+    perms(&user)
+        .server(&server)
+        .throw_permission(db, Permission::ManageServer)
+        .await?;
+
+    db.fetch_automod_rules(&server.id).await.map(Json)
+}
@@ -1,6 +1,11 @@
 use revolt_quark::{
-    models::{server_member::MemberCompositeKey, ServerBan, User},
-    perms, Db, Error, Permission, Ref, Result,
+    models::{
+        audit_log::AuditLogAction, server_member::MemberCompositeKey, AuditLogEntry, ServerBan,
+        User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Permission, Ref, Result,
 };
 
 use rocket::serde::json::Json;
@@ -26,6 +31,7 @@ pub async fn req(
     server: Ref,
     target: Ref,
     data: Json<DataBanCreate>,
+    reason: AuditLogReason,
 ) -> Result<Json<ServerBan>> {
     let data = data.into_inner();
     data.validate()
@@ -47,15 +53,18 @@ pub async fn req(
         .throw_permission(db, Permission::BanMembers)
         .await?;
 
+    let entry = AuditLogEntry::new(&server.id, &user.id, AuditLogAction::MemberBan, &target.id)
+        .with_reason(reason.into_inner());
+
     // If member exists, check privileges against them
-    if let Ok(member) = target.as_member(db, &server.id).await {
+    let ban = if let Ok(member) = target.as_member(db, &server.id).await {
         if member.get_ranking(permissions.server.get().unwrap())
             <= permissions.get_member_rank().unwrap_or(i64::MIN)
         {
             return Err(Error::NotElevated);
         }
 
-        server.ban_member(db, member, data.reason).await.map(Json)
+        server.ban_member(db, member, data.reason).await?
     } else {
         let server_id = server.id.to_string();
         server
@@ -67,7 +76,10 @@ pub async fn req(
                 },
                 data.reason,
             )
-            .await
-            .map(Json)
-    }
+            .await?
+    };
+
+    entry.record(db).await;
+
+    Ok(Json(ban))
 }
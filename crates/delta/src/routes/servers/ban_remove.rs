@@ -1,11 +1,22 @@
-use revolt_quark::{models::User, perms, Db, EmptyResponse, Permission, Ref, Result};
+use revolt_quark::{
+    models::{audit_log::AuditLogAction, AuditLogEntry, User},
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, EmptyResponse, Permission, Ref, Result,
+};
 
 /// # Unban user
 ///
 /// Remove a user's ban.
 #[openapi(tag = "Server Members")]
 #[delete("/<server>/bans/<target>")]
-pub async fn req(db: &Db, user: User, server: Ref, target: Ref) -> Result<EmptyResponse> {
+pub async fn req(
+    db: &Db,
+    user: User,
+    server: Ref,
+    target: Ref,
+    reason: AuditLogReason,
+) -> Result<EmptyResponse> {
     let server = server.as_server(db).await?;
     perms(&user)
         .server(&server)
@@ -13,5 +24,17 @@ pub async fn req(db: &Db, user: User, server: Ref, target: Ref) -> Result<EmptyR
         .await?;
 
     let ban = target.as_ban(db, &server.id).await?;
-    db.delete_ban(&ban.id).await.map(|_| EmptyResponse)
+    db.delete_ban(&ban.id).await?;
+
+    AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::MemberUnban,
+        &ban.id.user,
+    )
+    .with_reason(reason.into_inner())
+    .record(db)
+    .await;
+
+    Ok(EmptyResponse)
 }
@@ -1,8 +1,14 @@
 use std::collections::HashMap;
 
 use revolt_quark::{
-    models::{server::PartialServer, Channel, User},
-    perms, Db, Error, Permission, Ref, Result,
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        server::PartialServer,
+        AuditLogEntry, Channel, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Permission, Ref, Result,
 };
 
 use rocket::serde::json::Json;
@@ -51,6 +57,7 @@ pub async fn req(
     db: &Db,
     user: User,
     target: Ref,
+    reason: AuditLogReason,
     info: Json<DataCreateChannel>,
 ) -> Result<Json<Channel>> {
     let info = info.into_inner();
@@ -116,5 +123,16 @@ pub async fn req(
         )
         .await?;
 
+    AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::ChannelCreate,
+        channel.id(),
+    )
+    .with_changes(AuditLogChanges::channel(None, Some(&channel), vec![]))
+    .with_reason(reason.into_inner())
+    .record(db)
+    .await;
+
     Ok(Json(channel))
 }
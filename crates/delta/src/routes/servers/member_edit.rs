@@ -2,10 +2,13 @@ use std::collections::HashSet;
 
 use revolt_quark::{
     models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
         server_member::{FieldsMember, PartialMember},
-        File, Member, User,
+        AuditLogEntry, File, Member, User,
     },
-    perms, Db, Error, Permission, Ref, Result, Timestamp,
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Permission, Ref, Result, Timestamp,
 };
 
 use rocket::serde::json::Json;
@@ -39,6 +42,7 @@ pub async fn req(
     user: User,
     server: Ref,
     target: Ref,
+    reason: AuditLogReason,
     data: Json<DataMemberEdit>,
 ) -> Result<Json<Member>> {
     let data = data.into_inner();
@@ -163,9 +167,27 @@ pub async fn req(
         partial.avatar = Some(File::use_avatar(db, &avatar, &user.id).await?);
     }
 
-    member
-        .update(db, partial, remove.unwrap_or_default())
-        .await?;
+    let before = member.clone();
+    let remove = remove.unwrap_or_default();
+    member.update(db, partial, remove.clone()).await?;
+
+    // Members editing themselves aren't moderation actions.
+    if member.id.user != user.id {
+        AuditLogEntry::new(
+            &server.id,
+            &user.id,
+            AuditLogAction::MemberUpdate,
+            &member.id.user,
+        )
+        .with_changes(AuditLogChanges::member(
+            Some(&before),
+            Some(&member),
+            remove,
+        ))
+        .with_reason(reason.into_inner())
+        .record(db)
+        .await;
+    }
 
     Ok(Json(member))
 }
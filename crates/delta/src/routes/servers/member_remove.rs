@@ -1,6 +1,8 @@
 use revolt_quark::{
-    models::{server_member::RemovalIntention, User},
-    perms, Db, EmptyResponse, Error, Permission, Ref, Result,
+    models::{audit_log::AuditLogAction, server_member::RemovalIntention, AuditLogEntry, User},
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, EmptyResponse, Error, Permission, Ref, Result,
 };
 
 /// # Kick Member
@@ -8,7 +10,13 @@ use revolt_quark::{
 /// Removes a member from the server.
 #[openapi(tag = "Server Members")]
 #[delete("/<target>/members/<member>")]
-pub async fn req(db: &Db, user: User, target: Ref, member: Ref) -> Result<EmptyResponse> {
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    member: Ref,
+    reason: AuditLogReason,
+) -> Result<EmptyResponse> {
     let server = target.as_server(db).await?;
 
     if member.id == user.id {
@@ -33,8 +41,19 @@ pub async fn req(db: &Db, user: User, target: Ref, member: Ref) -> Result<EmptyR
         return Err(Error::NotElevated);
     }
 
+    let entry = AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::MemberKick,
+        &member.id.user,
+    )
+    .with_reason(reason.into_inner());
+
     server
         .remove_member(db, member, RemovalIntention::Kick, false)
-        .await
-        .map(|_| EmptyResponse)
+        .await?;
+
+    entry.record(db).await;
+
+    Ok(EmptyResponse)
 }
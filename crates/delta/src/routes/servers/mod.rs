@@ -1,6 +1,11 @@
 use rocket::Route;
 use rocket_okapi::okapi::openapi3::OpenApi;
 
+mod audit_log_fetch;
+mod automod_create;
+mod automod_delete;
+mod automod_edit;
+mod automod_fetch_all;
 mod ban_create;
 mod ban_list;
 mod ban_remove;
@@ -21,6 +26,7 @@ mod server_create;
 mod server_delete;
 mod server_edit;
 mod server_fetch;
+mod server_search;
 
 pub fn routes() -> (Vec<Route>, OpenApi) {
     openapi_get_routes_spec![
@@ -29,6 +35,7 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         server_fetch::req,
         server_edit::req,
         server_ack::req,
+        server_search::req,
         channel_create::req,
         member_fetch_all::req,
         member_remove::req,
@@ -37,6 +44,11 @@ pub fn routes() -> (Vec<Route>, OpenApi) {
         ban_create::req,
         ban_remove::req,
         ban_list::req,
+        audit_log_fetch::req,
+        automod_create::req,
+        automod_fetch_all::req,
+        automod_edit::req,
+        automod_delete::req,
         invites_fetch::req,
         roles_create::req,
         roles_edit::req,
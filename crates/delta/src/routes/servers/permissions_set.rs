@@ -2,8 +2,13 @@ use rocket::serde::json::Json;
 use serde::Deserialize;
 
 use revolt_quark::{
-    models::{Server, User},
-    perms, Db, Error, Override, Permission, Ref, Result,
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        AuditLogEntry, Server, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Override, Permission, Ref, Result,
 };
 
 /// # Permission Value
@@ -23,6 +28,7 @@ pub async fn req(
     user: User,
     target: Ref,
     role_id: String,
+    reason: AuditLogReason,
     data: Json<DataSetServerRolePermission>,
 ) -> Result<Json<Server>> {
     let data = data.into_inner();
@@ -45,10 +51,21 @@ pub async fn req(
             .throw_permission_override(db, current_value, data.permissions)
             .await?;
 
+        let before = server.roles.get(&role_id).cloned();
         server
             .set_role_permission(db, &role_id, data.permissions.into())
             .await?;
 
+        AuditLogEntry::new(&server.id, &user.id, AuditLogAction::RoleUpdate, &role_id)
+            .with_changes(AuditLogChanges::role(
+                before.as_ref(),
+                server.roles.get(&role_id),
+                vec![],
+            ))
+            .with_reason(reason.into_inner())
+            .record(db)
+            .await;
+
         Ok(Json(server))
     } else {
         Err(Error::NotFound)
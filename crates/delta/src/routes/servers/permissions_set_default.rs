@@ -2,8 +2,14 @@ use rocket::serde::json::Json;
 use serde::Deserialize;
 
 use revolt_quark::{
-    models::{server::PartialServer, Server, User},
-    perms, Db, Permission, Ref, Result,
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        server::PartialServer,
+        AuditLogEntry, Server, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Permission, Ref, Result,
 };
 
 /// # Permission Value
@@ -22,6 +28,7 @@ pub async fn req(
     db: &Db,
     user: User,
     target: Ref,
+    reason: AuditLogReason,
     data: Json<DataSetServerDefaultPermission>,
 ) -> Result<Json<Server>> {
     let data = data.into_inner();
@@ -37,6 +44,7 @@ pub async fn req(
         .throw_permission_value(db, data.permissions)
         .await?;
 
+    let before = server.clone();
     server
         .update(
             db,
@@ -48,5 +56,20 @@ pub async fn req(
         )
         .await?;
 
+    AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::ServerUpdate,
+        &server.id,
+    )
+    .with_changes(AuditLogChanges::server(
+        Some(&before),
+        Some(&server),
+        vec![],
+    ))
+    .with_reason(reason.into_inner())
+    .record(db)
+    .await;
+
     Ok(Json(server))
 }
@@ -1,6 +1,12 @@
 use revolt_quark::{
-    models::{server::Role, User},
-    perms, Db, Error, Permission, Ref, Result,
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        server::Role,
+        AuditLogEntry, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Permission, Ref, Result,
 };
 
 use rocket::serde::json::Json;
@@ -37,6 +43,7 @@ pub async fn req(
     db: &Db,
     user: User,
     target: Ref,
+    reason: AuditLogReason,
     data: Json<DataCreateRole>,
 ) -> Result<Json<NewRoleResponse>> {
     let data = data.into_inner();
@@ -67,8 +74,13 @@ pub async fn req(
         ..Default::default()
     };
 
-    Ok(Json(NewRoleResponse {
-        id: role.create(db, &server.id).await?,
-        role,
-    }))
+    let id = role.create(db, &server.id).await?;
+
+    AuditLogEntry::new(&server.id, &user.id, AuditLogAction::RoleCreate, &id)
+        .with_changes(AuditLogChanges::role(None, Some(&role), vec![]))
+        .with_reason(reason.into_inner())
+        .record(db)
+        .await;
+
+    Ok(Json(NewRoleResponse { id, role }))
 }
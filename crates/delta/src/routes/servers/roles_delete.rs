@@ -1,11 +1,25 @@
-use revolt_quark::{models::User, perms, Db, EmptyResponse, Error, Permission, Ref, Result};
+use revolt_quark::{
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        AuditLogEntry, User,
+    },
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, EmptyResponse, Error, Permission, Ref, Result,
+};
 
 /// # Delete Role
 ///
 /// Delete a server role by its id.
 #[openapi(tag = "Server Permissions")]
 #[delete("/<target>/roles/<role_id>")]
-pub async fn req(db: &Db, user: User, target: Ref, role_id: String) -> Result<EmptyResponse> {
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    role_id: String,
+    reason: AuditLogReason,
+) -> Result<EmptyResponse> {
     let mut server = target.as_server(db).await?;
     let mut permissions = perms(&user).server(&server);
 
@@ -20,9 +34,14 @@ pub async fn req(db: &Db, user: User, target: Ref, role_id: String) -> Result<Em
             return Err(Error::NotElevated);
         }
 
-        role.delete(db, &server.id, &role_id)
-            .await
-            .map(|_| EmptyResponse)
+        let entry = AuditLogEntry::new(&server.id, &user.id, AuditLogAction::RoleDelete, &role_id)
+            .with_changes(AuditLogChanges::role(Some(&role), None, vec![]))
+            .with_reason(reason.into_inner());
+
+        role.delete(db, &server.id, &role_id).await?;
+        entry.record(db).await;
+
+        Ok(EmptyResponse)
     } else {
         Err(Error::NotFound)
     }
@@ -1,10 +1,12 @@
 use revolt_quark::{
     models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
         server::{FieldsRole, PartialRole, Role},
-        User,
+        AuditLogEntry, User,
     },
     perms,
     util::regex::RE_COLOUR,
+    web::audit_log::AuditLogReason,
     Db, Error, Permission, Ref, Result,
 };
 
@@ -42,6 +44,7 @@ pub async fn req(
     user: User,
     target: Ref,
     role_id: String,
+    reason: AuditLogReason,
     data: Json<DataEditRole>,
 ) -> Result<Json<Role>> {
     let data = data.into_inner();
@@ -80,14 +83,16 @@ pub async fn req(
             ..Default::default()
         };
 
-        role.update(
-            db,
-            &server.id,
-            &role_id,
-            partial,
-            remove.unwrap_or_default(),
-        )
-        .await?;
+        let before = role.clone();
+        let remove = remove.unwrap_or_default();
+        role.update(db, &server.id, &role_id, partial, remove.clone())
+            .await?;
+
+        AuditLogEntry::new(&server.id, &user.id, AuditLogAction::RoleUpdate, &role_id)
+            .with_changes(AuditLogChanges::role(Some(&before), Some(&role), remove))
+            .with_reason(reason.into_inner())
+            .record(db)
+            .await;
 
         Ok(Json(role))
     } else {
@@ -2,10 +2,13 @@ use std::collections::HashSet;
 
 use revolt_quark::{
     models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
         server::{Category, FieldsServer, PartialServer, SystemMessageChannels},
-        File, Server, User,
+        AuditLogEntry, File, Server, User,
     },
-    perms, Db, Error, Permission, Ref, Result,
+    perms,
+    web::audit_log::AuditLogReason,
+    Db, Error, Permission, Ref, Result,
 };
 
 use rocket::serde::json::Json;
@@ -41,6 +44,11 @@ pub struct DataEditServer {
     ///
     /// Must be enabled in order to show up on [Revolt Discover](https://rvlt.gg).
     analytics: Option<bool>,
+    /// Number of days to keep message edit history for
+    ///
+    /// Set to 0 to stop recording edits.
+    #[validate(range(max = 365))]
+    message_history_retention: Option<u32>,
 
     /// Fields to remove from server object
     #[validate(length(min = 1))]
@@ -56,6 +64,7 @@ pub async fn req(
     db: &Db,
     user: User,
     target: Ref,
+    reason: AuditLogReason,
     data: Json<DataEditServer>,
 ) -> Result<Json<Server>> {
     let data = data.into_inner();
@@ -75,6 +84,7 @@ pub async fn req(
         && data.categories.is_none()
         // && data.nsfw.is_none()
         && data.analytics.is_none()
+        && data.message_history_retention.is_none()
         && data.remove.is_none()
     {
         return Ok(Json(server));
@@ -85,6 +95,7 @@ pub async fn req(
         || data.system_messages.is_some()
         // || data.nsfw.is_some()
         || data.analytics.is_some()
+        || data.message_history_retention.is_some()
         || data.remove.is_some()
     {
         permissions
@@ -108,6 +119,7 @@ pub async fn req(
         // nsfw,
         discoverable,
         analytics,
+        message_history_retention,
         remove,
     } = data;
 
@@ -119,9 +131,12 @@ pub async fn req(
         // nsfw,
         discoverable,
         analytics,
+        message_history_retention,
         ..Default::default()
     };
 
+    let before = server.clone();
+
     // 1. Remove fields from object
     if let Some(fields) = &remove {
         if fields.contains(&FieldsServer::Banner) {
@@ -175,9 +190,23 @@ pub async fn req(
         server.banner = partial.banner.clone();
     }
 
-    server
-        .update(db, partial, remove.unwrap_or_default())
-        .await?;
+    let remove = remove.unwrap_or_default();
+    server.update(db, partial, remove.clone()).await?;
+
+    AuditLogEntry::new(
+        &server.id,
+        &user.id,
+        AuditLogAction::ServerUpdate,
+        &server.id,
+    )
+    .with_changes(AuditLogChanges::server(
+        Some(&before),
+        Some(&server),
+        remove,
+    ))
+    .with_reason(reason.into_inner())
+    .record(db)
+    .await;
 
     Ok(Json(server))
 }
@@ -0,0 +1,93 @@
+use revolt_quark::{
+    models::{
+        message::{MessageFilter, MessageSort, ServerSearchResponse},
+        User,
+    },
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// # Search Parameters
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct OptionsServerSearch {
+    /// Criteria which messages must match
+    #[serde(flatten)]
+    #[validate]
+    filter: MessageFilter,
+
+    /// Maximum number of messages to fetch across all channels
+    #[validate(range(min = 1, max = 100))]
+    limit: Option<i64>,
+    /// Message sort direction
+    ///
+    /// By default, it will be sorted by relevance, or latest first if no query is given.
+    #[serde(default = "MessageSort::default")]
+    sort: MessageSort,
+    /// Whether to include user and member objects
+    include_users: Option<bool>,
+}
+
+/// # Search Server for Messages
+///
+/// Search for messages in every channel of a server which you can read the history of.
+#[openapi(tag = "Server Information")]
+#[post("/<target>/search", data = "<options>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    options: Json<OptionsServerSearch>,
+) -> Result<Json<ServerSearchResponse>> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let options = options.into_inner();
+    options
+        .validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let server = target.as_server(db).await?;
+    let member = db.fetch_member(&server.id, &user.id).await?;
+
+    // Find every channel and thread we are allowed to read.
+    let mut channels = db.fetch_channels(&server.channels).await?;
+    channels.append(&mut db.fetch_threads(&server.channels).await?);
+
+    let perm = perms(&user).server(&server).member(&member);
+    let mut channel_ids = vec![];
+    for channel in &channels {
+        if perm
+            .clone()
+            .channel(channel)
+            .has_permission_value(
+                db,
+                Permission::ViewChannel as u64 | Permission::ReadMessageHistory as u64,
+            )
+            .await?
+        {
+            channel_ids.push(channel.id().to_string());
+        }
+    }
+
+    let OptionsServerSearch {
+        filter,
+        limit,
+        sort,
+        include_users,
+    } = options;
+
+    let messages = if channel_ids.is_empty() {
+        vec![]
+    } else {
+        db.search_messages(&channel_ids, &filter, limit, sort)
+            .await?
+    };
+
+    ServerSearchResponse::transform(db, &server, messages, include_users)
+        .await
+        .map(Json)
+}
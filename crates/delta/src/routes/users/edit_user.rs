@@ -1,5 +1,6 @@
 use revolt_quark::models::user::{FieldsUser, PartialUser, User};
 use revolt_quark::models::File;
+use revolt_quark::presence::presence_populate;
 use revolt_quark::{Database, Error, Result};
 
 use revolt_quark::models::user::UserStatus;
@@ -121,5 +122,6 @@ pub async fn req(
     user.update(db, partial, data.remove.unwrap_or_default())
         .await?;
 
+    presence_populate(&mut user).await;
     Ok(Json(user.foreign()))
 }
@@ -1,4 +1,6 @@
-use revolt_quark::{models::User, perms, Database, Error, Ref, Result};
+use revolt_quark::{
+    models::User, perms, presence::presence_populate, Database, Error, Ref, Result,
+};
 
 use rocket::{serde::json::Json, State};
 
@@ -7,8 +9,9 @@ use rocket::{serde::json::Json, State};
 /// Retrieve a user's information.
 #[openapi(tag = "User Information")]
 #[get("/<target>")]
-pub async fn req(db: &State<Database>, user: User, target: Ref) -> Result<Json<User>> {
+pub async fn req(db: &State<Database>, mut user: User, target: Ref) -> Result<Json<User>> {
     if target.id == user.id {
+        presence_populate(&mut user).await;
         return Ok(Json(user));
     }
 
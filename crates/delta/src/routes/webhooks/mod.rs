@@ -0,0 +1,18 @@
+use rocket::Route;
+use rocket_okapi::okapi::openapi3::OpenApi;
+
+mod webhook_delete;
+mod webhook_edit;
+mod webhook_execute;
+mod webhook_fetch;
+mod webhook_fetch_token;
+
+pub fn routes() -> (Vec<Route>, OpenApi) {
+    openapi_get_routes_spec![
+        webhook_fetch::req,
+        webhook_fetch_token::req,
+        webhook_edit::req,
+        webhook_delete::req,
+        webhook_execute::req
+    ]
+}
@@ -0,0 +1,21 @@
+use revolt_quark::{models::User, perms, Db, EmptyResponse, Error, Permission, Ref, Result};
+
+/// # Delete Webhook
+///
+/// Delete a webhook by its id.
+#[openapi(tag = "Webhooks")]
+#[delete("/<target>")]
+pub async fn req(db: &Db, user: User, target: Ref) -> Result<EmptyResponse> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let webhook = target.as_webhook(db).await?;
+    let channel = db.fetch_channel(&webhook.channel_id).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ManageWebhooks)
+        .await?;
+
+    webhook.delete(db).await.map(|_| EmptyResponse)
+}
@@ -0,0 +1,82 @@
+use revolt_quark::{
+    models::{
+        webhook::{FieldsWebhook, PartialWebhook},
+        File, User, Webhook,
+    },
+    perms, Db, Error, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// # Webhook Details
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct DataEditWebhook {
+    /// Webhook name
+    #[validate(length(min = 1, max = 32))]
+    name: Option<String>,
+    /// Autumn file id used for the webhook avatar
+    #[validate(length(min = 1, max = 128))]
+    avatar: Option<String>,
+    /// Fields to remove from webhook object
+    #[validate(length(min = 1))]
+    remove: Option<Vec<FieldsWebhook>>,
+}
+
+/// # Edit Webhook
+///
+/// Edit a webhook by its id.
+#[openapi(tag = "Webhooks")]
+#[patch("/<target>", data = "<data>")]
+pub async fn req(
+    db: &Db,
+    user: User,
+    target: Ref,
+    data: Json<DataEditWebhook>,
+) -> Result<Json<Webhook>> {
+    if user.bot.is_some() {
+        return Err(Error::IsBot);
+    }
+
+    let data = data.into_inner();
+    data.validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    let mut webhook = target.as_webhook(db).await?;
+    let channel = db.fetch_channel(&webhook.channel_id).await?;
+    perms(&user)
+        .channel(&channel)
+        .throw_permission_and_view_channel(db, Permission::ManageWebhooks)
+        .await?;
+
+    if data.name.is_none() && data.avatar.is_none() && data.remove.is_none() {
+        return Ok(Json(webhook));
+    }
+
+    let DataEditWebhook {
+        name,
+        avatar,
+        remove,
+    } = data;
+
+    let mut partial = PartialWebhook {
+        name,
+        ..Default::default()
+    };
+
+    if let Some(avatar) = avatar {
+        partial.avatar = Some(File::use_webhook_avatar(db, &avatar, &webhook.id).await?);
+    }
+
+    // The old avatar is no longer used once it is removed or replaced.
+    let remove = remove.unwrap_or_default();
+    if remove.contains(&FieldsWebhook::Avatar) || partial.avatar.is_some() {
+        if let Some(avatar) = &webhook.avatar {
+            db.mark_attachment_as_deleted(&avatar.id).await?;
+        }
+    }
+
+    webhook.update(db, partial, remove).await?;
+    Ok(Json(webhook))
+}
@@ -0,0 +1,107 @@
+use revolt_quark::{
+    models::{
+        message::{Masquerade, SendableEmbed},
+        Message,
+    },
+    Db, Error, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+use serde::{Deserialize, Serialize};
+use ulid::Ulid;
+use validator::Validate;
+
+#[derive(Validate, Serialize, Deserialize, JsonSchema)]
+pub struct DataWebhookExecute {
+    /// Message content to send
+    #[validate(length(min = 0, max = 2000))]
+    content: Option<String>,
+    /// Embeds to include in message
+    ///
+    /// Text embed content contributes to the content length cap
+    #[validate(length(min = 1, max = 10))]
+    embeds: Option<Vec<SendableEmbed>>,
+    /// Masquerade to apply to this message
+    ///
+    /// Overrides the webhook's own name and avatar, colour cannot be set.
+    #[validate]
+    masquerade: Option<Masquerade>,
+}
+
+/// # Execute Webhook
+///
+/// Sends a message to the webhook's channel using its token.
+#[openapi(tag = "Webhooks")]
+#[post("/<target>/<token>", data = "<data>")]
+pub async fn req(
+    db: &Db,
+    target: Ref,
+    token: String,
+    data: Json<DataWebhookExecute>,
+) -> Result<Json<Message>> {
+    let data = data.into_inner();
+    data.validate()
+        .map_err(|error| Error::FailedValidation { error })?;
+
+    Message::validate_sum(&data.content, &data.embeds)?;
+
+    let webhook = target.as_webhook(db).await?;
+    if !webhook.has_token(&token) {
+        return Err(Error::NotFound);
+    }
+
+    if (data.content.as_ref().map_or(true, |v| v.is_empty()))
+        && (data.embeds.as_ref().map_or(true, |v| v.is_empty()))
+    {
+        return Err(Error::EmptyMessage);
+    }
+
+    let (name, avatar) = match data.masquerade {
+        Some(Masquerade {
+            colour: Some(_), ..
+        }) => return Err(Error::InvalidOperation),
+        Some(Masquerade { name, avatar, .. }) => (name, avatar),
+        None => (None, None),
+    };
+
+    let channel = db.fetch_channel(&webhook.channel_id).await?;
+    let message_id = Ulid::new().to_string();
+    let mut message = Message {
+        id: message_id.clone(),
+        channel: channel.id().to_string(),
+        author: webhook.id.clone(),
+        masquerade: Some(Masquerade {
+            name: name.or_else(|| Some(webhook.name.clone())),
+            avatar: avatar.or_else(|| webhook.avatar_url()),
+            colour: None,
+        }),
+        content: data.content,
+        ..Default::default()
+    };
+
+    let mut embeds = vec![];
+    if let Some(sendable_embeds) = data.embeds {
+        for sendable_embed in sendable_embeds {
+            embeds.push(sendable_embed.into_embed(db, message_id.clone()).await?)
+        }
+    }
+
+    if !embeds.is_empty() {
+        message.embeds.replace(embeds);
+    }
+
+    message.create(db, &channel, None).await?;
+
+    // Queue up a task for processing embeds
+    if let Some(content) = &message.content {
+        revolt_quark::tasks::process_embeds::queue(
+            db,
+            channel.id().to_string(),
+            message.id.to_string(),
+            content.clone(),
+        )
+        .await;
+    }
+
+    Ok(Json(message))
+}
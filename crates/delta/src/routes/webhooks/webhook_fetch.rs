@@ -0,0 +1,32 @@
+use revolt_quark::{
+    models::{User, Webhook},
+    perms, Db, Permission, Ref, Result,
+};
+
+use rocket::serde::json::Json;
+
+/// # Fetch Webhook
+///
+/// Fetch a webhook by its id.
+///
+/// The token is only included if you can manage webhooks in its channel.
+#[openapi(tag = "Webhooks")]
+#[get("/<target>")]
+pub async fn req(db: &Db, user: User, target: Ref) -> Result<Json<Webhook>> {
+    let webhook = target.as_webhook(db).await?;
+    let channel = db.fetch_channel(&webhook.channel_id).await?;
+
+    let mut permissions = perms(&user).channel(&channel);
+    permissions
+        .throw_permission(db, Permission::ViewChannel)
+        .await?;
+
+    if permissions
+        .has_permission(db, Permission::ManageWebhooks)
+        .await?
+    {
+        Ok(Json(webhook))
+    } else {
+        Ok(Json(webhook.without_token()))
+    }
+}
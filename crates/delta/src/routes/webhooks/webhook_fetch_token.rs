@@ -0,0 +1,17 @@
+use revolt_quark::{models::Webhook, Db, Error, Ref, Result};
+
+use rocket::serde::json::Json;
+
+/// # Fetch Webhook with Token
+///
+/// Fetch a webhook by its id using its token.
+#[openapi(tag = "Webhooks")]
+#[get("/<target>/<token>")]
+pub async fn req(db: &Db, target: Ref, token: String) -> Result<Json<Webhook>> {
+    let webhook = target.as_webhook(db).await?;
+    if !webhook.has_token(&token) {
+        return Err(Error::NotFound);
+    }
+
+    Ok(Json(webhook))
+}
@@ -12,3 +12,8 @@ pub static RE_USERNAME: Lazy<Regex> =
 ///
 /// Alphanumeric and underscores
 pub static RE_EMOJI: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9_]+$").unwrap());
+
+/// Regex for valid command names
+///
+/// Lowercase alphanumeric, dashes and underscores
+pub static RE_COMMAND: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[a-z0-9_-]+$").unwrap());
@@ -6,7 +6,7 @@ use revolt_quark::*;
 
 #[async_std::main]
 async fn main() {
-    let db = DatabaseInfo::Dummy.connect().await.unwrap();
+    let db = DatabaseInfo::Reference.connect().await.unwrap();
 
     let sus = PartialUser {
         username: Some("neat".into()),
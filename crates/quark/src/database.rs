@@ -1,28 +1,37 @@
 use std::env;
 use std::ops::Deref;
 
-use crate::r#impl::{DummyDb, MongoDb};
+use crate::r#impl::{MongoDb, ReferenceDb};
 use crate::AbstractDatabase;
 
+#[cfg(feature = "postgres")]
+use crate::r#impl::PostgresDb;
+
 /// Database information to use to create a client
 pub enum DatabaseInfo {
     /// Auto-detect the database in use
     Auto,
-    /// Use the mock database
-    Dummy,
+    /// Use the in-memory reference database
+    Reference,
     /// Connect to MongoDB
     MongoDb(String),
     /// Use existing MongoDB connection
     MongoDbFromClient(mongodb::Client),
+    /// Connect to PostgreSQL
+    #[cfg(feature = "postgres")]
+    Postgres(String),
 }
 
 /// Database
 #[derive(Debug, Clone)]
 pub enum Database {
-    /// Mock database
-    Dummy(DummyDb),
+    /// In-memory reference database
+    Reference(ReferenceDb),
     /// MongoDB database
     MongoDb(MongoDb),
+    /// PostgreSQL database
+    #[cfg(feature = "postgres")]
+    Postgres(PostgresDb),
 }
 
 impl DatabaseInfo {
@@ -35,9 +44,14 @@ impl DatabaseInfo {
                     return DatabaseInfo::MongoDb(uri).connect().await;
                 }
 
-                DatabaseInfo::Dummy.connect().await?
+                #[cfg(feature = "postgres")]
+                if let Ok(uri) = env::var("POSTGRES") {
+                    return DatabaseInfo::Postgres(uri).connect().await;
+                }
+
+                DatabaseInfo::Reference.connect().await?
             }
-            DatabaseInfo::Dummy => Database::Dummy(DummyDb),
+            DatabaseInfo::Reference => Database::Reference(Default::default()),
             DatabaseInfo::MongoDb(uri) => {
                 let client = mongodb::Client::with_uri_str(uri)
                     .await
@@ -46,6 +60,14 @@ impl DatabaseInfo {
                 Database::MongoDb(MongoDb(client))
             }
             DatabaseInfo::MongoDbFromClient(client) => Database::MongoDb(MongoDb(client)),
+            #[cfg(feature = "postgres")]
+            DatabaseInfo::Postgres(uri) => {
+                let pool = sqlx::PgPool::connect(&uri)
+                    .await
+                    .map_err(|_| "Failed to init db connection.".to_string())?;
+
+                Database::Postgres(PostgresDb(pool))
+            }
         })
     }
 }
@@ -55,19 +77,31 @@ impl Deref for Database {
 
     fn deref(&self) -> &Self::Target {
         match self {
-            Database::Dummy(dummy) => dummy,
+            Database::Reference(reference) => reference,
             Database::MongoDb(mongo) => mongo,
+            #[cfg(feature = "postgres")]
+            Database::Postgres(postgres) => postgres,
         }
     }
 }
 
-impl From<Database> for rauth::Database {
-    fn from(val: Database) -> Self {
+impl TryFrom<Database> for rauth::Database {
+    type Error = String;
+
+    /// Hand the database over to rAuth for account and session storage
+    ///
+    /// rAuth has no PostgreSQL driver, so this fails rather than silently
+    /// keeping accounts in memory where they would be lost on restart.
+    fn try_from(val: Database) -> Result<Self, Self::Error> {
         match val {
-            Database::Dummy(_) => rauth::Database::default(),
-            Database::MongoDb(MongoDb(client)) => {
-                rauth::Database::MongoDb(rauth::database::MongoDb(client.database("revolt")))
+            Database::Reference(_) => Ok(rauth::Database::default()),
+            #[cfg(feature = "postgres")]
+            Database::Postgres(_) => {
+                Err("Authentication is not supported on PostgreSQL yet.".to_string())
             }
+            Database::MongoDb(MongoDb(client)) => Ok(rauth::Database::MongoDb(
+                rauth::database::MongoDb(client.database("revolt")),
+            )),
         }
     }
 }
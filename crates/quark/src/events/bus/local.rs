@@ -0,0 +1,106 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use futures::{
+    channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    StreamExt,
+};
+
+use super::{EventBus, Subscriber};
+use crate::{events::client::EventV1, Result};
+
+/// Channel through which events reach a subscriber
+type Sink = UnboundedSender<(String, EventV1)>;
+
+/// Subscribers of each topic by subscriber id
+type Topics = Arc<Mutex<HashMap<String, HashMap<u64, Sink>>>>;
+
+/// Event bus which only delivers events within the current process
+///
+/// Useful for running every service in a single binary or in tests.
+#[derive(Default, Clone)]
+pub struct LocalEventBus {
+    topics: Topics,
+    next_id: Arc<AtomicU64>,
+}
+
+/// Subscriber to the in-process event bus
+pub struct LocalSubscriber {
+    id: u64,
+    topics: Topics,
+    subscribed: HashSet<String>,
+    sink: Sink,
+    events: UnboundedReceiver<(String, EventV1)>,
+}
+
+#[async_trait]
+impl EventBus for LocalEventBus {
+    async fn publish(&self, topic: String, event: EventV1) {
+        let mut topics = self.topics.lock().unwrap();
+        if let Some(subscribers) = topics.get_mut(&topic) {
+            subscribers
+                .retain(|_, sink| sink.unbounded_send((topic.clone(), event.clone())).is_ok());
+        }
+    }
+
+    async fn subscriber(&self) -> Result<Box<dyn Subscriber>> {
+        let (sink, events) = unbounded();
+        Ok(Box::new(LocalSubscriber {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            topics: self.topics.clone(),
+            subscribed: HashSet::new(),
+            sink,
+            events,
+        }))
+    }
+}
+
+impl LocalSubscriber {
+    /// Remove this subscriber from a topic
+    fn remove(&self, topics: &mut HashMap<String, HashMap<u64, Sink>>, topic: &str) {
+        if let Some(subscribers) = topics.get_mut(topic) {
+            subscribers.remove(&self.id);
+
+            if subscribers.is_empty() {
+                topics.remove(topic);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Subscriber for LocalSubscriber {
+    async fn subscribe(&mut self, topic: &str) {
+        self.topics
+            .lock()
+            .unwrap()
+            .entry(topic.to_string())
+            .or_default()
+            .insert(self.id, self.sink.clone());
+
+        self.subscribed.insert(topic.to_string());
+    }
+
+    async fn unsubscribe(&mut self, topic: &str) {
+        self.remove(&mut self.topics.lock().unwrap(), topic);
+        self.subscribed.remove(topic);
+    }
+
+    async fn next(&mut self) -> Option<(String, EventV1)> {
+        self.events.next().await
+    }
+}
+
+impl Drop for LocalSubscriber {
+    fn drop(&mut self) {
+        let mut topics = self.topics.lock().unwrap();
+        for topic in &self.subscribed {
+            self.remove(&mut topics, topic);
+        }
+    }
+}
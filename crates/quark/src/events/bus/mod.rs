@@ -0,0 +1,66 @@
+//! Publishing and subscribing to events
+//!
+//! Redis is used by default so that events reach every node,
+//! single node deployments can switch to the in-process bus.
+
+use once_cell::sync::OnceCell;
+
+use super::client::EventV1;
+use crate::{variables::delta::USE_LOCAL_EVENTS, Result};
+
+mod local;
+mod redis;
+
+pub use self::local::{LocalEventBus, LocalSubscriber};
+pub use self::redis::{RedisEventBus, RedisSubscriber};
+
+/// Publisher of events, able to hand out subscribers
+#[async_trait]
+pub trait EventBus: Sync + Send {
+    /// Publish an event to everyone subscribed to a topic
+    async fn publish(&self, topic: String, event: EventV1);
+
+    /// Open a new subscriber which starts out without any topics
+    async fn subscriber(&self) -> Result<Box<dyn Subscriber>>;
+}
+
+/// Receiver of events for a changing set of topics
+#[async_trait]
+pub trait Subscriber: Send {
+    /// Start receiving events published to a topic
+    async fn subscribe(&mut self, topic: &str);
+
+    /// Stop receiving events published to a topic
+    async fn unsubscribe(&mut self, topic: &str);
+
+    /// Wait for the next event along with the topic it was published to
+    ///
+    /// Returns None once no more events can be received.
+    async fn next(&mut self) -> Option<(String, EventV1)>;
+}
+
+static EVENT_BUS: OnceCell<Box<dyn EventBus>> = OnceCell::new();
+
+/// Use a different event bus for this process
+///
+/// Must be called before any events are published or subscribed to.
+pub fn set_event_bus<B: EventBus + 'static>(bus: B) {
+    if EVENT_BUS.set(Box::new(bus)).is_err() {
+        panic!("Event bus was already initialised.");
+    }
+}
+
+/// Use the event bus selected through `REVOLT_EVENT_BUS`
+///
+/// Should be called on startup, before any events are published or subscribed to.
+pub fn configure_event_bus() {
+    if *USE_LOCAL_EVENTS {
+        info!("Using the in-process event bus");
+        set_event_bus(LocalEventBus::default());
+    }
+}
+
+/// Get the event bus for this process, Redis unless configured otherwise
+pub fn event_bus() -> &'static dyn EventBus {
+    EVENT_BUS.get_or_init(|| Box::new(RedisEventBus)).as_ref()
+}
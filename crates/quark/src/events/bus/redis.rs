@@ -0,0 +1,129 @@
+use async_std::task;
+use futures::{
+    channel::mpsc::{channel, unbounded, Receiver, UnboundedSender},
+    future::Either,
+    pin_mut, select, FutureExt, StreamExt,
+};
+
+use super::{EventBus, Subscriber};
+use crate::{events::client::EventV1, metrics::REDIS_ERRORS, Error, Result};
+
+/// Number of events a subscriber may fall behind by before it is disconnected
+const SUBSCRIBER_BUFFER: usize = 1024;
+
+/// Event bus backed by Redis pub/sub, shared between all nodes
+pub struct RedisEventBus;
+
+/// Change to the topics a Redis subscriber is listening to
+enum Command {
+    Subscribe(String),
+    Unsubscribe(String),
+}
+
+/// Subscriber to Redis pub/sub
+///
+/// The connection itself is driven by a background task, which closes
+/// it if the subscriber does not keep up with incoming events.
+pub struct RedisSubscriber {
+    commands: UnboundedSender<Command>,
+    events: Receiver<(String, EventV1)>,
+}
+
+#[async_trait]
+impl EventBus for RedisEventBus {
+    async fn publish(&self, topic: String, event: EventV1) {
+        #[cfg(not(debug_assertions))]
+        if let Err(err) = redis_kiss::publish(topic, event).await {
+            REDIS_ERRORS.with_label_values(&["publish"]).inc();
+            error!("Failed to publish event: {err:?}");
+        }
+
+        #[cfg(debug_assertions)]
+        redis_kiss::publish(topic, event).await.unwrap();
+    }
+
+    async fn subscriber(&self) -> Result<Box<dyn Subscriber>> {
+        let mut conn = redis_kiss::open_pubsub_connection().await.map_err(|_| {
+            REDIS_ERRORS
+                .with_label_values(&["open_pubsub_connection"])
+                .inc();
+            Error::InternalError
+        })?;
+
+        let (commands, mut receiver) = unbounded::<Command>();
+        let (mut sink, events) = channel(SUBSCRIBER_BUFFER);
+
+        task::spawn(async move {
+            loop {
+                let next = {
+                    let message = conn.on_message().next().fuse();
+                    let command = receiver.next().fuse();
+                    pin_mut!(message, command);
+
+                    select!(
+                        item = message => Either::Left(item.map(|item| {
+                            (
+                                item.get_channel_name().to_string(),
+                                redis_kiss::decode_payload::<EventV1>(&item),
+                            )
+                        })),
+                        command = command => Either::Right(command)
+                    )
+                };
+
+                match next {
+                    Either::Left(Some((topic, Ok(event)))) => {
+                        if let Err(err) = sink.try_send((topic, event)) {
+                            if err.is_full() {
+                                REDIS_ERRORS
+                                    .with_label_values(&["subscriber_overflow"])
+                                    .inc();
+                                warn!("Subscriber fell too far behind, disconnecting it.");
+                            }
+
+                            break;
+                        }
+                    }
+                    Either::Left(Some((topic, Err(_)))) => {
+                        warn!("Failed to deserialise an event for {topic}!");
+                    }
+                    Either::Right(Some(Command::Subscribe(topic))) => {
+                        if let Err(err) = conn.subscribe(&topic).await {
+                            REDIS_ERRORS.with_label_values(&["subscribe"]).inc();
+                            error!("Failed to subscribe to {topic}: {err:?}");
+                        }
+                    }
+                    Either::Right(Some(Command::Unsubscribe(topic))) => {
+                        if let Err(err) = conn.unsubscribe(&topic).await {
+                            REDIS_ERRORS.with_label_values(&["unsubscribe"]).inc();
+                            error!("Failed to unsubscribe from {topic}: {err:?}");
+                        }
+                    }
+                    // Either the connection dropped or the subscriber went away.
+                    Either::Left(None) | Either::Right(None) => break,
+                }
+            }
+        });
+
+        Ok(Box::new(RedisSubscriber { commands, events }))
+    }
+}
+
+#[async_trait]
+impl Subscriber for RedisSubscriber {
+    async fn subscribe(&mut self, topic: &str) {
+        self.commands
+            .unbounded_send(Command::Subscribe(topic.to_string()))
+            .ok();
+    }
+
+    async fn unsubscribe(&mut self, topic: &str) {
+        self.commands
+            .unbounded_send(Command::Unsubscribe(topic.to_string()))
+            .ok();
+    }
+
+    async fn next(&mut self) -> Option<(String, EventV1)> {
+        self.events.next().await
+    }
+}
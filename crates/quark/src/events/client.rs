@@ -5,7 +5,8 @@ use crate::models::message::{AppendMessage, PartialMessage};
 use crate::models::server::{FieldsRole, FieldsServer, PartialRole, PartialServer};
 use crate::models::server_member::{FieldsMember, MemberCompositeKey, PartialMember};
 use crate::models::user::{FieldsUser, PartialUser, RelationshipStatus};
-use crate::models::{Channel, Emoji, Member, Message, Server, User, UserSettings};
+use crate::models::webhook::{FieldsWebhook, PartialWebhook};
+use crate::models::{Channel, Emoji, Member, Message, Server, User, UserSettings, Webhook};
 use crate::Error;
 
 /// WebSocket Client Errors
@@ -28,6 +29,34 @@ pub enum Ping {
     Number(usize),
 }
 
+/// Entry in a server member list
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum MemberListItem {
+    /// Header for a group of members
+    Group { id: String },
+    /// Member along with their user
+    Member { member: Member, user: User },
+}
+
+/// Number of members in a member list group
+///
+/// Groups are identified by the id of a hoisted role,
+/// or `online` / `offline` for everyone else.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct MemberListGroup {
+    pub id: String,
+    pub count: usize,
+}
+
+/// Contents of a subscribed member list range
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct MemberListRange {
+    /// Inclusive start and end index
+    pub range: [usize; 2],
+    pub items: Vec<MemberListItem>,
+}
+
 /// Untagged Error
 #[derive(Serialize)]
 #[serde(untagged)]
@@ -44,7 +73,16 @@ pub enum EventV1 {
     Bulk { v: Vec<EventV1> },
 
     /// Successfully authenticated
-    Authenticated,
+    ///
+    /// Clients must send a ping at least every `heartbeat` seconds
+    /// or the connection will be closed.
+    Authenticated { heartbeat: u64 },
+
+    /// Session which can be resumed if the connection drops
+    Session { id: String },
+
+    /// Successfully resumed a previous session, missed events follow
+    Resumed { id: String },
 
     /// Basic data to cache
     Ready {
@@ -117,6 +155,19 @@ pub enum EventV1 {
     /// Delete channel
     ChannelDelete { id: String },
 
+    /// New thread
+    ThreadCreate(Channel),
+
+    /// Update existing thread
+    ThreadUpdate {
+        id: String,
+        data: PartialChannel,
+        clear: Vec<FieldsChannel>,
+    },
+
+    /// Delete thread
+    ThreadDelete { id: String },
+
     /// User joins a group
     ChannelGroupJoin { id: String, user: String },
 
@@ -160,6 +211,14 @@ pub enum EventV1 {
         clear: Vec<FieldsMember>,
     },
 
+    /// Member list changed within subscribed ranges
+    ServerMemberList {
+        id: String,
+        online: usize,
+        groups: Vec<MemberListGroup>,
+        ranges: Vec<MemberListRange>,
+    },
+
     /// User joins server
     ServerMemberJoin { id: String, user: String },
 
@@ -200,4 +259,17 @@ pub enum EventV1 {
 
     /// Delete emoji
     EmojiDelete { id: String },
+
+    /// New webhook
+    WebhookCreate(Webhook),
+
+    /// Update existing webhook
+    WebhookUpdate {
+        id: String,
+        data: PartialWebhook,
+        clear: Vec<FieldsWebhook>,
+    },
+
+    /// Delete webhook
+    WebhookDelete { id: String },
 }
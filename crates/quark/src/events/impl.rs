@@ -1,18 +1,21 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, future::Future};
+
+use futures::future::join_all;
 
 use crate::{
     get_relationship,
     models::{
         server_member::FieldsMember,
-        user::{PartialUser, Presence, RelationshipStatus},
+        user::{PartialUser, Presence, RelationshipStatus, UserStatus},
         Channel, Member, User,
     },
     perms,
-    presence::presence_filter_online,
+    presence::{presence_effective, presence_filter_online, presence_populate},
     Database, Permission, Result,
 };
 
 use super::{
+    bus::event_bus,
     client::EventV1,
     state::{Cache, State},
 };
@@ -22,7 +25,9 @@ impl Cache {
     /// Check whether the current user can view a channel
     pub async fn can_view_channel(&self, db: &Database, channel: &Channel) -> bool {
         match &channel {
-            Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } => {
+            Channel::TextChannel { server, .. }
+            | Channel::VoiceChannel { server, .. }
+            | Channel::Thread { server, .. } => {
                 let member = self
                     .members
                     .iter()
@@ -128,6 +133,20 @@ impl State {
         // Filter server channels by permission.
         let channels = self.cache.filter_accessible_channels(db, channels).await;
 
+        // Find active threads in any server channels we can see.
+        let threads: Vec<Channel> = db
+            .fetch_threads(
+                &channels
+                    .iter()
+                    .filter(|channel| matches!(channel, Channel::TextChannel { .. }))
+                    .map(|channel| channel.id().to_string())
+                    .collect::<Vec<String>>(),
+            )
+            .await?
+            .into_iter()
+            .filter(|thread| !matches!(thread, Channel::Thread { archived: true, .. }))
+            .collect();
+
         // Append known user IDs from DMs.
         for channel in &channels {
             match channel {
@@ -143,7 +162,7 @@ impl State {
         user.online = Some(true);
 
         // Fetch user data.
-        let users = db
+        let mut users = db
             .fetch_users(
                 &user_ids
                     .into_iter()
@@ -180,13 +199,21 @@ impl State {
             .map(|x| (x.id.server.clone(), x))
             .collect();
 
+        // Show online users with the presence they appear with across their sessions.
+        let online_ids = &online_ids;
+        join_all(users.iter_mut().map(|x| async move {
+            if online_ids.contains(&x.id) {
+                presence_populate(x).await;
+            } else {
+                x.online = Some(false);
+            }
+        }))
+        .await;
+
         // Make all users appear from our perspective.
         let mut users: Vec<User> = users
             .into_iter()
-            .map(|mut x| {
-                x.online = Some(online_ids.contains(&x.id));
-                x.with_relationship(&user)
-            })
+            .map(|x| x.with_relationship(&user))
             .collect();
 
         // Make sure we see our own user correctly.
@@ -198,7 +225,7 @@ impl State {
         self.insert_subscription(self.private_topic.clone());
 
         for user in &users {
-            self.insert_subscription(user.id.clone());
+            self.insert_user_subscription(user.id.clone());
         }
 
         for server in &servers {
@@ -209,6 +236,16 @@ impl State {
             self.insert_subscription(channel.id().to_string());
         }
 
+        for thread in threads {
+            if let Channel::Thread { id, parent, .. } = thread {
+                self.insert_subscription(id.clone());
+                self.cache.threads.insert(id, parent);
+            }
+        }
+
+        // Lazy clients load members through member lists instead.
+        let members = if self.lazy { vec![] } else { members };
+
         Ok(EventV1::Ready {
             users,
             servers,
@@ -253,6 +290,7 @@ impl State {
 
             for id in removed_channels {
                 self.remove_subscription(&id);
+                self.remove_thread_subscriptions(&id);
                 self.cache.channels.remove(&id);
 
                 bulk_events.push(EventV1::ChannelDelete { id });
@@ -292,28 +330,60 @@ impl State {
         }
     }
 
-    /// Push presence change to the user and all associated server topics
-    pub async fn broadcast_presence_change(&self, target: bool) {
-        if if let Some(status) = &self.cache.users.get(&self.cache.user_id).unwrap().status {
-            status.presence != Some(Presence::Invisible)
+    /// Apply a change to the user's presence sessions and push
+    /// any resulting change in presence to the user and all associated server topics
+    ///
+    /// Users appearing invisible are treated as offline.
+    pub async fn change_presence<T>(&self, change: impl Future<Output = T>) -> T {
+        let status = self
+            .cache
+            .users
+            .get(&self.cache.user_id)
+            .and_then(|user| user.status.clone())
+            .unwrap_or_default();
+
+        let before = presence_effective(&self.cache.user_id, status.presence.as_ref()).await;
+        let result = change.await;
+        let after = presence_effective(&self.cache.user_id, status.presence.as_ref()).await;
+
+        let visible = |presence: &Option<Presence>| {
+            presence
+                .as_ref()
+                .map_or(false, |presence| presence != &Presence::Invisible)
+        };
+
+        if before == after || (!visible(&before) && !visible(&after)) {
+            return result;
+        }
+
+        let data = if visible(&after) {
+            PartialUser {
+                online: Some(true),
+                status: Some(UserStatus {
+                    text: status.text,
+                    presence: after,
+                }),
+                ..Default::default()
+            }
         } else {
-            true
-        } {
-            let event = EventV1::UserUpdate {
-                id: self.cache.user_id.clone(),
-                data: PartialUser {
-                    online: Some(target),
-                    ..Default::default()
-                },
-                clear: vec![],
-            };
-
-            for server in self.cache.servers.keys() {
-                event.clone().p(server.clone()).await;
+            PartialUser {
+                online: Some(false),
+                ..Default::default()
             }
+        };
+
+        let event = EventV1::UserUpdate {
+            id: self.cache.user_id.clone(),
+            data,
+            clear: vec![],
+        };
 
-            event.p(self.cache.user_id.clone()).await;
+        for server in self.cache.servers.keys() {
+            event.clone().p(server.clone()).await;
         }
+
+        event.p(self.cache.user_id.clone()).await;
+        result
     }
 
     /// Handle an incoming event for protocol version 1
@@ -382,10 +452,21 @@ impl State {
             }
             EventV1::ChannelDelete { id } => {
                 self.remove_subscription(id);
+                self.remove_thread_subscriptions(id);
                 self.cache.channels.remove(id);
             }
+            EventV1::ThreadCreate(thread) => {
+                if let Channel::Thread { id, parent, .. } = thread {
+                    self.insert_subscription(id.clone());
+                    self.cache.threads.insert(id.clone(), parent.clone());
+                }
+            }
+            EventV1::ThreadDelete { id } => {
+                self.remove_subscription(id);
+                self.cache.threads.remove(id);
+            }
             EventV1::ChannelGroupJoin { user, .. } => {
-                self.insert_subscription(user.clone());
+                self.insert_user_subscription(user.clone());
             }
             EventV1::ChannelGroupLeave { id, user, .. } => {
                 if user == &self.cache.user_id {
@@ -436,6 +517,7 @@ impl State {
                     if let Some(server) = self.cache.servers.remove(id) {
                         for channel in &server.channels {
                             self.remove_subscription(channel);
+                            self.remove_thread_subscriptions(channel);
                             self.cache.channels.remove(channel);
                         }
                     }
@@ -447,6 +529,7 @@ impl State {
                 if let Some(server) = self.cache.servers.remove(id) {
                     for channel in &server.channels {
                         self.remove_subscription(channel);
+                        self.remove_thread_subscriptions(channel);
                         self.cache.channels.remove(channel);
                     }
                 }
@@ -503,11 +586,24 @@ impl State {
                 }
             }
 
+            // Keep our own account up to date, presence changes are left out
+            // as they carry the presence we appear with rather than our status.
+            EventV1::UserUpdate { id, data, clear }
+                if id == &self.cache.user_id && data.online.is_none() =>
+            {
+                if let Some(user) = self.cache.users.get_mut(id) {
+                    for field in clear.iter() {
+                        user.remove(field);
+                    }
+
+                    user.apply_options(data.clone());
+                }
+            }
             EventV1::UserRelationship { id, user, .. } => {
                 self.cache.users.insert(id.clone(), user.clone());
 
                 if self.cache.can_subscribe_to_user(id) {
-                    self.insert_subscription(id.clone());
+                    self.insert_user_subscription(id.clone());
                 } else {
                     self.remove_subscription(id);
                 }
@@ -515,6 +611,16 @@ impl State {
             _ => {}
         }
 
+        // Drop events the client did not ask for.
+        if !self.filter_intents(event) {
+            return false;
+        }
+
+        // Drop member lists we can no longer see, which may also filter out the event.
+        if !self.update_member_lists(event) {
+            return false;
+        }
+
         // Calculate server permissions if requested.
         if let Some(server_id) = queue_server {
             self.recalculate_server(db, &server_id, event).await;
@@ -527,6 +633,7 @@ impl State {
 
         if let Some(id) = queue_remove {
             self.remove_subscription(&id);
+            self.remove_thread_subscriptions(&id);
         }
 
         true
@@ -536,14 +643,10 @@ impl State {
 impl EventV1 {
     /// Publish helper wrapper
     pub async fn p(self, channel: String) {
-        #[cfg(not(debug_assertions))]
-        redis_kiss::p(channel, self).await;
-
         #[cfg(debug_assertions)]
         info!("Publishing event to {channel}: {self:?}");
 
-        #[cfg(debug_assertions)]
-        redis_kiss::publish(channel, self).await.unwrap();
+        event_bus().publish(channel, self).await;
     }
 
     /// Publish user event
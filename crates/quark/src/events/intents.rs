@@ -0,0 +1,191 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::user::PartialUser;
+
+use super::client::EventV1;
+
+/// Categories of events a connection can opt into
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Copy, Clone)]
+#[repr(u32)]
+pub enum Intent {
+    /// Messages being sent, edited, deleted or acknowledged
+    Messages = 1 << 0,
+    /// Members joining, leaving or being updated
+    Members = 1 << 1,
+    /// Reactions being added or removed
+    Reactions = 1 << 2,
+    /// Users starting or stopping typing
+    Typing = 1 << 3,
+    /// Users coming online, going offline or changing status
+    ///
+    /// Also required to receive updates from the topics of
+    /// friends and direct message recipients.
+    Presence = 1 << 4,
+}
+
+/// Every intent, used for connections which did not declare any
+pub const ALL_INTENTS: u32 = Intent::Messages as u32
+    | Intent::Members as u32
+    | Intent::Reactions as u32
+    | Intent::Typing as u32
+    | Intent::Presence as u32;
+
+impl Intent {
+    /// Find an intent by its name
+    pub fn from_name(name: &str) -> Option<Intent> {
+        match name {
+            "messages" => Some(Intent::Messages),
+            "members" => Some(Intent::Members),
+            "reactions" => Some(Intent::Reactions),
+            "typing" => Some(Intent::Typing),
+            "presence" => Some(Intent::Presence),
+            _ => None,
+        }
+    }
+
+    /// Parse a comma separated list of intent names into a bitfield
+    ///
+    /// Names are matched case-insensitively and unknown names are ignored.
+    pub fn parse_list(list: &str) -> u32 {
+        list.split(',')
+            .filter_map(|name| Intent::from_name(&name.trim().to_lowercase()))
+            .fold(0, |intents, intent| intents | intent as u32)
+    }
+}
+
+/// Check whether a user update only carries presence information
+fn is_presence_only(data: &PartialUser) -> bool {
+    (data.online.is_some() || data.status.is_some())
+        && data.username.is_none()
+        && data.avatar.is_none()
+        && data.badges.is_none()
+        && data.profile.is_none()
+        && data.flags.is_none()
+}
+
+impl EventV1 {
+    /// Determine which intent is required to receive this event
+    ///
+    /// Events concerning the given user are always delivered.
+    pub fn intent(&self, user_id: &str) -> Option<Intent> {
+        match self {
+            EventV1::Message(_)
+            | EventV1::MessageUpdate { .. }
+            | EventV1::MessageAppend { .. }
+            | EventV1::MessageDelete { .. }
+            | EventV1::BulkMessageDelete { .. }
+            | EventV1::ChannelAck { .. } => Some(Intent::Messages),
+            EventV1::MessageReact { .. }
+            | EventV1::MessageUnreact { .. }
+            | EventV1::MessageRemoveReaction { .. } => Some(Intent::Reactions),
+            EventV1::ChannelStartTyping { .. } | EventV1::ChannelStopTyping { .. } => {
+                Some(Intent::Typing)
+            }
+            EventV1::ServerMemberJoin { user, .. } | EventV1::ServerMemberLeave { user, .. } => {
+                (user != user_id).then(|| Intent::Members)
+            }
+            EventV1::ServerMemberUpdate { id, .. } => (id.user != user_id).then(|| Intent::Members),
+            EventV1::ServerMemberList { .. } => Some(Intent::Members),
+            EventV1::UserUpdate { id, data, .. } => {
+                (id != user_id && is_presence_only(data)).then(|| Intent::Presence)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::models::server_member::MemberCompositeKey;
+
+    use super::*;
+
+    #[test]
+    fn parse_list() {
+        assert_eq!(Intent::parse_list(""), 0);
+        assert_eq!(Intent::parse_list("unknown,nothing"), 0);
+        assert_eq!(
+            Intent::parse_list("messages,unknown,typing"),
+            Intent::Messages as u32 | Intent::Typing as u32
+        );
+        assert_eq!(
+            Intent::parse_list(" Members , PRESENCE"),
+            Intent::Members as u32 | Intent::Presence as u32
+        );
+        assert_eq!(
+            Intent::parse_list("messages,members,reactions,typing,presence"),
+            ALL_INTENTS
+        );
+    }
+
+    #[test]
+    fn event_intent() {
+        let typing = EventV1::ChannelStartTyping {
+            id: "channel".into(),
+            user: "other".into(),
+        };
+
+        assert_eq!(typing.intent("me"), Some(Intent::Typing));
+
+        let ack = EventV1::ChannelAck {
+            id: "channel".into(),
+            user: "me".into(),
+            message_id: "message".into(),
+        };
+
+        assert_eq!(ack.intent("me"), Some(Intent::Messages));
+
+        let join = |user: &str| EventV1::ServerMemberJoin {
+            id: "server".into(),
+            user: user.into(),
+        };
+
+        assert_eq!(join("other").intent("me"), Some(Intent::Members));
+        assert_eq!(join("me").intent("me"), None);
+
+        let member_update = EventV1::ServerMemberUpdate {
+            id: MemberCompositeKey {
+                server: "server".into(),
+                user: "me".into(),
+            },
+            data: Default::default(),
+            clear: vec![],
+        };
+
+        assert_eq!(member_update.intent("me"), None);
+        assert_eq!(
+            EventV1::ServerDelete {
+                id: "server".into()
+            }
+            .intent("me"),
+            None
+        );
+    }
+
+    #[test]
+    fn user_update_intent() {
+        let update = |id: &str, data: PartialUser| EventV1::UserUpdate {
+            id: id.into(),
+            data,
+            clear: vec![],
+        };
+
+        let presence = PartialUser {
+            online: Some(true),
+            ..Default::default()
+        };
+
+        let profile = PartialUser {
+            username: Some("name".into()),
+            online: Some(true),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            update("other", presence.clone()).intent("me"),
+            Some(Intent::Presence)
+        );
+        assert_eq!(update("me", presence).intent("me"), None);
+        assert_eq!(update("other", profile).intent("me"), None);
+    }
+}
@@ -0,0 +1,900 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, Mutex, RwLock, Weak},
+    time::Duration,
+};
+
+use async_std::task;
+use futures::{
+    channel::mpsc::{unbounded, UnboundedReceiver, UnboundedSender},
+    pin_mut, select, FutureExt,
+};
+use once_cell::sync::Lazy;
+
+use crate::{
+    models::{
+        server_member::{FieldsMember, PartialMember},
+        user::{FieldsUser, PartialUser, Presence},
+        Member, Server, User,
+    },
+    presence::{presence_filter_online, presence_is_online},
+    Database, Error, Result,
+};
+
+use super::{
+    bus::{event_bus, Subscriber},
+    client::{EventV1, MemberListGroup, MemberListItem, MemberListRange},
+    intents::Intent,
+    state::State,
+};
+
+/// Group for online members without a hoisted role
+pub const ONLINE_GROUP: &str = "online";
+
+/// Group for offline members
+pub const OFFLINE_GROUP: &str = "offline";
+
+/// Maximum number of member lists a client may subscribe to at once
+pub const MAX_MEMBER_LISTS: usize = 5;
+
+/// Maximum number of ranges a client may subscribe to per member list
+pub const MAX_MEMBER_LIST_RANGES: usize = 5;
+
+/// Maximum number of items in a single range
+pub const MAX_MEMBER_LIST_RANGE_SIZE: usize = 100;
+
+/// How often a shared member list checks whether any connection still uses it
+const RELEASE_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Member lists shared by every connection on this node, by server id
+static SHARED_LISTS: Lazy<Mutex<HashMap<String, Weak<SharedMemberList>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Information required to place a member in the list
+#[derive(Debug)]
+struct Entry {
+    username: String,
+    nickname: Option<String>,
+    roles: Vec<String>,
+    online: bool,
+    invisible: bool,
+}
+
+/// Position in the member list
+#[derive(Debug, PartialEq, Eq)]
+enum Key {
+    Group(String),
+    Member(String),
+}
+
+/// Borrowed position in the member list, used while walking it
+enum Position<'a> {
+    Group(&'a str),
+    Member(&'a str),
+}
+
+/// Members of a server ordered into groups
+///
+/// Each group is kept sorted as members change, so ranges can be read
+/// straight off the list without sorting it again.
+#[derive(Debug, Default)]
+struct Layout {
+    entries: HashMap<String, Entry>,
+    /// Hoisted role ids, highest ranked first
+    hoisted: Vec<String>,
+    /// Members of each group by lowercase display name and id
+    groups: HashMap<String, BTreeSet<(String, String)>>,
+}
+
+/// Server member list shared between every connection on this node
+///
+/// Only the information needed to order members is kept around,
+/// full objects are fetched for the items in subscribed ranges.
+#[derive(Debug)]
+pub struct SharedMemberList {
+    server: String,
+    layout: RwLock<Layout>,
+    /// Connections to notify with the server id whenever the list changes
+    watchers: Mutex<Vec<UnboundedSender<String>>>,
+}
+
+/// Server member list as seen by a single client
+#[derive(Debug)]
+pub struct MemberList {
+    shared: Arc<SharedMemberList>,
+    ranges: Vec<[usize; 2]>,
+    /// Contents of each range as last sent to the client
+    windows: Vec<Vec<Key>>,
+    groups: Vec<MemberListGroup>,
+}
+
+impl Entry {
+    fn new(member: &Member, user: &User, online: bool) -> Entry {
+        Entry {
+            username: user.username.clone(),
+            nickname: member.nickname.clone(),
+            roles: member.roles.clone(),
+            online,
+            invisible: is_invisible(user.status.as_ref().and_then(|x| x.presence.as_ref())),
+        }
+    }
+
+    fn is_online(&self) -> bool {
+        self.online && !self.invisible
+    }
+
+    fn name(&self) -> &str {
+        self.nickname.as_deref().unwrap_or(&self.username)
+    }
+
+    /// Group this member is shown under
+    ///
+    /// Online members are placed under their highest hoisted role,
+    /// falling back to the online group, and everyone else is offline.
+    fn group<'a>(&self, hoisted: &'a [String]) -> &'a str {
+        if !self.is_online() {
+            return OFFLINE_GROUP;
+        }
+
+        hoisted
+            .iter()
+            .find(|role| self.roles.contains(role))
+            .map_or(ONLINE_GROUP, |role| role.as_str())
+    }
+
+    /// Position of this member within their group
+    fn sort_key(&self, id: &str) -> (String, String) {
+        (self.name().to_lowercase(), id.to_string())
+    }
+
+    fn update_member(&mut self, data: &PartialMember, clear: &[FieldsMember]) {
+        for field in clear {
+            match field {
+                FieldsMember::Nickname => self.nickname = None,
+                FieldsMember::Roles => self.roles.clear(),
+                _ => {}
+            }
+        }
+
+        if let Some(nickname) = &data.nickname {
+            self.nickname = Some(nickname.clone());
+        }
+
+        if let Some(roles) = &data.roles {
+            self.roles = roles.clone();
+        }
+    }
+
+    fn update_user(&mut self, data: &PartialUser, clear: &[FieldsUser]) {
+        if clear.contains(&FieldsUser::StatusPresence) {
+            self.invisible = false;
+        }
+
+        if let Some(username) = &data.username {
+            self.username = username.clone();
+        }
+
+        if let Some(online) = data.online {
+            self.online = online;
+        }
+
+        if let Some(status) = &data.status {
+            self.invisible = is_invisible(status.presence.as_ref());
+        }
+    }
+}
+
+fn is_invisible(presence: Option<&Presence>) -> bool {
+    presence == Some(&Presence::Invisible)
+}
+
+/// Find the hoisted roles of a server, highest ranked first
+fn hoisted_roles(server: &Server) -> Vec<String> {
+    let mut hoisted: Vec<&String> = server
+        .roles
+        .iter()
+        .filter(|(_, role)| role.hoist)
+        .map(|(id, _)| id)
+        .collect();
+
+    hoisted.sort_by(|a, b| {
+        server.roles[*a]
+            .rank
+            .cmp(&server.roles[*b].rank)
+            .then_with(|| a.cmp(b))
+    });
+
+    hoisted.into_iter().cloned().collect()
+}
+
+impl Layout {
+    fn new(server: &Server, entries: HashMap<String, Entry>) -> Layout {
+        let mut layout = Layout {
+            entries,
+            ..Default::default()
+        };
+
+        layout.set_roles(server);
+        layout
+    }
+
+    /// Take on the hoisted roles of a server, placing every member again
+    fn set_roles(&mut self, server: &Server) {
+        self.hoisted = hoisted_roles(server);
+        self.groups.clear();
+
+        for (id, entry) in &self.entries {
+            self.groups
+                .entry(entry.group(&self.hoisted).to_string())
+                .or_default()
+                .insert(entry.sort_key(id));
+        }
+    }
+
+    /// Place an existing entry into its group
+    fn place(&mut self, id: &str) {
+        if let Some(entry) = self.entries.get(id) {
+            self.groups
+                .entry(entry.group(&self.hoisted).to_string())
+                .or_default()
+                .insert(entry.sort_key(id));
+        }
+    }
+
+    /// Take an existing entry out of its group
+    fn unplace(&mut self, id: &str) {
+        if let Some(entry) = self.entries.get(id) {
+            if let Some(members) = self.groups.get_mut(entry.group(&self.hoisted)) {
+                members.remove(&entry.sort_key(id));
+            }
+        }
+    }
+
+    /// Add or replace a member
+    fn insert(&mut self, id: String, entry: Entry) {
+        self.unplace(&id);
+        self.entries.insert(id.clone(), entry);
+        self.place(&id);
+    }
+
+    /// Remove a member, returns whether they were in the list
+    fn remove(&mut self, id: &str) -> bool {
+        self.unplace(id);
+        self.entries.remove(id).is_some()
+    }
+
+    /// Change a member, returns whether they are in the list
+    fn update(&mut self, id: &str, f: impl FnOnce(&mut Entry)) -> bool {
+        self.unplace(id);
+        let found = self.entries.get_mut(id).map(f).is_some();
+        self.place(id);
+        found
+    }
+
+    fn is_online(&self, id: &str) -> bool {
+        self.entries.get(id).map_or(false, Entry::is_online)
+    }
+
+    /// Walk through the groups which have any members, in order
+    fn non_empty_groups(&self) -> impl Iterator<Item = (&str, &BTreeSet<(String, String)>)> {
+        self.hoisted
+            .iter()
+            .map(String::as_str)
+            .chain([ONLINE_GROUP, OFFLINE_GROUP])
+            .filter_map(|group| {
+                self.groups
+                    .get(group)
+                    .filter(|members| !members.is_empty())
+                    .map(|members| (group, members))
+            })
+    }
+
+    /// Take the keys falling within an inclusive range
+    fn window(&self, [start, end]: [usize; 2]) -> Vec<Key> {
+        self.non_empty_groups()
+            .flat_map(|(group, members)| {
+                std::iter::once(Position::Group(group))
+                    .chain(members.iter().map(|(_, id)| Position::Member(id)))
+            })
+            .skip(start)
+            .take((end + 1).saturating_sub(start))
+            .map(|position| match position {
+                Position::Group(id) => Key::Group(id.to_string()),
+                Position::Member(id) => Key::Member(id.to_string()),
+            })
+            .collect()
+    }
+
+    /// Count the members in each group, along with how many are online
+    fn counts(&self) -> (Vec<MemberListGroup>, usize) {
+        let groups: Vec<MemberListGroup> = self
+            .non_empty_groups()
+            .map(|(group, members)| MemberListGroup {
+                id: group.to_string(),
+                count: members.len(),
+            })
+            .collect();
+
+        let online = groups
+            .iter()
+            .filter(|group| group.id != OFFLINE_GROUP)
+            .map(|group| group.count)
+            .sum();
+
+        (groups, online)
+    }
+}
+
+impl SharedMemberList {
+    /// Get the member list for a server, loading it if nobody on this node has yet
+    pub async fn get(db: &Database, server: &Server) -> Result<Arc<SharedMemberList>> {
+        if let Some(list) = SHARED_LISTS
+            .lock()
+            .unwrap()
+            .get(&server.id)
+            .and_then(Weak::upgrade)
+        {
+            return Ok(list);
+        }
+
+        // Listen for changes before loading, so none are missed in between.
+        let mut subscriber = event_bus().subscriber().await?;
+        subscriber.subscribe(&server.id).await;
+
+        let members = db.fetch_all_members(&server.id).await?;
+        let ids: Vec<String> = members.iter().map(|x| x.id.user.clone()).collect();
+        let users: HashMap<String, User> = db
+            .fetch_users(&ids)
+            .await?
+            .into_iter()
+            .map(|x| (x.id.clone(), x))
+            .collect();
+
+        let online_ids = presence_filter_online(&ids).await;
+        let entries = members
+            .iter()
+            .filter_map(|member| {
+                users.get(&member.id.user).map(|user| {
+                    (
+                        user.id.clone(),
+                        Entry::new(member, user, online_ids.contains(&user.id)),
+                    )
+                })
+            })
+            .collect();
+
+        let list = Arc::new(SharedMemberList {
+            server: server.id.clone(),
+            layout: RwLock::new(Layout::new(server, entries)),
+            watchers: Default::default(),
+        });
+
+        let mut lists = SHARED_LISTS.lock().unwrap();
+
+        // Someone else may have loaded the list in the meantime.
+        if let Some(existing) = lists.get(&server.id).and_then(Weak::upgrade) {
+            return Ok(existing);
+        }
+
+        lists.retain(|_, list| list.strong_count() > 0);
+        lists.insert(server.id.clone(), Arc::downgrade(&list));
+        task::spawn(follow(db.clone(), Arc::downgrade(&list), subscriber));
+
+        Ok(list)
+    }
+
+    /// Notify a connection whenever this list changes
+    fn watch(&self, watcher: &UnboundedSender<String>) {
+        let mut watchers = self.watchers.lock().unwrap();
+        if !watchers.iter().any(|x| x.same_receiver(watcher)) {
+            watchers.push(watcher.clone());
+        }
+    }
+
+    /// Let every watching connection know this list changed
+    fn notify(&self) {
+        self.watchers
+            .lock()
+            .unwrap()
+            .retain(|watcher| watcher.unbounded_send(self.server.clone()).is_ok());
+    }
+
+    /// Make a change to the layout, returns whether anything changed
+    fn modify(&self, f: impl FnOnce(&mut Layout) -> bool) -> bool {
+        f(&mut self.layout.write().unwrap())
+    }
+
+    /// Apply an event published to the server, returns whether the list changed
+    async fn apply(&self, db: &Database, event: EventV1) -> bool {
+        match event {
+            EventV1::ServerMemberJoin { id, user } => {
+                match (
+                    db.fetch_member(&id, &user).await,
+                    db.fetch_user(&user).await,
+                ) {
+                    (Ok(member), Ok(joined)) => {
+                        let entry = Entry::new(&member, &joined, presence_is_online(&user).await);
+                        self.modify(|layout| {
+                            layout.insert(user, entry);
+                            true
+                        })
+                    }
+                    _ => false,
+                }
+            }
+            EventV1::ServerMemberLeave { user, .. } => self.modify(|layout| layout.remove(&user)),
+            EventV1::ServerMemberUpdate { id, data, clear } => self.modify(|layout| {
+                layout.update(&id.user, |entry| entry.update_member(&data, &clear))
+            }),
+            EventV1::UserUpdate { id, data, clear } => {
+                self.modify(|layout| layout.update(&id, |entry| entry.update_user(&data, &clear)))
+            }
+            EventV1::ServerRoleUpdate { .. } | EventV1::ServerRoleDelete { .. } => {
+                match db.fetch_server(&self.server).await {
+                    Ok(server) => self.modify(|layout| {
+                        layout.set_roles(&server);
+                        true
+                    }),
+                    Err(err) => {
+                        warn!(
+                            "Failed to reload roles for member list of {}: {err:?}",
+                            self.server
+                        );
+                        false
+                    }
+                }
+            }
+            EventV1::ServerDelete { .. } => self.modify(|layout| {
+                *layout = Layout::default();
+                true
+            }),
+            _ => false,
+        }
+    }
+
+    /// Fetch full items for the given keys
+    async fn items(
+        &self,
+        db: &Database,
+        keys: &[Key],
+        perspective: &User,
+    ) -> Result<Vec<MemberListItem>> {
+        let ids: Vec<String> = keys
+            .iter()
+            .filter_map(|key| match key {
+                Key::Member(id) => Some(id.clone()),
+                Key::Group(_) => None,
+            })
+            .collect();
+
+        let mut members: HashMap<String, Member> = db
+            .fetch_members(&self.server, &ids)
+            .await?
+            .into_iter()
+            .map(|x| (x.id.user.clone(), x))
+            .collect();
+
+        let mut users: HashMap<String, User> = db
+            .fetch_users(&ids)
+            .await?
+            .into_iter()
+            .map(|x| (x.id.clone(), x))
+            .collect();
+
+        let layout = self.layout.read().unwrap();
+        Ok(keys
+            .iter()
+            .filter_map(|key| match key {
+                Key::Group(id) => Some(MemberListItem::Group { id: id.clone() }),
+                Key::Member(id) => {
+                    let member = members.remove(id)?;
+                    let mut user = users.remove(id)?;
+                    user.online = Some(layout.is_online(id));
+
+                    Some(MemberListItem::Member {
+                        member,
+                        user: user.with_relationship(perspective),
+                    })
+                }
+            })
+            .collect())
+    }
+}
+
+/// Keep a shared member list up to date until no connection uses it anymore
+/// Stop handing out a list which can no longer follow changes
+///
+/// Connections already holding it keep it, anyone asking next loads it anew.
+fn forget(list: &Weak<SharedMemberList>) {
+    if let Some(shared) = list.upgrade() {
+        warn!(
+            "Lost the event subscriber for member list of {}",
+            shared.server
+        );
+
+        let mut lists = SHARED_LISTS.lock().unwrap();
+        if lists
+            .get(&shared.server)
+            .map_or(false, |existing| existing.ptr_eq(list))
+        {
+            lists.remove(&shared.server);
+        }
+    }
+}
+
+async fn follow(db: Database, list: Weak<SharedMemberList>, mut subscriber: Box<dyn Subscriber>) {
+    loop {
+        let event = {
+            let next = subscriber.next().fuse();
+            let tick = task::sleep(RELEASE_CHECK_INTERVAL).fuse();
+            pin_mut!(next, tick);
+
+            select!(
+                item = next => match item {
+                    Some((_, event)) => Some(event),
+                    None => {
+                        forget(&list);
+                        break;
+                    }
+                },
+                () = tick => None
+            )
+        };
+
+        let list = match list.upgrade() {
+            Some(list) => list,
+            None => break,
+        };
+
+        if let Some(event) = event {
+            if list.apply(&db, event).await {
+                list.notify();
+            }
+        }
+    }
+}
+
+impl MemberList {
+    /// Check whether a user falls within any of the ranges sent to the client
+    pub fn is_visible(&self, user: &str) -> bool {
+        self.windows
+            .iter()
+            .flatten()
+            .any(|key| matches!(key, Key::Member(id) if id == user))
+    }
+
+    /// Generate an update for any ranges that changed since they were last sent
+    ///
+    /// If `force` is set, every subscribed range is sent regardless.
+    pub async fn sync(
+        &mut self,
+        db: &Database,
+        perspective: &User,
+        force: bool,
+    ) -> Result<Option<EventV1>> {
+        let (windows, groups, online) = {
+            let layout = self.shared.layout.read().unwrap();
+            let windows: Vec<Vec<Key>> = self
+                .ranges
+                .iter()
+                .map(|range| layout.window(*range))
+                .collect();
+
+            let (groups, online) = layout.counts();
+            (windows, groups, online)
+        };
+
+        let mut ranges = vec![];
+        for (index, range) in self.ranges.iter().enumerate() {
+            if force || self.windows.get(index) != Some(&windows[index]) {
+                ranges.push(MemberListRange {
+                    range: *range,
+                    items: self.shared.items(db, &windows[index], perspective).await?,
+                });
+            }
+        }
+
+        let unchanged = !force && ranges.is_empty() && groups == self.groups;
+
+        self.windows = windows;
+        self.groups = groups.clone();
+
+        if unchanged {
+            Ok(None)
+        } else {
+            Ok(Some(EventV1::ServerMemberList {
+                id: self.shared.server.clone(),
+                online,
+                groups,
+                ranges,
+            }))
+        }
+    }
+}
+
+impl State {
+    /// Receive the ids of subscribed member lists whenever they change
+    ///
+    /// Each id should be passed on to [State::sync_member_list].
+    pub fn watch_member_lists(&mut self) -> UnboundedReceiver<String> {
+        let (watcher, changes) = unbounded();
+        self.member_list_watcher = Some(watcher);
+        changes
+    }
+
+    /// Subscribe to ranges of a server's member list
+    ///
+    /// Passing no ranges unsubscribes from the list.
+    pub async fn subscribe_member_list(
+        &mut self,
+        db: &Database,
+        server: &str,
+        ranges: Vec<[usize; 2]>,
+    ) -> Result<Option<EventV1>> {
+        if ranges.is_empty() {
+            self.member_lists.remove(server);
+            return Ok(None);
+        }
+
+        if !self.has_intent(Intent::Members) {
+            return Err(Error::InvalidOperation);
+        }
+
+        if ranges.len() > MAX_MEMBER_LIST_RANGES
+            || ranges
+                .iter()
+                .any(|[start, end]| start > end || end - start >= MAX_MEMBER_LIST_RANGE_SIZE)
+        {
+            return Err(Error::InvalidOperation);
+        }
+
+        let server = self.cache.servers.get(server).ok_or(Error::NotFound)?;
+
+        if !self.member_lists.contains_key(&server.id) {
+            if self.member_lists.len() >= MAX_MEMBER_LISTS {
+                return Err(Error::InvalidOperation);
+            }
+
+            let shared = SharedMemberList::get(db, server).await?;
+            if let Some(watcher) = &self.member_list_watcher {
+                shared.watch(watcher);
+            }
+
+            self.member_lists.insert(
+                server.id.clone(),
+                MemberList {
+                    shared,
+                    ranges: vec![],
+                    windows: vec![],
+                    groups: vec![],
+                },
+            );
+        }
+
+        let perspective = self.cache.users.get(&self.cache.user_id).unwrap();
+        let list = self.member_lists.get_mut(&server.id).unwrap();
+        list.ranges = ranges;
+        list.sync(db, perspective, true).await
+    }
+
+    /// Generate an update for a subscribed member list which changed
+    pub async fn sync_member_list(
+        &mut self,
+        db: &Database,
+        server: &str,
+    ) -> Result<Option<EventV1>> {
+        let perspective = self.cache.users.get(&self.cache.user_id).unwrap();
+        match self.member_lists.get_mut(server) {
+            Some(list) => list.sync(db, perspective, false).await,
+            None => Ok(None),
+        }
+    }
+
+    /// Check whether a user is visible in any subscribed member list
+    fn is_visible_in_member_lists(&self, user: &str) -> bool {
+        self.member_lists.values().any(|list| list.is_visible(user))
+    }
+
+    /// Drop member lists the client can no longer see and filter events about members
+    ///
+    /// In lazy mode, events about other users are only kept if the client
+    /// knows about them or can see them in a member list.
+    /// Returns whether the event should be sent to the client.
+    pub fn update_member_lists(&mut self, event: &EventV1) -> bool {
+        let target = match event {
+            EventV1::ServerMemberJoin { user, .. } | EventV1::ServerMemberLeave { user, .. } => {
+                user
+            }
+            EventV1::ServerMemberUpdate { id, .. } => &id.user,
+            EventV1::UserUpdate { id, .. } => id,
+            EventV1::ServerDelete { id } => {
+                self.member_lists.remove(id);
+                return true;
+            }
+            _ => return true,
+        };
+
+        let visible = !self.lazy
+            || target == &self.cache.user_id
+            || self.cache.users.contains_key(target)
+            || self.is_visible_in_member_lists(target);
+
+        if let EventV1::ServerMemberLeave { id, user } = event {
+            if user == &self.cache.user_id {
+                self.member_lists.remove(id);
+            }
+        }
+
+        visible
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::server::Role;
+
+    fn role(rank: i64, hoist: bool) -> Role {
+        Role {
+            name: "role".to_string(),
+            permissions: Default::default(),
+            colour: None,
+            hoist,
+            rank,
+        }
+    }
+
+    fn entry(name: &str, roles: &[&str], online: bool) -> Entry {
+        Entry {
+            username: name.to_string(),
+            nickname: None,
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            online,
+            invisible: false,
+        }
+    }
+
+    fn member(id: &str) -> Key {
+        Key::Member(id.to_string())
+    }
+
+    fn group(id: &str) -> Key {
+        Key::Group(id.to_string())
+    }
+
+    fn counts(groups: &[(&str, usize)]) -> Vec<MemberListGroup> {
+        groups
+            .iter()
+            .map(|(id, count)| MemberListGroup {
+                id: id.to_string(),
+                count: *count,
+            })
+            .collect()
+    }
+
+    /// Server with a hoisted moderator role ranked above a hoisted helper role
+    fn layout() -> Layout {
+        let server = Server {
+            roles: [
+                ("mod".to_string(), role(0, true)),
+                ("helper".to_string(), role(1, true)),
+                ("plain".to_string(), role(2, false)),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+
+        Layout::new(
+            &server,
+            [
+                ("a", entry("Alice", &["helper", "mod"], true)),
+                ("b", entry("bob", &["helper"], true)),
+                ("c", entry("Carol", &["plain"], true)),
+                ("d", entry("dave", &["mod"], false)),
+                ("e", entry("Eve", &[], false)),
+            ]
+            .into_iter()
+            .map(|(id, entry)| (id.to_string(), entry))
+            .collect(),
+        )
+    }
+
+    #[test]
+    fn grouping() {
+        let mut layout = layout();
+
+        // Online members go under their highest hoisted role, others are offline.
+        assert_eq!(
+            layout.window([0, 9]),
+            vec![
+                group("mod"),
+                member("a"),
+                group("helper"),
+                member("b"),
+                group(ONLINE_GROUP),
+                member("c"),
+                group(OFFLINE_GROUP),
+                member("d"),
+                member("e"),
+            ]
+        );
+
+        // Members move between groups and are kept sorted by display name.
+        layout.update("a", |entry| entry.online = false);
+        layout.update("c", |entry| entry.nickname = Some("Aaron".to_string()));
+        layout.update("c", |entry| entry.roles.push("helper".to_string()));
+        assert_eq!(
+            layout.window([0, 9]),
+            vec![
+                group("helper"),
+                member("c"),
+                member("b"),
+                group(OFFLINE_GROUP),
+                member("a"),
+                member("d"),
+                member("e"),
+            ]
+        );
+
+        // Invisible members appear offline.
+        layout.update("b", |entry| entry.invisible = true);
+        assert_eq!(layout.window([0, 1]), vec![group("helper"), member("c")]);
+        assert_eq!(layout.window([2, 2]), vec![group(OFFLINE_GROUP)]);
+
+        // Removing a role from the server regroups its members.
+        layout.set_roles(&Server::default());
+        assert_eq!(
+            layout.window([0, 1]),
+            vec![group(ONLINE_GROUP), member("c")]
+        );
+    }
+
+    #[test]
+    fn windowing() {
+        let mut layout = layout();
+
+        assert_eq!(
+            layout.window([1, 3]),
+            vec![member("a"), group("helper"), member("b")]
+        );
+        assert_eq!(layout.window([7, 100]), vec![member("d"), member("e")]);
+        assert!(layout.window([9, 20]).is_empty());
+
+        layout.insert("f".to_string(), entry("Frank", &[], false));
+        assert_eq!(layout.window([8, 9]), vec![member("e"), member("f")]);
+
+        assert!(layout.remove("a"));
+        assert!(!layout.remove("a"));
+        assert_eq!(layout.window([0, 1]), vec![group("helper"), member("b")]);
+    }
+
+    #[test]
+    fn online_counts() {
+        let mut layout = layout();
+        assert_eq!(
+            layout.counts(),
+            (
+                counts(&[
+                    ("mod", 1),
+                    ("helper", 1),
+                    (ONLINE_GROUP, 1),
+                    (OFFLINE_GROUP, 2)
+                ]),
+                3
+            )
+        );
+
+        layout.update("d", |entry| entry.online = true);
+        layout.update("b", |entry| entry.online = false);
+        layout.remove("c");
+        assert_eq!(
+            layout.counts(),
+            (counts(&[("mod", 2), (OFFLINE_GROUP, 2)]), 2)
+        );
+
+        // Updates for users outside of the list are ignored.
+        assert!(!layout.update("z", |entry| entry.online = true));
+        assert_eq!(layout.counts().1, 2);
+    }
+}
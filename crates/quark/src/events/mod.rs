@@ -1,4 +1,7 @@
+pub mod bus;
 pub mod client;
 pub mod r#impl;
+pub mod intents;
+pub mod member_list;
 pub mod server;
 pub mod state;
@@ -1,12 +1,25 @@
 use serde::Deserialize;
 
+use crate::models::user::Presence;
+
 use super::client::Ping;
 
 #[derive(Deserialize, Debug)]
 #[serde(tag = "type")]
 pub enum ClientMessage {
     Authenticate { token: String },
+    Resume {
+        token: String,
+        session: String,
+        seq: u64,
+    },
     BeginTyping { channel: String },
     EndTyping { channel: String },
     Ping { data: Ping, responded: Option<()> },
+    SetPresence { presence: Option<Presence> },
+    SetIdle { idle: bool },
+    SubscribeMemberList {
+        server: String,
+        ranges: Vec<[usize; 2]>,
+    },
 }
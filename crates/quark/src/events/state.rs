@@ -1,7 +1,15 @@
 use std::collections::{HashMap, HashSet};
 
+use futures::channel::mpsc::UnboundedSender;
+
 use crate::models::{Channel, Member, Server, User};
 
+use super::{
+    client::EventV1,
+    intents::{Intent, ALL_INTENTS},
+    member_list::MemberList,
+};
+
 /// Enumeration representing some change in subscriptions
 pub enum SubscriptionStateChange {
     /// No change
@@ -34,6 +42,9 @@ pub struct Cache {
     pub channels: HashMap<String, Channel>,
     pub members: HashMap<String, Member>,
     pub servers: HashMap<String, Server>,
+
+    /// Subscribed thread ids mapped to their parent channel id
+    pub threads: HashMap<String, String>,
 }
 
 /// Client state
@@ -43,6 +54,18 @@ pub struct State {
     pub private_topic: String,
     subscribed: HashSet<String>,
     state: SubscriptionStateChange,
+
+    /// Whether events about other members are only delivered
+    /// for users the client knows about or can see in a member list
+    ///
+    /// Server members are also left out of Ready.
+    pub lazy: bool,
+    /// Member lists the client is subscribed to by server id
+    pub member_lists: HashMap<String, MemberList>,
+    /// Notified with the server id whenever a subscribed member list changes
+    pub member_list_watcher: Option<UnboundedSender<String>>,
+    /// Bitfield of event categories the client wants to receive
+    pub intents: u32,
 }
 
 impl State {
@@ -65,6 +88,10 @@ impl State {
             subscribed,
             private_topic,
             state: SubscriptionStateChange::Reset,
+            lazy: false,
+            member_lists: HashMap::new(),
+            member_list_watcher: None,
+            intents: ALL_INTENTS,
         }
     }
 
@@ -122,6 +149,30 @@ impl State {
         self.subscribed.insert(subscription);
     }
 
+    /// Add a subscription to another user's topic if the client wants their updates
+    pub fn insert_user_subscription(&mut self, user: String) {
+        if user == self.cache.user_id || self.has_intent(Intent::Presence) {
+            self.insert_subscription(user);
+        }
+    }
+
+    /// Check whether the client asked for a category of events
+    pub fn has_intent(&self, intent: Intent) -> bool {
+        self.intents & intent as u32 == intent as u32
+    }
+
+    /// Drop any events the client did not ask for, returns whether anything is left
+    pub fn filter_intents(&self, event: &mut EventV1) -> bool {
+        if let EventV1::Bulk { v } = event {
+            v.retain_mut(|event| self.filter_intents(event));
+            !v.is_empty()
+        } else {
+            event
+                .intent(&self.cache.user_id)
+                .map_or(true, |intent| self.has_intent(intent))
+        }
+    }
+
     /// Remove existing subscription
     pub fn remove_subscription(&mut self, subscription: &str) {
         if !self.subscribed.contains(&subscription.to_string()) {
@@ -143,4 +194,20 @@ impl State {
 
         self.subscribed.remove(subscription);
     }
+
+    /// Remove subscriptions to all threads started in a given channel
+    pub fn remove_thread_subscriptions(&mut self, parent: &str) {
+        let threads: Vec<String> = self
+            .cache
+            .threads
+            .iter()
+            .filter(|(_, id)| *id == parent)
+            .map(|(thread, _)| thread.clone())
+            .collect();
+
+        for thread in threads {
+            self.cache.threads.remove(&thread);
+            self.remove_subscription(&thread);
+        }
+    }
 }
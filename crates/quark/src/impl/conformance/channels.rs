@@ -0,0 +1,310 @@
+use crate::models::channel::{FieldsChannel, PartialChannel};
+use crate::models::server::SystemMessageChannels;
+use crate::models::{Channel, Invite, Message, Server};
+use crate::{AbstractDatabase, OverrideField};
+
+use super::{assert_not_found, id, insert_text_channel, sorted};
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    server_channels(db).await;
+    threads(db).await;
+    groups(db).await;
+    direct_messages(db).await;
+}
+
+/// Updating and deleting channels belonging to a server
+async fn server_channels(db: &dyn AbstractDatabase) {
+    let server_id = id();
+    let channel = insert_text_channel(db, &server_id).await;
+    let other = insert_text_channel(db, &server_id).await;
+    let channel_id = channel.id().to_string();
+
+    db.insert_server(&Server {
+        id: server_id.clone(),
+        owner: id(),
+        name: "Conformance".to_string(),
+        channels: vec![channel_id.clone(), other.id().to_string()],
+        system_messages: Some(SystemMessageChannels {
+            user_joined: Some(channel_id.clone()),
+            user_left: Some(other.id().to_string()),
+            user_kicked: None,
+            user_banned: None,
+        }),
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    assert!(db.insert_channel(&channel).await.is_err());
+    assert_eq!(
+        sorted(
+            db.fetch_channels(&[channel_id.clone(), other.id().to_string(), id()])
+                .await
+                .unwrap()
+                .iter()
+                .map(|channel| channel.id().to_string())
+                .collect()
+        ),
+        sorted(vec![channel_id.clone(), other.id().to_string()])
+    );
+
+    let last_message_id = id();
+    db.update_channel(
+        &channel_id,
+        &PartialChannel {
+            name: Some("renamed".to_string()),
+            last_message_id: Some(last_message_id.clone()),
+            ..Default::default()
+        },
+        vec![FieldsChannel::Description],
+    )
+    .await
+    .unwrap();
+
+    let role = id();
+    db.set_channel_role_permission(&channel_id, &role, OverrideField::default())
+        .await
+        .unwrap();
+
+    match db.fetch_channel(&channel_id).await.unwrap() {
+        Channel::TextChannel {
+            name,
+            description,
+            last_message_id: last_id,
+            role_permissions,
+            ..
+        } => {
+            assert_eq!(name, "renamed");
+            assert_eq!(description, None);
+            assert_eq!(last_id, Some(last_message_id));
+            assert!(role_permissions.contains_key(&role));
+        }
+        channel => panic!("unexpected channel {:?}", channel),
+    }
+
+    // Deleting a channel also deletes its invites, unreads and messages, including deleted ones.
+    let code = id();
+    db.insert_invite(&Invite::Server {
+        code: code.clone(),
+        server: server_id.clone(),
+        creator: id(),
+        channel: channel_id.clone(),
+    })
+    .await
+    .unwrap();
+
+    let user = id();
+    db.acknowledge_message(&channel_id, &user, &id())
+        .await
+        .unwrap();
+
+    let message = Message {
+        id: id(),
+        channel: channel_id.clone(),
+        author: user.clone(),
+        content: Some("Hello".to_string()),
+        ..Default::default()
+    };
+    db.insert_message(&message).await.unwrap();
+
+    let deleted = Message {
+        id: id(),
+        ..message.clone()
+    };
+    db.insert_message(&deleted).await.unwrap();
+    db.delete_message(&deleted.id, 1_000).await.unwrap();
+
+    db.delete_channel(&channel).await.unwrap();
+
+    assert_not_found(db.fetch_channel(&channel_id).await);
+    assert_not_found(db.fetch_invite(&code).await);
+    assert_not_found(db.fetch_message(&message.id).await);
+    assert!(db
+        .fetch_deleted_messages(&channel_id, 10)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(db.fetch_unreads(&user).await.unwrap().is_empty());
+
+    // And it is removed from the server.
+    let server = db.fetch_server(&server_id).await.unwrap();
+    assert_eq!(server.channels, vec![other.id().to_string()]);
+
+    let system_messages = server.system_messages.unwrap();
+    assert_eq!(system_messages.user_joined, None);
+    assert_eq!(system_messages.user_left, Some(other.id().to_string()));
+
+    db.fetch_channel(other.id()).await.unwrap();
+}
+
+/// Listing threads and deleting them alongside their parent
+async fn threads(db: &dyn AbstractDatabase) {
+    let server_id = id();
+    let parent = insert_text_channel(db, &server_id).await;
+    let parent_id = parent.id().to_string();
+
+    let thread = |archived: bool| Channel::Thread {
+        id: id(),
+        server: server_id.clone(),
+        parent: parent_id.clone(),
+        message: id(),
+        owner: id(),
+        name: "thread".to_string(),
+        last_message_id: None,
+        archived,
+    };
+
+    let (open, archived) = (thread(false), thread(true));
+    db.insert_channel(&open).await.unwrap();
+    db.insert_channel(&archived).await.unwrap();
+
+    assert_eq!(
+        sorted(
+            db.fetch_threads(&[parent_id.clone(), id()])
+                .await
+                .unwrap()
+                .iter()
+                .map(|channel| channel.id().to_string())
+                .collect()
+        ),
+        sorted(vec![open.id().to_string(), archived.id().to_string()])
+    );
+
+    db.update_channel(
+        open.id(),
+        &PartialChannel {
+            archived: Some(true),
+            ..Default::default()
+        },
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    match db.fetch_channel(open.id()).await.unwrap() {
+        Channel::Thread {
+            archived, parent, ..
+        } => {
+            assert!(archived);
+            assert_eq!(parent, parent_id);
+        }
+        channel => panic!("unexpected channel {:?}", channel),
+    }
+
+    // Deleting the parent channel also deletes its threads.
+    db.delete_channel(&parent).await.unwrap();
+    assert_not_found(db.fetch_channel(open.id()).await);
+    assert_not_found(db.fetch_channel(archived.id()).await);
+    assert!(db.fetch_threads(&[parent_id]).await.unwrap().is_empty());
+}
+
+/// Adding and removing group recipients
+async fn groups(db: &dyn AbstractDatabase) {
+    let (owner, user) = (id(), id());
+    let group = Channel::Group {
+        id: id(),
+        name: "group".to_string(),
+        owner: owner.clone(),
+        description: None,
+        recipients: vec![owner.clone()],
+        icon: None,
+        last_message_id: None,
+        permissions: None,
+        nsfw: false,
+    };
+
+    db.insert_channel(&group).await.unwrap();
+    db.add_user_to_group(group.id(), &user).await.unwrap();
+
+    let recipients = |channel: Channel| match channel {
+        Channel::Group { recipients, .. } => recipients,
+        channel => panic!("unexpected channel {:?}", channel),
+    };
+
+    assert_eq!(
+        recipients(db.fetch_channel(group.id()).await.unwrap()),
+        vec![owner.clone(), user.clone()]
+    );
+
+    db.remove_user_from_group(group.id(), &owner).await.unwrap();
+    assert_eq!(
+        recipients(db.fetch_channel(group.id()).await.unwrap()),
+        vec![user]
+    );
+}
+
+/// Looking up saved messages and direct message channels
+async fn direct_messages(db: &dyn AbstractDatabase) {
+    let (a, b) = (id(), id());
+    assert_not_found(db.find_saved_messages_channel(&a).await);
+    assert_not_found(db.find_direct_message_channel(&a, &b).await);
+
+    let saved = Channel::SavedMessages {
+        id: id(),
+        user: a.clone(),
+    };
+
+    let dm = Channel::DirectMessage {
+        id: id(),
+        active: false,
+        recipients: vec![a.clone(), b.clone()],
+        last_message_id: None,
+    };
+
+    db.insert_channel(&saved).await.unwrap();
+    db.insert_channel(&dm).await.unwrap();
+
+    assert_eq!(
+        db.find_saved_messages_channel(&a).await.unwrap().id(),
+        saved.id()
+    );
+
+    // Looking up a conversation with yourself finds saved messages.
+    assert_eq!(
+        db.find_direct_message_channel(&a, &a).await.unwrap().id(),
+        saved.id()
+    );
+
+    assert_eq!(
+        db.find_direct_message_channel(&b, &a).await.unwrap().id(),
+        dm.id()
+    );
+
+    assert_eq!(
+        sorted(
+            db.find_direct_messages(&a)
+                .await
+                .unwrap()
+                .iter()
+                .map(|channel| channel.id().to_string())
+                .collect()
+        ),
+        sorted(vec![saved.id().to_string(), dm.id().to_string()])
+    );
+
+    assert_eq!(db.find_direct_messages(&b).await.unwrap().len(), 1);
+
+    // The last message id only moves forward, even if updates arrive out of order.
+    let mut ids = [id(), id()];
+    ids.sort();
+    let [older, newer] = ids;
+
+    db.update_last_message_id(dm.id(), &newer, true)
+        .await
+        .unwrap();
+    db.update_last_message_id(dm.id(), &older, false)
+        .await
+        .unwrap();
+
+    match db.fetch_channel(dm.id()).await.unwrap() {
+        Channel::DirectMessage {
+            active,
+            last_message_id,
+            ..
+        } => {
+            assert!(active);
+            assert_eq!(last_message_id, Some(newer));
+        }
+        channel => panic!("unexpected channel {:?}", channel),
+    }
+}
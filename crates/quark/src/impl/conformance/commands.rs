@@ -0,0 +1,60 @@
+use crate::models::BotCommand;
+use crate::AbstractDatabase;
+
+use super::{assert_not_found, id, sorted};
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    lifecycle(db).await;
+}
+
+/// Create and insert a new command for a bot
+async fn insert_command(
+    db: &dyn AbstractDatabase,
+    bot: &str,
+    server: Option<String>,
+) -> BotCommand {
+    let command = BotCommand {
+        id: id(),
+        bot_id: bot.to_string(),
+        server_id: server,
+        name: "conformance".to_string(),
+        description: "Conformance command".to_string(),
+        options: vec![],
+    };
+
+    db.insert_command(&command).await.unwrap();
+    command
+}
+
+/// Registering, fetching and deleting commands
+async fn lifecycle(db: &dyn AbstractDatabase) {
+    let (bot, other_bot) = (id(), id());
+    let global = insert_command(db, &bot, None).await;
+    let local = insert_command(db, &bot, Some(id())).await;
+    let other = insert_command(db, &other_bot, None).await;
+
+    assert!(db.insert_command(&global).await.is_err());
+    assert_not_found(db.fetch_command(&id()).await);
+
+    let fetched = db.fetch_command(&local.id).await.unwrap();
+    assert_eq!(fetched.server_id, local.server_id);
+
+    assert_eq!(
+        sorted(
+            db.fetch_commands(&bot)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|command| command.id)
+                .collect()
+        ),
+        sorted(vec![global.id.clone(), local.id.clone()])
+    );
+
+    db.delete_command(&global.id).await.unwrap();
+    assert_not_found(db.fetch_command(&global.id).await);
+
+    db.delete_commands_for_bot(&bot).await.unwrap();
+    assert!(db.fetch_commands(&bot).await.unwrap().is_empty());
+    db.fetch_command(&other.id).await.unwrap();
+}
@@ -0,0 +1,104 @@
+use crate::models::Job;
+use crate::AbstractDatabase;
+
+use super::id;
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    claiming(db).await;
+    failures(db).await;
+}
+
+/// Create and insert a new job which becomes available at the given time
+async fn insert_job(db: &dyn AbstractDatabase, queue: &str, available_at: i64) -> Job {
+    let job = Job {
+        id: id(),
+        queue: queue.to_string(),
+        payload: "{}".to_string(),
+        attempts: 0,
+        available_at,
+        last_error: None,
+        dead: false,
+    };
+
+    db.insert_job(&job).await.unwrap();
+    job
+}
+
+/// Jobs are handed out oldest first and hidden while claimed
+async fn claiming(db: &dyn AbstractDatabase) {
+    let queue = id();
+    let later = insert_job(db, &queue, 200).await;
+    let first = insert_job(db, &queue, 100).await;
+    let pending = insert_job(db, &queue, 1_000).await;
+    insert_job(db, &id(), 100).await;
+
+    assert!(db.insert_job(&first).await.is_err());
+    assert_eq!(db.count_jobs(&queue).await.unwrap(), 3);
+
+    let claimed = db.claim_job(&queue, 500, 10_000).await.unwrap().unwrap();
+    assert_eq!(claimed.id, first.id);
+    assert_eq!(claimed.attempts, 1);
+    assert_eq!(claimed.available_at, 10_000);
+
+    let claimed = db.claim_job(&queue, 500, 10_000).await.unwrap().unwrap();
+    assert_eq!(claimed.id, later.id);
+
+    // Remaining job isn't available yet and claimed jobs are hidden.
+    assert!(db.claim_job(&queue, 500, 10_000).await.unwrap().is_none());
+
+    // Once the visibility timeout passes, unfinished jobs are handed out again.
+    db.complete_job(&later.id).await.unwrap();
+    let claimed = db.claim_job(&queue, 20_000, 30_000).await.unwrap().unwrap();
+    assert_eq!(claimed.id, pending.id);
+
+    let claimed = db.claim_job(&queue, 20_000, 30_000).await.unwrap().unwrap();
+    assert_eq!(claimed.id, first.id);
+    assert_eq!(claimed.attempts, 2);
+    assert!(db
+        .claim_job(&queue, 20_000, 30_000)
+        .await
+        .unwrap()
+        .is_none());
+
+    db.complete_job(&first.id).await.unwrap();
+    db.complete_job(&pending.id).await.unwrap();
+    assert_eq!(db.count_jobs(&queue).await.unwrap(), 0);
+}
+
+/// Failed jobs are retried later or moved to the dead letter queue
+async fn failures(db: &dyn AbstractDatabase) {
+    let queue = id();
+    let retried = insert_job(db, &queue, 0).await;
+    let buried = insert_job(db, &queue, 0).await;
+
+    db.claim_job(&queue, 0, 1_000).await.unwrap().unwrap();
+    db.claim_job(&queue, 0, 1_000).await.unwrap().unwrap();
+
+    db.retry_job(&retried.id, 5_000, "timed out").await.unwrap();
+    db.bury_job(&buried.id, "invalid payload").await.unwrap();
+
+    assert!(db.claim_job(&queue, 2_000, 10_000).await.unwrap().is_none());
+
+    let claimed = db.claim_job(&queue, 5_000, 10_000).await.unwrap().unwrap();
+    assert_eq!(claimed.id, retried.id);
+    assert_eq!(claimed.attempts, 2);
+    assert_eq!(claimed.last_error.as_deref(), Some("timed out"));
+
+    // Dead jobs are never handed out again.
+    assert!(db
+        .claim_job(&queue, i64::MAX, i64::MAX)
+        .await
+        .unwrap()
+        .is_none());
+
+    // Dead jobs no longer count towards the queue.
+    assert_eq!(db.count_jobs(&queue).await.unwrap(), 1);
+
+    let dead = db.fetch_dead_jobs(&queue).await.unwrap();
+    assert_eq!(dead.len(), 1);
+    assert_eq!(dead[0].id, buried.id);
+    assert_eq!(dead[0].last_error.as_deref(), Some("invalid payload"));
+
+    db.complete_job(&buried.id).await.unwrap();
+    assert!(db.fetch_dead_jobs(&queue).await.unwrap().is_empty());
+}
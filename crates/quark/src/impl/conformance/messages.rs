@@ -0,0 +1,668 @@
+use futures::future::join_all;
+
+use crate::models::message::{Message, MessageFilter, MessageSort, PartialMessage};
+use crate::models::{DeletedMessage, File, MessageRevision};
+use crate::types::january::Embed;
+use crate::{AbstractDatabase, Error};
+
+use super::{assert_not_found, id, sorted};
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    pagination(db).await;
+    search(db).await;
+    search_filters(db).await;
+    reactions(db).await;
+    pins(db).await;
+    edits_and_deletion(db).await;
+    revisions(db).await;
+    recovery(db).await;
+}
+
+/// Insert ten messages into a new channel, ids sort in insertion order
+async fn seed(db: &dyn AbstractDatabase) -> (String, Vec<String>) {
+    let channel = id();
+    let prefix = id();
+
+    let mut ids = vec![];
+    for i in 0..10 {
+        let message = Message {
+            id: format!("{prefix}{i:02}"),
+            channel: channel.to_string(),
+            author: prefix.to_string(),
+            content: Some(if i % 3 == 0 {
+                format!("apple banana {i}")
+            } else {
+                format!("cherry {i}")
+            }),
+            ..Default::default()
+        };
+
+        db.insert_message(&message).await.unwrap();
+        ids.push(message.id);
+    }
+
+    (channel, ids)
+}
+
+fn ids_of(messages: Vec<Message>) -> Vec<String> {
+    messages.into_iter().map(|message| message.id).collect()
+}
+
+/// Cursor pagination with `before`, `after`, `sort` and `nearby`
+async fn pagination(db: &dyn AbstractDatabase) {
+    let (channel, ids) = seed(db).await;
+    let fetch =
+        |limit, before: Option<&String>, after: Option<&String>, sort, nearby: Option<&String>| {
+            db.fetch_messages(
+                &channel,
+                Some(limit),
+                before.cloned(),
+                after.cloned(),
+                sort,
+                nearby.cloned(),
+            )
+        };
+
+    // Latest is the default sort.
+    assert_eq!(
+        ids_of(fetch(3, None, None, None, None).await.unwrap()),
+        vec![ids[9].clone(), ids[8].clone(), ids[7].clone()]
+    );
+
+    assert_eq!(
+        ids_of(
+            fetch(3, None, None, Some(MessageSort::Oldest), None)
+                .await
+                .unwrap()
+        ),
+        ids[0..3].to_vec()
+    );
+
+    assert_eq!(
+        ids_of(
+            fetch(2, Some(&ids[5]), None, Some(MessageSort::Latest), None)
+                .await
+                .unwrap()
+        ),
+        vec![ids[4].clone(), ids[3].clone()]
+    );
+
+    assert_eq!(
+        ids_of(
+            fetch(2, None, Some(&ids[5]), Some(MessageSort::Oldest), None)
+                .await
+                .unwrap()
+        ),
+        ids[6..8].to_vec()
+    );
+
+    // `after` takes precedence over `before`.
+    assert_eq!(
+        ids_of(
+            fetch(
+                2,
+                Some(&ids[1]),
+                Some(&ids[2]),
+                Some(MessageSort::Oldest),
+                None
+            )
+            .await
+            .unwrap()
+        ),
+        ids[3..5].to_vec()
+    );
+
+    // Nearby returns the message and those after it, followed by those before it.
+    assert_eq!(
+        ids_of(fetch(4, None, None, None, Some(&ids[5])).await.unwrap()),
+        vec![
+            ids[5].clone(),
+            ids[6].clone(),
+            ids[7].clone(),
+            ids[4].clone(),
+            ids[3].clone()
+        ]
+    );
+
+    // Other channels are never included.
+    assert!(db
+        .fetch_messages(&id(), None, None, None, None, None)
+        .await
+        .unwrap()
+        .is_empty());
+}
+
+/// Full text search within a channel
+async fn search(db: &dyn AbstractDatabase) {
+    let (channel, ids) = seed(db).await;
+    let matching = vec![
+        ids[0].clone(),
+        ids[3].clone(),
+        ids[6].clone(),
+        ids[9].clone(),
+    ];
+
+    let search = |sort, before: Option<&String>, after: Option<&String>| {
+        let filter = MessageFilter {
+            query: Some("apple".to_string()),
+            before: before.cloned(),
+            after: after.cloned(),
+            ..Default::default()
+        };
+
+        let channels = vec![channel.clone()];
+
+        async move { db.search_messages(&channels, &filter, Some(10), sort).await }
+    };
+
+    assert_eq!(
+        ids_of(search(MessageSort::Oldest, None, None).await.unwrap()),
+        matching
+    );
+
+    let mut latest = matching.clone();
+    latest.reverse();
+    assert_eq!(
+        ids_of(search(MessageSort::Latest, None, None).await.unwrap()),
+        latest
+    );
+
+    assert_eq!(
+        sorted(ids_of(
+            search(MessageSort::Relevance, None, None).await.unwrap()
+        )),
+        matching
+    );
+
+    assert_eq!(
+        ids_of(
+            search(MessageSort::Oldest, Some(&ids[9]), Some(&ids[0]))
+                .await
+                .unwrap()
+        ),
+        matching[1..3].to_vec()
+    );
+}
+
+/// Structured filters, with and without a text query, across several channels
+async fn search_filters(db: &dyn AbstractDatabase) {
+    let (channel_a, channel_b) = (id(), id());
+    let (author, other, mentioned) = (id(), id(), id());
+    let prefix = id();
+
+    let messages = vec![
+        Message {
+            content: Some("apple plain".to_string()),
+            ..Default::default()
+        },
+        Message {
+            content: Some("apple mention".to_string()),
+            mentions: Some(vec![mentioned.clone()]),
+            ..Default::default()
+        },
+        Message {
+            content: Some("apple pinned".to_string()),
+            pinned: true,
+            ..Default::default()
+        },
+        Message {
+            attachments: Some(vec![File {
+                id: id(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        },
+        Message {
+            content: Some("apple link https://example.com".to_string()),
+            embeds: Some(vec![Embed::None]),
+            ..Default::default()
+        },
+        Message {
+            content: Some("cherry from someone else".to_string()),
+            ..Default::default()
+        },
+    ];
+
+    let mut ids = vec![];
+    for (i, message) in messages.into_iter().enumerate() {
+        let message = Message {
+            id: format!("{prefix}{i:02}"),
+            channel: if i % 2 == 0 { &channel_a } else { &channel_b }.to_string(),
+            author: if i == 5 { &other } else { &author }.to_string(),
+            ..message
+        };
+
+        db.insert_message(&message).await.unwrap();
+        ids.push(message.id);
+    }
+
+    let channels = vec![channel_a.clone(), channel_b.clone()];
+    let search = |filter: MessageFilter| {
+        let channels = channels.clone();
+        async move {
+            ids_of(
+                db.search_messages(&channels, &filter, None, MessageSort::Oldest)
+                    .await
+                    .unwrap(),
+            )
+        }
+    };
+
+    // Without any criteria, everything in the given channels is returned.
+    assert_eq!(search(MessageFilter::default()).await, ids);
+
+    // Only the given channels are searched.
+    assert_eq!(
+        ids_of(
+            db.search_messages(
+                &[channel_a.clone()],
+                &MessageFilter::default(),
+                None,
+                MessageSort::Oldest
+            )
+            .await
+            .unwrap()
+        ),
+        vec![ids[0].clone(), ids[2].clone(), ids[4].clone()]
+    );
+
+    // Relevance falls back to latest first without a query.
+    let mut latest = ids.clone();
+    latest.reverse();
+    assert_eq!(
+        ids_of(
+            db.search_messages(
+                &channels,
+                &MessageFilter::default(),
+                None,
+                MessageSort::Relevance
+            )
+            .await
+            .unwrap()
+        ),
+        latest
+    );
+
+    assert_eq!(
+        search(MessageFilter {
+            author: Some(other.clone()),
+            ..Default::default()
+        })
+        .await,
+        vec![ids[5].clone()]
+    );
+
+    assert_eq!(
+        search(MessageFilter {
+            mention: Some(mentioned.clone()),
+            ..Default::default()
+        })
+        .await,
+        vec![ids[1].clone()]
+    );
+
+    assert_eq!(
+        search(MessageFilter {
+            pinned: true,
+            ..Default::default()
+        })
+        .await,
+        vec![ids[2].clone()]
+    );
+
+    assert_eq!(
+        search(MessageFilter {
+            has_attachment: true,
+            ..Default::default()
+        })
+        .await,
+        vec![ids[3].clone()]
+    );
+
+    assert_eq!(
+        search(MessageFilter {
+            has_embed: true,
+            ..Default::default()
+        })
+        .await,
+        vec![ids[4].clone()]
+    );
+
+    assert_eq!(
+        search(MessageFilter {
+            has_link: true,
+            ..Default::default()
+        })
+        .await,
+        vec![ids[4].clone()]
+    );
+
+    // Criteria are combined with each other and with the text query.
+    assert_eq!(
+        search(MessageFilter {
+            query: Some("apple".to_string()),
+            author: Some(author.clone()),
+            after: Some(ids[0].clone()),
+            before: Some(ids[4].clone()),
+            ..Default::default()
+        })
+        .await,
+        vec![ids[1].clone(), ids[2].clone()]
+    );
+
+    assert!(search(MessageFilter {
+        query: Some("apple".to_string()),
+        author: Some(other.clone()),
+        ..Default::default()
+    })
+    .await
+    .is_empty());
+}
+
+/// Adding, removing and clearing reactions
+async fn reactions(db: &dyn AbstractDatabase) {
+    let (_, ids) = seed(db).await;
+    let (user_a, user_b) = (id(), id());
+
+    db.add_reaction(&ids[0], "👍", &user_a).await.unwrap();
+    db.add_reaction(&ids[0], "👍", &user_b).await.unwrap();
+    db.add_reaction(&ids[0], "👍", &user_b).await.unwrap();
+    db.add_reaction(&ids[0], "🎉", &user_a).await.unwrap();
+
+    let message = db.fetch_message(&ids[0]).await.unwrap();
+    assert_eq!(message.reactions.len(), 2);
+    assert_eq!(message.reactions["👍"].len(), 2);
+
+    db.remove_reaction(&ids[0], "👍", &user_a).await.unwrap();
+    db.clear_reaction(&ids[0], "🎉").await.unwrap();
+
+    let message = db.fetch_message(&ids[0]).await.unwrap();
+    assert!(!message.reactions.contains_key("🎉"));
+    assert_eq!(
+        message.reactions["👍"]
+            .iter()
+            .cloned()
+            .collect::<Vec<String>>(),
+        vec![user_b]
+    );
+}
+
+/// Pinning and unpinning messages
+async fn pins(db: &dyn AbstractDatabase) {
+    let (channel, ids) = seed(db).await;
+    assert!(db.fetch_pinned_messages(&channel).await.unwrap().is_empty());
+
+    for message in [&ids[2], &ids[7], &ids[4]] {
+        db.update_message(
+            message,
+            &PartialMessage {
+                pinned: Some(true),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    db.update_message(
+        &ids[4],
+        &PartialMessage {
+            pinned: Some(false),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        ids_of(db.fetch_pinned_messages(&channel).await.unwrap()),
+        vec![ids[7].clone(), ids[2].clone()]
+    );
+
+    assert!(db.fetch_pinned_messages(&id()).await.unwrap().is_empty());
+
+    // Pinning respects the limit, including when pins race each other.
+    db.pin_message(&channel, &ids[0], 3).await.unwrap();
+    db.pin_message(&channel, &ids[0], 3).await.unwrap();
+
+    let results = join_all(
+        [&ids[1], &ids[3], &ids[5]]
+            .into_iter()
+            .map(|message| db.pin_message(&channel, message, 4)),
+    )
+    .await;
+
+    // Exactly one of the racing pins fits.
+    assert_eq!(results.iter().filter(|result| result.is_ok()).count(), 1);
+    assert!(results
+        .into_iter()
+        .filter_map(Result::err)
+        .all(|error| matches!(error, Error::TooManyPins { max: 4 })));
+
+    assert_eq!(db.fetch_pinned_messages(&channel).await.unwrap().len(), 4);
+}
+
+/// Partial updates and bulk deletion
+async fn edits_and_deletion(db: &dyn AbstractDatabase) {
+    let (channel, ids) = seed(db).await;
+
+    db.update_message(
+        &ids[1],
+        &PartialMessage {
+            content: Some("edited".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let message = db.fetch_message(&ids[1]).await.unwrap();
+    assert_eq!(message.content.as_deref(), Some("edited"));
+
+    // Updates to missing messages are not errors.
+    db.update_message(
+        &id(),
+        &PartialMessage {
+            content: Some("edited".to_string()),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    // Bulk deletion only affects the given channel.
+    db.delete_messages(&id(), vec![ids[0].clone()], 1_000)
+        .await
+        .unwrap();
+    db.fetch_message(&ids[0]).await.unwrap();
+
+    db.delete_messages(&channel, vec![ids[0].clone(), ids[1].clone()], 1_000)
+        .await
+        .unwrap();
+    assert_not_found(db.fetch_message(&ids[0]).await);
+    assert_not_found(db.fetch_message(&ids[1]).await);
+
+    db.delete_message(&ids[2], 1_000).await.unwrap();
+    assert_not_found(db.fetch_message(&ids[2]).await);
+
+    assert_eq!(
+        db.fetch_messages(&channel, Some(100), None, None, None, None)
+            .await
+            .unwrap()
+            .len(),
+        7
+    );
+}
+
+/// Revisions are fetched oldest first, pruned by id and kept while their message can be restored
+async fn revisions(db: &dyn AbstractDatabase) {
+    let (channel, ids) = seed(db).await;
+    let prefix = id();
+
+    for (i, message) in [&ids[0], &ids[0], &ids[0], &ids[1]].into_iter().enumerate() {
+        db.insert_message_revision(&MessageRevision {
+            id: format!("{prefix}{i:02}"),
+            message: message.to_string(),
+            channel: channel.to_string(),
+            content: Some(format!("revision {i}")),
+            embeds: None,
+            edited: None,
+        })
+        .await
+        .unwrap();
+    }
+
+    let revision_ids = |revisions: Vec<MessageRevision>| {
+        revisions
+            .into_iter()
+            .map(|revision| revision.id)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        revision_ids(db.fetch_message_revisions(&ids[0]).await.unwrap()),
+        vec![
+            format!("{prefix}00"),
+            format!("{prefix}01"),
+            format!("{prefix}02")
+        ]
+    );
+
+    // Pruning only affects older revisions of the given message.
+    db.delete_message_revisions_before(&ids[0], &format!("{prefix}02"))
+        .await
+        .unwrap();
+    assert_eq!(
+        revision_ids(db.fetch_message_revisions(&ids[0]).await.unwrap()),
+        vec![format!("{prefix}02")]
+    );
+    assert_eq!(db.fetch_message_revisions(&ids[1]).await.unwrap().len(), 1);
+
+    // Channels are listed once, however many revisions they hold.
+    let channels = db.fetch_message_revision_channels().await.unwrap();
+    assert_eq!(channels.iter().filter(|id| *id == &channel).count(), 1);
+
+    // Pruning a channel affects older revisions of every message within it.
+    db.delete_channel_message_revisions_before(&channel, &format!("{prefix}03"))
+        .await
+        .unwrap();
+    assert!(db
+        .fetch_message_revisions(&ids[0])
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(db.fetch_message_revisions(&ids[1]).await.unwrap().len(), 1);
+
+    db.delete_channel_message_revisions_before(&channel, &format!("{prefix}04"))
+        .await
+        .unwrap();
+    assert!(!db
+        .fetch_message_revision_channels()
+        .await
+        .unwrap()
+        .contains(&channel));
+
+    // Deleted messages keep their revisions until purged.
+    db.insert_message_revision(&MessageRevision {
+        id: format!("{prefix}04"),
+        message: ids[0].to_string(),
+        channel: channel.to_string(),
+        content: None,
+        embeds: None,
+        edited: None,
+    })
+    .await
+    .unwrap();
+
+    db.delete_message(&ids[0], 1_000).await.unwrap();
+    assert_eq!(db.fetch_message_revisions(&ids[0]).await.unwrap().len(), 1);
+}
+
+/// Deleted messages can be listed and restored until they are purged
+async fn recovery(db: &dyn AbstractDatabase) {
+    let (channel, ids) = seed(db).await;
+
+    db.delete_message(&ids[0], 100).await.unwrap();
+    db.delete_messages(&channel, vec![ids[1].clone(), ids[2].clone()], 300)
+        .await
+        .unwrap();
+    assert_not_found(db.fetch_message(&ids[0]).await);
+    assert_not_found(db.fetch_message(&ids[2]).await);
+
+    // Most recently deleted first.
+    let deleted_ids = |deleted: Vec<DeletedMessage>| {
+        deleted
+            .into_iter()
+            .map(|deleted| deleted.id)
+            .collect::<Vec<String>>()
+    };
+
+    assert_eq!(
+        deleted_ids(db.fetch_deleted_messages(&channel, 10).await.unwrap()),
+        vec![ids[2].clone(), ids[1].clone(), ids[0].clone()]
+    );
+    assert_eq!(
+        db.fetch_deleted_messages(&channel, 1).await.unwrap().len(),
+        1
+    );
+    assert!(db
+        .fetch_deleted_messages(&id(), 10)
+        .await
+        .unwrap()
+        .is_empty());
+
+    // Restoring only works from the channel the message was deleted from.
+    assert_not_found(db.restore_message(&id(), &ids[1], 0).await);
+    let message = db.restore_message(&channel, &ids[1], 0).await.unwrap();
+    assert_eq!(message.id, ids[1]);
+    assert_eq!(
+        message.content,
+        db.fetch_message(&ids[1]).await.unwrap().content
+    );
+    assert_not_found(db.restore_message(&channel, &ids[1], 0).await);
+
+    // Only one of several concurrent restores succeeds, the rest find nothing.
+    db.delete_message(&ids[3], 300).await.unwrap();
+    let restores = join_all((0..4).map(|_| db.restore_message(&channel, &ids[3], 0))).await;
+    assert_eq!(restores.iter().filter(|result| result.is_ok()).count(), 1);
+    assert!(restores
+        .iter()
+        .all(|result| matches!(result, Ok(_) | Err(Error::NotFound))));
+    assert_eq!(db.fetch_message(&ids[3]).await.unwrap().id, ids[3]);
+    assert!(!deleted_ids(db.fetch_deleted_messages(&channel, 10).await.unwrap()).contains(&ids[3]));
+
+    // Messages deleted before the given cutoff can't be restored.
+    assert_not_found(db.restore_message(&channel, &ids[2], 301).await);
+
+    // Purging only affects messages deleted before the given time.
+    for message in [&ids[0], &ids[2]] {
+        db.insert_message_revision(&MessageRevision {
+            id: id(),
+            message: message.to_string(),
+            channel: channel.to_string(),
+            content: None,
+            embeds: None,
+            edited: None,
+        })
+        .await
+        .unwrap();
+    }
+
+    db.purge_deleted_messages(200).await.unwrap();
+    assert_not_found(db.restore_message(&channel, &ids[0], 0).await);
+    assert!(db
+        .fetch_message_revisions(&ids[0])
+        .await
+        .unwrap()
+        .is_empty());
+    assert_eq!(db.fetch_message_revisions(&ids[2]).await.unwrap().len(), 1);
+
+    db.restore_message(&channel, &ids[2], 300).await.unwrap();
+    assert_eq!(
+        db.fetch_messages(&channel, Some(100), None, None, None, None)
+            .await
+            .unwrap()
+            .len(),
+        9
+    );
+}
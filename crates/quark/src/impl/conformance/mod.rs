@@ -0,0 +1,104 @@
+//! Conformance suite for database implementations
+//!
+//! Runs the same scenarios against any [`AbstractDatabase`] so that every
+//! backend can be checked to behave like the MongoDB implementation.
+//! Scenarios only create objects with fresh ids, which means the suite can
+//! safely be pointed at a database which already holds data.
+//!
+//! Each scenario panics on the first mismatch, so this is intended to be
+//! driven from a test harness, see `tests/conformance.rs`. Only compiled
+//! with the `conformance` feature so it never ends up in production builds.
+use ulid::Ulid;
+
+use crate::models::server_member::MemberCompositeKey;
+use crate::models::{Channel, Member, User};
+use crate::{AbstractDatabase, Error, Result, Timestamp};
+
+mod channels;
+mod commands;
+mod jobs;
+mod messages;
+mod servers;
+mod unreads;
+mod users;
+mod webhooks;
+
+/// Run every scenario against the given database
+pub async fn run(db: &dyn AbstractDatabase) {
+    channels::run(db).await;
+    commands::run(db).await;
+    jobs::run(db).await;
+    messages::run(db).await;
+    servers::run(db).await;
+    unreads::run(db).await;
+    users::run(db).await;
+    webhooks::run(db).await;
+}
+
+/// Generate a new unique id
+fn id() -> String {
+    Ulid::new().to_string()
+}
+
+/// Sort a list of ids so results can be compared regardless of order
+fn sorted(mut ids: Vec<String>) -> Vec<String> {
+    ids.sort();
+    ids
+}
+
+/// Assert that an operation failed because the object does not exist
+fn assert_not_found<T: std::fmt::Debug>(result: Result<T>) {
+    match result {
+        Err(Error::NotFound) => {}
+        other => panic!("expected NotFound, got {:?}", other),
+    }
+}
+
+/// Create and insert a new user with a unique username
+async fn insert_user(db: &dyn AbstractDatabase) -> User {
+    let id = id();
+    let user = User {
+        username: format!("Conformance{}", &id[16..]),
+        id,
+        ..Default::default()
+    };
+
+    db.insert_user(&user).await.unwrap();
+    user
+}
+
+/// Create and insert a new text channel in a server
+async fn insert_text_channel(db: &dyn AbstractDatabase, server: &str) -> Channel {
+    let channel = Channel::TextChannel {
+        id: id(),
+        server: server.to_string(),
+        name: "general".to_string(),
+        description: Some("Conformance channel".to_string()),
+        icon: None,
+        last_message_id: None,
+        default_permissions: None,
+        role_permissions: Default::default(),
+        nsfw: false,
+    };
+
+    db.insert_channel(&channel).await.unwrap();
+    channel
+}
+
+/// Create and insert a new server member
+async fn insert_member(db: &dyn AbstractDatabase, server: &str, user: &str) -> Member {
+    let member = Member {
+        id: MemberCompositeKey {
+            server: server.to_string(),
+            user: user.to_string(),
+        },
+        joined_at: Timestamp::now_utc(),
+        nickname: None,
+        avatar: None,
+        roles: vec![],
+        timeout: None,
+    };
+
+    db.insert_member(&member).await.unwrap();
+    member
+}
@@ -0,0 +1,406 @@
+use crate::models::audit_log::{AuditLogAction, AuditLogChanges};
+use crate::models::automod_rule::{AutomodAction, AutomodTrigger, PartialAutomodRule};
+use crate::models::server::{FieldsRole, FieldsServer, PartialRole, PartialServer, Role};
+use crate::models::server_member::{FieldsMember, MemberCompositeKey, PartialMember};
+use crate::models::{AuditLogEntry, AutomodRule, Channel, Server, ServerBan};
+use crate::{AbstractDatabase, OverrideField};
+
+use super::{assert_not_found, id, insert_member, insert_text_channel, sorted};
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    members(db).await;
+    bans(db).await;
+    roles(db).await;
+    audit_log(db).await;
+    automod_rules(db).await;
+    deletion(db).await;
+}
+
+/// Create and insert a new server with a single text channel
+async fn insert_server(db: &dyn AbstractDatabase) -> (Server, Channel) {
+    let server_id = id();
+    let channel = insert_text_channel(db, &server_id).await;
+    let server = Server {
+        id: server_id,
+        owner: id(),
+        name: "Conformance".to_string(),
+        description: Some("Conformance server".to_string()),
+        channels: vec![channel.id().to_string()],
+        ..Default::default()
+    };
+
+    db.insert_server(&server).await.unwrap();
+    (server, channel)
+}
+
+/// Membership lookups and counts
+async fn members(db: &dyn AbstractDatabase) {
+    let (server, _) = insert_server(db).await;
+    let (a, b) = (id(), id());
+
+    let member = insert_member(db, &server.id, &a).await;
+    insert_member(db, &server.id, &b).await;
+    insert_member(db, &id(), &a).await;
+
+    assert!(db.insert_member(&member).await.is_err());
+    assert_not_found(db.fetch_member(&server.id, &id()).await);
+
+    assert_eq!(db.fetch_member_count(&server.id).await.unwrap(), 2);
+    assert_eq!(db.fetch_server_count(&a).await.unwrap(), 2);
+    assert_eq!(db.fetch_all_members(&server.id).await.unwrap().len(), 2);
+    assert_eq!(db.fetch_all_memberships(&a).await.unwrap().len(), 2);
+
+    let members = db
+        .fetch_members(&server.id, &[a.clone(), id()])
+        .await
+        .unwrap();
+    assert_eq!(members.len(), 1);
+    assert_eq!(members[0].id.user, a);
+
+    db.update_member(
+        &member.id,
+        &PartialMember {
+            nickname: Some("nickname".to_string()),
+            ..Default::default()
+        },
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let fetched = db.fetch_member(&server.id, &a).await.unwrap();
+    assert_eq!(fetched.nickname.as_deref(), Some("nickname"));
+
+    db.update_member(
+        &member.id,
+        &PartialMember {
+            roles: Some(vec![id()]),
+            ..Default::default()
+        },
+        vec![FieldsMember::Nickname],
+    )
+    .await
+    .unwrap();
+
+    let fetched = db.fetch_member(&server.id, &a).await.unwrap();
+    assert_eq!(fetched.nickname, None);
+    assert_eq!(fetched.roles.len(), 1);
+
+    db.delete_member(&member.id).await.unwrap();
+    assert_not_found(db.fetch_member(&server.id, &a).await);
+    assert_eq!(db.fetch_member_count(&server.id).await.unwrap(), 1);
+}
+
+/// Inserting, fetching and removing bans
+async fn bans(db: &dyn AbstractDatabase) {
+    let (server, _) = insert_server(db).await;
+    let (a, b) = (id(), id());
+
+    for user in [&a, &b] {
+        db.insert_ban(&ServerBan {
+            id: MemberCompositeKey {
+                server: server.id.to_string(),
+                user: user.to_string(),
+            },
+            reason: Some("reason".to_string()),
+        })
+        .await
+        .unwrap();
+    }
+
+    let ban = db.fetch_ban(&server.id, &a).await.unwrap();
+    assert_eq!(ban.reason.as_deref(), Some("reason"));
+
+    assert_eq!(
+        sorted(
+            db.fetch_bans(&server.id)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|ban| ban.id.user)
+                .collect()
+        ),
+        sorted(vec![a.clone(), b])
+    );
+
+    db.delete_ban(&ban.id).await.unwrap();
+    assert_not_found(db.fetch_ban(&server.id, &a).await);
+    assert_eq!(db.fetch_bans(&server.id).await.unwrap().len(), 1);
+}
+
+/// Creating, updating and deleting roles
+async fn roles(db: &dyn AbstractDatabase) {
+    let (server, channel) = insert_server(db).await;
+    let (role_id, other_role) = (id(), id());
+
+    for role in [&role_id, &other_role] {
+        db.insert_role(
+            &server.id,
+            role,
+            &Role {
+                name: "role".to_string(),
+                colour: Some("red".to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .unwrap();
+    }
+
+    db.update_role(
+        &server.id,
+        &role_id,
+        &PartialRole {
+            name: Some("renamed".to_string()),
+            ..Default::default()
+        },
+        vec![FieldsRole::Colour],
+    )
+    .await
+    .unwrap();
+
+    let role = db.fetch_server(&server.id).await.unwrap().roles[&role_id].clone();
+    assert_eq!(role.name, "renamed");
+    assert_eq!(role.colour, None);
+
+    // Deleting a role removes it from members and channel overrides.
+    let member = insert_member(db, &server.id, &id()).await;
+    db.update_member(
+        &member.id,
+        &PartialMember {
+            roles: Some(vec![role_id.clone(), other_role.clone()]),
+            ..Default::default()
+        },
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    db.set_channel_role_permission(channel.id(), &role_id, OverrideField::default())
+        .await
+        .unwrap();
+
+    db.delete_role(&server.id, &role_id).await.unwrap();
+
+    let server = db.fetch_server(&server.id).await.unwrap();
+    assert!(!server.roles.contains_key(&role_id));
+    assert!(server.roles.contains_key(&other_role));
+
+    let member = db
+        .fetch_member(&member.id.server, &member.id.user)
+        .await
+        .unwrap();
+    assert_eq!(member.roles, vec![other_role]);
+
+    match db.fetch_channel(channel.id()).await.unwrap() {
+        Channel::TextChannel {
+            role_permissions, ..
+        } => assert!(!role_permissions.contains_key(&role_id)),
+        channel => panic!("unexpected channel {:?}", channel),
+    }
+}
+
+/// Filtering and paginating the audit log
+async fn audit_log(db: &dyn AbstractDatabase) {
+    let (server, _) = insert_server(db).await;
+    let (moderator, admin, prefix) = (id(), id(), id());
+
+    let mut renamed = server.clone();
+    renamed.name = "renamed".to_string();
+
+    let entries = [
+        (&moderator, AuditLogAction::MemberKick),
+        (&admin, AuditLogAction::ServerUpdate),
+        (&moderator, AuditLogAction::MemberBan),
+        (&moderator, AuditLogAction::MemberKick),
+    ];
+
+    let mut ids = vec![];
+    for (i, (actor, action)) in entries.into_iter().enumerate() {
+        let mut entry = AuditLogEntry::new(&server.id, actor, action, &id());
+        entry.id = format!("{prefix}{i:02}");
+
+        if action == AuditLogAction::ServerUpdate {
+            entry = entry.with_changes(AuditLogChanges::server(
+                Some(&server),
+                Some(&renamed),
+                vec![],
+            ));
+        }
+
+        db.insert_audit_log_entry(&entry).await.unwrap();
+        ids.push(entry.id);
+    }
+
+    assert!(db
+        .insert_audit_log_entry(&AuditLogEntry {
+            id: ids[0].clone(),
+            ..AuditLogEntry::new(&server.id, &admin, AuditLogAction::MemberKick, &id())
+        })
+        .await
+        .is_err());
+
+    let fetch = |limit, before: Option<&String>, actor: Option<&String>, action| {
+        let server = server.id.clone();
+        let (before, actor) = (before.cloned(), actor.cloned());
+        async move {
+            db.fetch_audit_log(&server, limit, before, actor, action)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|entry| entry.id)
+                .collect::<Vec<String>>()
+        }
+    };
+
+    assert_eq!(
+        fetch(10, None, None, None).await,
+        ids.iter().rev().cloned().collect::<Vec<String>>()
+    );
+
+    assert_eq!(
+        fetch(2, Some(&ids[3]), None, None).await,
+        vec![ids[2].clone(), ids[1].clone()]
+    );
+
+    assert_eq!(
+        fetch(10, None, Some(&moderator), Some(AuditLogAction::MemberKick)).await,
+        vec![ids[3].clone(), ids[0].clone()]
+    );
+
+    let entry = db
+        .fetch_audit_log(&server.id, 10, None, Some(admin), None)
+        .await
+        .unwrap()
+        .remove(0);
+
+    match entry.changes {
+        Some(AuditLogChanges::Server { before, after, .. }) => {
+            assert_eq!(before.name, Some(server.name.clone()));
+            assert_eq!(after.name, Some("renamed".to_string()));
+            assert_eq!(after.description, None);
+        }
+        changes => panic!("unexpected changes {:?}", changes),
+    }
+
+    // Deleting the server also deletes its audit log.
+    db.delete_server(&server).await.unwrap();
+    assert!(fetch(10, None, None, None).await.is_empty());
+}
+
+/// Automod rule lookups, updates and cleanup
+async fn automod_rules(db: &dyn AbstractDatabase) {
+    let (server, channel) = insert_server(db).await;
+    let (other, _) = insert_server(db).await;
+
+    let rule = AutomodRule {
+        id: id(),
+        server: server.id.clone(),
+        name: "No spam".to_string(),
+        enabled: true,
+        trigger: AutomodTrigger::Keyword {
+            keywords: vec!["spam".to_string()],
+        },
+        action: AutomodAction::Block,
+        exempt_roles: vec![],
+        exempt_channels: vec![],
+        revision: 0,
+    };
+
+    db.insert_automod_rule(&rule).await.unwrap();
+    assert!(db.insert_automod_rule(&rule).await.is_err());
+    db.insert_automod_rule(&AutomodRule {
+        id: id(),
+        server: other.id.clone(),
+        ..rule.clone()
+    })
+    .await
+    .unwrap();
+
+    let rules = db.fetch_automod_rules(&server.id).await.unwrap();
+    assert_eq!(rules.len(), 1);
+    assert_eq!(rules[0].id, rule.id);
+
+    db.update_automod_rule(
+        &rule.id,
+        &PartialAutomodRule {
+            enabled: Some(false),
+            action: Some(AutomodAction::Alert {
+                channel: channel.id().to_string(),
+            }),
+            exempt_channels: Some(vec![channel.id().to_string()]),
+            ..Default::default()
+        },
+    )
+    .await
+    .unwrap();
+
+    let updated = db.fetch_automod_rule(&rule.id).await.unwrap();
+    assert!(!updated.enabled);
+    assert_eq!(updated.name, rule.name);
+    assert!(matches!(
+        updated.trigger,
+        AutomodTrigger::Keyword { ref keywords } if keywords == &["spam".to_string()]
+    ));
+    assert!(matches!(
+        updated.action,
+        AutomodAction::Alert { channel: ref target } if target == channel.id()
+    ));
+    assert_eq!(updated.exempt_channels, vec![channel.id().to_string()]);
+
+    db.delete_automod_rule(&rule.id).await.unwrap();
+    assert_not_found(db.fetch_automod_rule(&rule.id).await);
+    db.delete_automod_rule(&rule.id).await.unwrap();
+
+    // Deleting the server also deletes its automod rules.
+    db.delete_server(&other).await.unwrap();
+    assert!(db.fetch_automod_rules(&other.id).await.unwrap().is_empty());
+}
+
+/// Updating a server and deleting it along with everything it owns
+async fn deletion(db: &dyn AbstractDatabase) {
+    let (server, channel) = insert_server(db).await;
+    let user = id();
+
+    db.update_server(
+        &server.id,
+        &PartialServer {
+            name: Some("renamed".to_string()),
+            ..Default::default()
+        },
+        vec![FieldsServer::Description],
+    )
+    .await
+    .unwrap();
+
+    let updated = db.fetch_server(&server.id).await.unwrap();
+    assert_eq!(updated.name, "renamed");
+    assert_eq!(updated.description, None);
+
+    assert_eq!(
+        db.fetch_servers(&[server.id.clone(), id()])
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+
+    insert_member(db, &server.id, &user).await;
+    db.insert_ban(&ServerBan {
+        id: MemberCompositeKey {
+            server: server.id.to_string(),
+            user: id(),
+        },
+        reason: None,
+    })
+    .await
+    .unwrap();
+
+    db.delete_server(&server).await.unwrap();
+
+    assert_not_found(db.fetch_server(&server.id).await);
+    assert_not_found(db.fetch_channel(channel.id()).await);
+    assert_not_found(db.fetch_member(&server.id, &user).await);
+    assert!(db.fetch_bans(&server.id).await.unwrap().is_empty());
+    assert_eq!(db.fetch_server_count(&user).await.unwrap(), 0);
+}
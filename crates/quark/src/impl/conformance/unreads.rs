@@ -0,0 +1,69 @@
+use crate::models::channel_unread::ChannelUnread;
+use crate::AbstractDatabase;
+
+use super::id;
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    mentions(db).await;
+    acknowledge_channels(db).await;
+}
+
+async fn fetch_unread(db: &dyn AbstractDatabase, channel: &str, user: &str) -> ChannelUnread {
+    db.fetch_unreads(user)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|unread| unread.id.channel == channel)
+        .expect("unread should exist")
+}
+
+/// Mentions accumulate until the channel is acknowledged
+async fn mentions(db: &dyn AbstractDatabase) {
+    let (channel, user) = (id(), id());
+    let (first, second) = (id(), id());
+
+    // Adding a mention creates the unread if it does not exist.
+    db.add_mention_to_unread(&channel, &user, &[first.clone()])
+        .await
+        .unwrap();
+    db.add_mention_to_unread(&channel, &user, &[second.clone()])
+        .await
+        .unwrap();
+
+    let unread = fetch_unread(db, &channel, &user).await;
+    assert_eq!(unread.id.user, user);
+    assert_eq!(unread.last_id, None);
+    assert_eq!(unread.mentions, Some(vec![first, second.clone()]));
+
+    // Acknowledging clears mentions.
+    db.acknowledge_message(&channel, &user, &second)
+        .await
+        .unwrap();
+
+    let unread = fetch_unread(db, &channel, &user).await;
+    assert_eq!(unread.last_id, Some(second));
+    assert!(unread.mentions.unwrap_or_default().is_empty());
+
+    // Unreads are per user.
+    assert!(db.fetch_unreads(&id()).await.unwrap().is_empty());
+}
+
+/// Acknowledging many channels at once
+async fn acknowledge_channels(db: &dyn AbstractDatabase) {
+    let (channel_a, channel_b, user) = (id(), id(), id());
+
+    db.add_mention_to_unread(&channel_a, &user, &[id()])
+        .await
+        .unwrap();
+    db.acknowledge_channels(&user, &[channel_a.clone(), channel_b.clone()])
+        .await
+        .unwrap();
+
+    assert_eq!(db.fetch_unreads(&user).await.unwrap().len(), 2);
+
+    let a = fetch_unread(db, &channel_a, &user).await;
+    let b = fetch_unread(db, &channel_b, &user).await;
+    assert!(a.last_id.is_some());
+    assert_eq!(a.last_id, b.last_id);
+    assert!(a.mentions.unwrap_or_default().is_empty());
+}
@@ -0,0 +1,208 @@
+use crate::models::user::{FieldsUser, PartialUser, RelationshipStatus, UserStatus};
+use crate::models::Channel;
+use crate::{AbstractDatabase, Error};
+
+use super::{assert_not_found, id, insert_member, insert_user, sorted};
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    lookups(db).await;
+    relationships(db).await;
+    mutual_channels(db).await;
+    mutual_servers(db).await;
+}
+
+/// Fetching, updating and deleting users
+async fn lookups(db: &dyn AbstractDatabase) {
+    let user = insert_user(db).await;
+
+    assert_eq!(db.fetch_user(&user.id).await.unwrap().id, user.id);
+    assert_not_found(db.fetch_user(&id()).await);
+
+    // Usernames are matched case-insensitively.
+    let by_username = db
+        .fetch_user_by_username(&user.username.to_uppercase())
+        .await
+        .unwrap();
+    assert_eq!(by_username.id, user.id);
+
+    assert!(db
+        .is_username_taken(&user.username.to_lowercase())
+        .await
+        .unwrap());
+    assert!(!db.is_username_taken(&id()).await.unwrap());
+
+    // Unknown session tokens are rejected.
+    match db.fetch_user_by_token(&id()).await {
+        Err(Error::InvalidSession) => {}
+        other => panic!("expected InvalidSession, got {:?}", other),
+    }
+
+    // Duplicate inserts are rejected.
+    assert!(db.insert_user(&user).await.is_err());
+
+    let other = insert_user(db).await;
+    let users = db
+        .fetch_users(&[user.id.clone(), other.id.clone(), id()])
+        .await
+        .unwrap();
+    assert_eq!(
+        sorted(users.into_iter().map(|user| user.id).collect()),
+        sorted(vec![user.id.clone(), other.id])
+    );
+
+    db.update_user(
+        &user.id,
+        &PartialUser {
+            status: Some(UserStatus {
+                text: Some("status".to_string()),
+                presence: None,
+            }),
+            ..Default::default()
+        },
+        vec![],
+    )
+    .await
+    .unwrap();
+
+    let updated = db.fetch_user(&user.id).await.unwrap();
+    assert_eq!(updated.status.unwrap().text.as_deref(), Some("status"));
+
+    db.update_user(
+        &user.id,
+        &PartialUser {
+            username: Some(format!("{}x", user.username)),
+            ..Default::default()
+        },
+        vec![FieldsUser::StatusText],
+    )
+    .await
+    .unwrap();
+
+    let updated = db.fetch_user(&user.id).await.unwrap();
+    assert_eq!(updated.username, format!("{}x", user.username));
+    assert!(updated.status.and_then(|status| status.text).is_none());
+
+    db.delete_user(&user.id).await.unwrap();
+    assert_not_found(db.fetch_user(&user.id).await);
+}
+
+/// Setting and pulling relationships, and finding mutual friends
+async fn relationships(db: &dyn AbstractDatabase) {
+    let a = insert_user(db).await;
+    let b = insert_user(db).await;
+    let both = insert_user(db).await;
+    let one = insert_user(db).await;
+
+    for (user, target) in [(&both, &a), (&both, &b), (&one, &a)] {
+        db.set_relationship(&user.id, &target.id, &RelationshipStatus::Friend)
+            .await
+            .unwrap();
+    }
+
+    // Outgoing requests do not count as friendships.
+    db.set_relationship(&one.id, &b.id, &RelationshipStatus::Outgoing)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        db.fetch_mutual_user_ids(&a.id, &b.id).await.unwrap(),
+        vec![both.id.clone()]
+    );
+
+    // Setting a relationship replaces the existing one.
+    db.set_relationship(&one.id, &b.id, &RelationshipStatus::Friend)
+        .await
+        .unwrap();
+
+    let relations = db.fetch_user(&one.id).await.unwrap().relations.unwrap();
+    assert_eq!(relations.len(), 2);
+    assert!(relations
+        .iter()
+        .all(|entry| entry.status == RelationshipStatus::Friend));
+
+    assert_eq!(
+        sorted(db.fetch_mutual_user_ids(&a.id, &b.id).await.unwrap()),
+        sorted(vec![both.id.clone(), one.id.clone()])
+    );
+
+    // Setting the relationship to None removes it.
+    db.set_relationship(&one.id, &a.id, &RelationshipStatus::None)
+        .await
+        .unwrap();
+    db.pull_relationship(&one.id, &b.id).await.unwrap();
+
+    assert!(db
+        .fetch_user(&one.id)
+        .await
+        .unwrap()
+        .relations
+        .unwrap_or_default()
+        .is_empty());
+
+    assert_eq!(
+        db.fetch_mutual_user_ids(&a.id, &b.id).await.unwrap(),
+        vec![both.id]
+    );
+}
+
+/// Direct message and group channels shared by two users
+async fn mutual_channels(db: &dyn AbstractDatabase) {
+    let (a, b, c) = (id(), id(), id());
+
+    let dm = Channel::DirectMessage {
+        id: id(),
+        active: true,
+        recipients: vec![a.clone(), b.clone()],
+        last_message_id: None,
+    };
+
+    let group = |recipients: Vec<String>| Channel::Group {
+        id: id(),
+        name: "group".to_string(),
+        owner: recipients[0].clone(),
+        description: None,
+        recipients,
+        icon: None,
+        last_message_id: None,
+        permissions: None,
+        nsfw: false,
+    };
+
+    let shared = group(vec![a.clone(), b.clone(), c.clone()]);
+    let unshared = group(vec![a.clone(), c.clone()]);
+
+    for channel in [&dm, &shared, &unshared] {
+        db.insert_channel(channel).await.unwrap();
+    }
+
+    assert_eq!(
+        sorted(db.fetch_mutual_channel_ids(&a, &b).await.unwrap()),
+        sorted(vec![dm.id().to_string(), shared.id().to_string()])
+    );
+
+    assert_eq!(
+        db.fetch_mutual_channel_ids(&b, &c).await.unwrap(),
+        vec![shared.id().to_string()]
+    );
+}
+
+/// Servers which two users are both members of
+async fn mutual_servers(db: &dyn AbstractDatabase) {
+    let (a, b) = (id(), id());
+    let (only_a, shared, only_b) = (id(), id(), id());
+
+    for (server, user) in [(&only_a, &a), (&shared, &a), (&shared, &b), (&only_b, &b)] {
+        insert_member(db, server, user).await;
+    }
+
+    assert_eq!(
+        db.fetch_mutual_server_ids(&a, &b).await.unwrap(),
+        vec![shared]
+    );
+
+    assert!(db
+        .fetch_mutual_server_ids(&a, &id())
+        .await
+        .unwrap()
+        .is_empty());
+}
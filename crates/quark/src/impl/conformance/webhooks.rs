@@ -0,0 +1,96 @@
+use crate::models::attachment::File;
+use crate::models::webhook::{FieldsWebhook, PartialWebhook};
+use crate::models::Webhook;
+use crate::AbstractDatabase;
+
+use super::{assert_not_found, id, insert_text_channel, sorted};
+
+pub async fn run(db: &dyn AbstractDatabase) {
+    lifecycle(db).await;
+    channel_deletion(db).await;
+}
+
+/// Create and insert a new webhook in a channel
+async fn insert_webhook(db: &dyn AbstractDatabase, channel: &str) -> Webhook {
+    let webhook = Webhook {
+        id: id(),
+        name: "Conformance".to_string(),
+        avatar: Some(File {
+            id: id(),
+            tag: "avatars".to_string(),
+            filename: "avatar.png".to_string(),
+            content_type: "image/png".to_string(),
+            ..Default::default()
+        }),
+        channel_id: channel.to_string(),
+        creator_id: id(),
+        token: Some(id()),
+    };
+
+    db.insert_webhook(&webhook).await.unwrap();
+    webhook
+}
+
+/// Fetching, updating and deleting webhooks
+async fn lifecycle(db: &dyn AbstractDatabase) {
+    let channel = id();
+    let webhook = insert_webhook(db, &channel).await;
+    let other = insert_webhook(db, &channel).await;
+    insert_webhook(db, &id()).await;
+
+    assert!(db.insert_webhook(&webhook).await.is_err());
+    assert_not_found(db.fetch_webhook(&id()).await);
+
+    let fetched = db.fetch_webhook(&webhook.id).await.unwrap();
+    assert_eq!(fetched.token, webhook.token);
+
+    assert_eq!(
+        sorted(
+            db.fetch_webhooks_for_channel(&channel)
+                .await
+                .unwrap()
+                .into_iter()
+                .map(|webhook| webhook.id)
+                .collect()
+        ),
+        sorted(vec![webhook.id.clone(), other.id])
+    );
+
+    db.update_webhook(
+        &webhook.id,
+        &PartialWebhook {
+            name: Some("renamed".to_string()),
+            ..Default::default()
+        },
+        vec![FieldsWebhook::Avatar],
+    )
+    .await
+    .unwrap();
+
+    let fetched = db.fetch_webhook(&webhook.id).await.unwrap();
+    assert_eq!(fetched.name, "renamed");
+    assert!(fetched.avatar.is_none());
+    assert_eq!(fetched.token, webhook.token);
+
+    db.delete_webhook(&webhook.id).await.unwrap();
+    assert_not_found(db.fetch_webhook(&webhook.id).await);
+    assert_eq!(
+        db.fetch_webhooks_for_channel(&channel).await.unwrap().len(),
+        1
+    );
+}
+
+/// Deleting a channel also deletes its webhooks
+async fn channel_deletion(db: &dyn AbstractDatabase) {
+    let channel = insert_text_channel(db, &id()).await;
+    let webhook = insert_webhook(db, channel.id()).await;
+
+    db.delete_channel(&channel).await.unwrap();
+
+    assert_not_found(db.fetch_webhook(&webhook.id).await);
+    assert!(db
+        .fetch_webhooks_for_channel(channel.id())
+        .await
+        .unwrap()
+        .is_empty());
+}
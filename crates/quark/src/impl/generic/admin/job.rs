@@ -0,0 +1,95 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{de::DeserializeOwned, Serialize};
+use ulid::Ulid;
+
+use crate::{models::Job, variables::delta::MAX_JOB_ATTEMPTS, Database, Error, Result};
+
+/// Delay before the first retry of a failed job
+const BASE_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Longest delay between retries of a failed job
+const MAX_BACKOFF: Duration = Duration::from_secs(60 * 60);
+
+/// Current time in milliseconds since Unix epoch
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+impl Job {
+    /// Create a new job for a queue
+    pub fn new<T: Serialize>(queue: &str, payload: &T) -> Result<Job> {
+        Ok(Job {
+            id: Ulid::new().to_string(),
+            queue: queue.to_string(),
+            payload: serde_json::to_string(payload).map_err(|_| Error::InternalError)?,
+            attempts: 0,
+            available_at: now(),
+            last_error: None,
+            dead: false,
+        })
+    }
+
+    /// Create and persist a new job for a queue
+    pub async fn enqueue<T: Serialize>(db: &Database, queue: &str, payload: &T) -> Result<()> {
+        db.insert_job(&Job::new(queue, payload)?).await
+    }
+
+    /// Claim the next available job from a queue
+    ///
+    /// The job stays hidden from other workers for the given visibility timeout,
+    /// after which it is handed out again unless it was completed or failed.
+    pub async fn claim(db: &Database, queue: &str, visibility: Duration) -> Result<Option<Job>> {
+        let now = now();
+        db.claim_job(queue, now, now + visibility.as_millis() as i64)
+            .await
+    }
+
+    /// Deserialise the task information held by this job
+    pub fn payload<T: DeserializeOwned>(&self) -> Result<T> {
+        serde_json::from_str(&self.payload).map_err(|_| Error::InternalError)
+    }
+
+    /// Remove this job from the queue
+    pub async fn complete(&self, db: &Database) -> Result<()> {
+        db.complete_job(&self.id).await
+    }
+
+    /// Record a failed attempt at this job
+    ///
+    /// The job is retried with exponential backoff until it runs out
+    /// of attempts, at which point it is moved to the dead letter queue.
+    pub async fn fail(&self, db: &Database, error: &str) -> Result<()> {
+        if self.attempts >= *MAX_JOB_ATTEMPTS {
+            warn!(
+                "Job {} in {} failed {} times, moving to dead letter queue: {error}",
+                self.id, self.queue, self.attempts
+            );
+
+            return db.bury_job(&self.id, error).await;
+        }
+
+        let backoff = BASE_BACKOFF
+            .saturating_mul(2_u32.saturating_pow(self.attempts.saturating_sub(1)))
+            .min(MAX_BACKOFF);
+
+        db.retry_job(&self.id, now() + backoff.as_millis() as i64, error)
+            .await
+    }
+
+    /// Complete or fail this job depending on the outcome of running it
+    pub async fn finish(&self, db: &Database, result: Result<()>) {
+        if let Err(err) = match result {
+            Ok(_) => self.complete(db).await,
+            Err(error) => self.fail(db, &format!("{error:?}")).await,
+        } {
+            error!(
+                "Failed to update job {} in {}: {err:?}",
+                self.id, self.queue
+            );
+        }
+    }
+}
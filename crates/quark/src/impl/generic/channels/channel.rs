@@ -17,7 +17,8 @@ impl Channel {
             | Channel::Group { id, .. }
             | Channel::SavedMessages { id, .. }
             | Channel::TextChannel { id, .. }
-            | Channel::VoiceChannel { id, .. } => id,
+            | Channel::VoiceChannel { id, .. }
+            | Channel::Thread { id, .. } => id,
         }
     }
 
@@ -28,7 +29,8 @@ impl Channel {
             | Channel::Group { id, .. }
             | Channel::SavedMessages { id, .. }
             | Channel::TextChannel { id, .. }
-            | Channel::VoiceChannel { id, .. } => id,
+            | Channel::VoiceChannel { id, .. }
+            | Channel::Thread { id, .. } => id,
         }
     }
 
@@ -37,12 +39,33 @@ impl Channel {
         matches!(self, Channel::DirectMessage { .. })
     }
 
+    /// Get the id of the server this channel belongs to
+    pub fn server_id(&'_ self) -> Option<&'_ str> {
+        match self {
+            Channel::TextChannel { server, .. }
+            | Channel::VoiceChannel { server, .. }
+            | Channel::Thread { server, .. } => Some(server),
+            _ => None,
+        }
+    }
+
+    /// Get the id of the channel this thread was started in
+    pub fn thread_parent(&'_ self) -> Option<&'_ str> {
+        match self {
+            Channel::Thread { parent, .. } => Some(parent),
+            _ => None,
+        }
+    }
+
     /// Create a channel
     pub async fn create(&self, db: &Database) -> Result<()> {
         db.insert_channel(self).await?;
 
         let event = EventV1::ChannelCreate(self.clone());
         match self {
+            Self::Thread { parent, .. } => {
+                EventV1::ThreadCreate(self.clone()).p(parent.clone()).await;
+            }
             Self::SavedMessages { user, .. } => event.private(user.clone()).await,
             Self::DirectMessage { recipients, .. } | Self::Group { recipients, .. } => {
                 for recipient in recipients {
@@ -73,6 +96,18 @@ impl Channel {
         let id = self.id().to_string();
         db.update_channel(&id, &partial, remove.clone()).await?;
 
+        if let Self::Thread { parent, .. } = self {
+            EventV1::ThreadUpdate {
+                id,
+                data: partial,
+                clear: remove,
+            }
+            .p(parent.clone())
+            .await;
+
+            return Ok(());
+        }
+
         EventV1::ChannelUpdate {
             id: id.clone(),
             data: partial,
@@ -90,7 +125,14 @@ impl Channel {
     /// Delete a channel
     pub async fn delete(self, db: &Database) -> Result<()> {
         let id = self.id().to_string();
-        EventV1::ChannelDelete { id: id.clone() }.p(id).await;
+        if let Self::Thread { parent, .. } = &self {
+            EventV1::ThreadDelete { id: id.clone() }
+                .p(parent.clone())
+                .await;
+        } else {
+            EventV1::ChannelDelete { id: id.clone() }.p(id).await;
+        }
+
         db.delete_channel(&self).await
     }
 
@@ -129,6 +171,34 @@ impl Channel {
         }
     }
 
+    /// Move the last message id forward, ignoring ids older than the current one
+    pub(crate) fn advance_last_message_id(&mut self, message_id: &str, activate: bool) {
+        if let Self::DirectMessage { active, .. } = self {
+            *active |= activate;
+        }
+
+        if let Self::DirectMessage {
+            last_message_id, ..
+        }
+        | Self::Group {
+            last_message_id, ..
+        }
+        | Self::TextChannel {
+            last_message_id, ..
+        }
+        | Self::Thread {
+            last_message_id, ..
+        } = self
+        {
+            if last_message_id
+                .as_deref()
+                .map_or(true, |current| current < message_id)
+            {
+                last_message_id.replace(message_id.to_string());
+            }
+        }
+    }
+
     /// Apply partial channel to channel
     pub fn apply_options(&mut self, partial: PartialChannel) {
         // ! FIXME: maybe flatten channel object?
@@ -213,12 +283,21 @@ impl Channel {
                     default_permissions.replace(v);
                 }
             }
+            Self::Thread { name, archived, .. } => {
+                if let Some(v) = partial.name {
+                    *name = v;
+                }
+
+                if let Some(v) = partial.archived {
+                    *archived = v;
+                }
+            }
             _ => {}
         }
     }
 
     /// Acknowledge a message
-    pub async fn ack(&self, user: &str, message: &str) -> Result<()> {
+    pub async fn ack(&self, db: &Database, user: &str, message: &str) -> Result<()> {
         EventV1::ChannelAck {
             id: self.id().to_string(),
             user: user.to_string(),
@@ -228,6 +307,7 @@ impl Channel {
         .await;
 
         crate::tasks::ack::queue(
+            db,
             self.id().to_string(),
             user.to_string(),
             AckEvent::AckMessage {
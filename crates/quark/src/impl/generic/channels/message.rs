@@ -1,5 +1,7 @@
 use std::collections::HashSet;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
+use indexmap::IndexMap;
 use serde_json::json;
 use ulid::Ulid;
 use validator::Validate;
@@ -9,9 +11,9 @@ use crate::{
     models::{
         message::{
             AppendMessage, BulkMessageResponse, Interactions, PartialMessage, SendableEmbed,
-            SystemMessage,
+            ServerSearchResponse, SystemMessage,
         },
-        Channel, Emoji, Message, User,
+        Channel, Emoji, Message, Server, User,
     },
     permissions::PermissionCalculator,
     presence::presence_filter_online,
@@ -20,9 +22,24 @@ use crate::{
         january::{Embed, Text},
         push::PushNotification,
     },
+    variables::delta::{MAX_PIN_COUNT, MESSAGE_RECOVERY_WINDOW},
     Database, Error, Permission, Result,
 };
 
+/// Current time in milliseconds since Unix epoch
+fn now() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as i64)
+        .unwrap_or_default()
+}
+
+/// Earliest deletion time of messages which can still be restored
+fn recovery_cutoff() -> i64 {
+    let window = Duration::from_secs(*MESSAGE_RECOVERY_WINDOW * 60 * 60);
+    now() - window.as_millis() as i64
+}
+
 impl Message {
     /// Create a message
     pub async fn create_no_web_push(
@@ -38,6 +55,7 @@ impl Message {
 
         // Update last_message_id
         crate::tasks::last_message_id::queue(
+            db,
             channel.to_string(),
             self.id.to_string(),
             is_direct_dm,
@@ -48,6 +66,7 @@ impl Message {
         if let Some(mentions) = &self.mentions {
             for user in mentions {
                 crate::tasks::ack::queue(
+                    db,
                     channel.to_string(),
                     user.to_string(),
                     AckEvent::AddMention {
@@ -73,6 +92,7 @@ impl Message {
 
         // Push out Web Push notifications
         crate::tasks::web_push::queue(
+            db,
             {
                 let mut target_ids = vec![];
                 match &channel {
@@ -83,7 +103,7 @@ impl Message {
                             .into_iter()
                             .collect::<Vec<String>>();
                     }
-                    Channel::TextChannel { .. } => {
+                    Channel::TextChannel { .. } | Channel::Thread { .. } => {
                         if let Some(mentions) = &self.mentions {
                             target_ids.append(&mut mentions.clone());
                         }
@@ -115,6 +135,58 @@ impl Message {
         Ok(())
     }
 
+    /// Pin this message to its channel
+    pub async fn pin(&mut self, db: &Database, channel: &Channel, by: &str) -> Result<()> {
+        if self.pinned {
+            return Ok(());
+        }
+
+        db.pin_message(&self.channel, &self.id, *MAX_PIN_COUNT)
+            .await?;
+
+        let partial = PartialMessage {
+            pinned: Some(true),
+            ..Default::default()
+        };
+
+        self.apply_options(partial.clone());
+
+        EventV1::MessageUpdate {
+            id: self.id.clone(),
+            channel: self.channel.clone(),
+            data: partial,
+        }
+        .p(self.channel.clone())
+        .await;
+
+        SystemMessage::MessagePinned {
+            id: self.id.clone(),
+            by: by.to_string(),
+        }
+        .into_message(self.channel.clone())
+        .create(db, channel, None)
+        .await
+        .ok();
+
+        Ok(())
+    }
+
+    /// Unpin this message from its channel
+    pub async fn unpin(&mut self, db: &Database) -> Result<()> {
+        if !self.pinned {
+            return Ok(());
+        }
+
+        self.update(
+            db,
+            PartialMessage {
+                pinned: Some(false),
+                ..Default::default()
+            },
+        )
+        .await
+    }
+
     /// Append message data
     pub async fn append(
         db: &Database,
@@ -136,17 +208,11 @@ impl Message {
     }
 
     /// Delete a message
+    ///
+    /// The message can be restored until the recovery window passes,
+    /// its attachments are only marked as deleted once it is purged.
     pub async fn delete(self, db: &Database) -> Result<()> {
-        let file_ids: Vec<String> = self
-            .attachments
-            .map(|files| files.iter().map(|file| file.id.to_string()).collect())
-            .unwrap_or_default();
-
-        if !file_ids.is_empty() {
-            db.mark_attachments_as_deleted(&file_ids).await?;
-        }
-
-        db.delete_message(&self.id).await?;
+        db.delete_message(&self.id, now()).await?;
 
         EventV1::MessageDelete {
             id: self.id,
@@ -159,7 +225,7 @@ impl Message {
 
     /// Bulk delete messages
     pub async fn bulk_delete(db: &Database, channel: &str, ids: Vec<String>) -> Result<()> {
-        db.delete_messages(channel, ids.clone()).await?;
+        db.delete_messages(channel, ids.clone(), now()).await?;
         EventV1::BulkMessageDelete {
             channel: channel.to_string(),
             ids,
@@ -169,6 +235,21 @@ impl Message {
         Ok(())
     }
 
+    /// Restore a message which was deleted from a channel within the recovery window
+    pub async fn restore(db: &Database, channel: &str, id: &str) -> Result<Message> {
+        let message = db.restore_message(channel, id, recovery_cutoff()).await?;
+        EventV1::Message(message.clone())
+            .p(message.channel.to_string())
+            .await;
+
+        Ok(message)
+    }
+
+    /// Permanently remove messages which were deleted before the recovery window
+    pub async fn purge_deleted(db: &Database) -> Result<()> {
+        db.purge_deleted_messages(recovery_cutoff()).await
+    }
+
     /// Validate the sum of content of a message is under threshold
     pub fn validate_sum(
         content: &Option<String>,
@@ -345,6 +426,8 @@ impl From<SystemMessage> for String {
             SystemMessage::ChannelOwnershipChanged { .. } => {
                 "Channel ownership changed.".to_string()
             }
+            SystemMessage::MessagePinned { .. } => "Message pinned.".to_string(),
+            SystemMessage::AutomodTriggered { .. } => "Automod rule triggered.".to_string(),
         }
     }
 }
@@ -386,13 +469,13 @@ impl BulkMessageResponse {
             let users = db.fetch_users(&user_ids).await?;
 
             Ok(match channel {
-                Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } => {
-                    BulkMessageResponse::MessagesAndUsers {
-                        messages,
-                        users,
-                        members: Some(db.fetch_members(server, &user_ids).await?),
-                    }
-                }
+                Channel::TextChannel { server, .. }
+                | Channel::VoiceChannel { server, .. }
+                | Channel::Thread { server, .. } => BulkMessageResponse::MessagesAndUsers {
+                    messages,
+                    users,
+                    members: Some(db.fetch_members(server, &user_ids).await?),
+                },
                 _ => BulkMessageResponse::MessagesAndUsers {
                     messages,
                     users,
@@ -405,6 +488,40 @@ impl BulkMessageResponse {
     }
 }
 
+impl ServerSearchResponse {
+    /// Group messages found in a server by their channel
+    pub async fn transform(
+        db: &Database,
+        server: &Server,
+        messages: Vec<Message>,
+        include_users: Option<bool>,
+    ) -> Result<ServerSearchResponse> {
+        let (users, members) = if let Some(true) = include_users {
+            let user_ids = messages.get_user_ids();
+            (
+                Some(db.fetch_users(&user_ids).await?),
+                Some(db.fetch_members(&server.id, &user_ids).await?),
+            )
+        } else {
+            (None, None)
+        };
+
+        let mut channels = IndexMap::<String, Vec<Message>>::new();
+        for message in messages {
+            channels
+                .entry(message.channel.clone())
+                .or_default()
+                .push(message);
+        }
+
+        Ok(ServerSearchResponse {
+            channels,
+            users,
+            members,
+        })
+    }
+}
+
 impl Interactions {
     /// Validate interactions info is correct
     pub async fn validate(
@@ -0,0 +1,107 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use ulid::Ulid;
+
+use crate::{
+    models::{Channel, Message, MessageRevision},
+    variables::delta::MESSAGE_HISTORY_RETENTION,
+    Database, Error, Result,
+};
+
+impl MessageRevision {
+    /// Number of days to keep revisions of messages in a given channel for
+    pub async fn retention(db: &Database, channel: &Channel) -> Result<u32> {
+        Ok(match channel.server_id() {
+            Some(server) => db
+                .fetch_server(server)
+                .await?
+                .message_history_retention
+                .unwrap_or(*MESSAGE_HISTORY_RETENTION),
+            None => *MESSAGE_HISTORY_RETENTION,
+        })
+    }
+
+    /// Lowest id a revision may have to still be within the retention period
+    fn cutoff(retention: u32) -> String {
+        let timestamp = SystemTime::now()
+            .checked_sub(Duration::from_secs(retention as u64 * 24 * 60 * 60))
+            .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or_default();
+
+        Ulid::from_parts(timestamp, 0).to_string()
+    }
+
+    /// Delete revisions which have fallen out of the retention period of their channel
+    ///
+    /// Revisions are otherwise only pruned when their message is edited or fetched again.
+    pub async fn purge_expired(db: &Database) -> Result<()> {
+        let mut servers: HashMap<String, u32> = HashMap::new();
+        for id in db.fetch_message_revision_channels().await? {
+            let retention = match db.fetch_channel(&id).await {
+                Ok(channel) => match channel.server_id() {
+                    Some(server) => match servers.get(server) {
+                        Some(retention) => *retention,
+                        None => {
+                            let retention = MessageRevision::retention(db, &channel).await?;
+                            servers.insert(server.to_string(), retention);
+                            retention
+                        }
+                    },
+                    None => *MESSAGE_HISTORY_RETENTION,
+                },
+                // Nothing can read revisions of a channel which is gone.
+                Err(Error::NotFound) => 0,
+                Err(err) => return Err(err),
+            };
+
+            db.delete_channel_message_revisions_before(&id, &MessageRevision::cutoff(retention))
+                .await?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Message {
+    /// Store the current version of this message before it is edited
+    ///
+    /// Also drops any revisions which have fallen out of the retention period.
+    pub async fn record_revision(&self, db: &Database, channel: &Channel) -> Result<()> {
+        let retention = MessageRevision::retention(db, channel).await?;
+        if retention == 0 {
+            return Ok(());
+        }
+
+        db.insert_message_revision(&MessageRevision {
+            id: Ulid::new().to_string(),
+            message: self.id.clone(),
+            channel: self.channel.clone(),
+            content: self.content.clone(),
+            embeds: self.embeds.clone(),
+            edited: self.edited,
+        })
+        .await?;
+
+        db.delete_message_revisions_before(&self.id, &MessageRevision::cutoff(retention))
+            .await
+    }
+
+    /// Fetch previous versions of this message, oldest first
+    ///
+    /// Revisions outside of the current retention period are removed first.
+    pub async fn fetch_revisions(
+        &self,
+        db: &Database,
+        channel: &Channel,
+    ) -> Result<Vec<MessageRevision>> {
+        let retention = MessageRevision::retention(db, channel).await?;
+        db.delete_message_revisions_before(&self.id, &MessageRevision::cutoff(retention))
+            .await?;
+
+        db.fetch_message_revisions(&self.id).await
+    }
+}
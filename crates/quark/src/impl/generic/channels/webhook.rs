@@ -0,0 +1,90 @@
+use subtle::ConstantTimeEq;
+
+use crate::{
+    events::client::EventV1,
+    models::webhook::{FieldsWebhook, PartialWebhook, Webhook},
+    variables::delta::AUTUMN_URL,
+    Database, Result,
+};
+
+impl Webhook {
+    /// Create a webhook
+    pub async fn create(&self, db: &Database) -> Result<()> {
+        db.insert_webhook(self).await?;
+        EventV1::WebhookCreate(self.clone().without_token())
+            .p(self.channel_id.clone())
+            .await;
+
+        Ok(())
+    }
+
+    /// Update webhook data
+    pub async fn update(
+        &mut self,
+        db: &Database,
+        mut partial: PartialWebhook,
+        remove: Vec<FieldsWebhook>,
+    ) -> Result<()> {
+        for field in &remove {
+            self.remove(field);
+        }
+
+        self.apply_options(partial.clone());
+
+        db.update_webhook(&self.id, &partial, remove.clone())
+            .await?;
+
+        partial.token = None;
+        EventV1::WebhookUpdate {
+            id: self.id.clone(),
+            data: partial,
+            clear: remove,
+        }
+        .p(self.channel_id.clone())
+        .await;
+
+        Ok(())
+    }
+
+    /// Delete a webhook
+    pub async fn delete(self, db: &Database) -> Result<()> {
+        db.delete_webhook(&self.id).await?;
+
+        EventV1::WebhookDelete {
+            id: self.id.clone(),
+        }
+        .p(self.channel_id.clone())
+        .await;
+
+        Ok(())
+    }
+
+    /// Remove a field from this object
+    pub fn remove(&mut self, field: &FieldsWebhook) {
+        match field {
+            FieldsWebhook::Avatar => self.avatar = None,
+        }
+    }
+
+    /// Strip the secret token from this webhook
+    pub fn without_token(mut self) -> Webhook {
+        self.token = None;
+        self
+    }
+
+    /// Check whether the given token matches this webhook
+    ///
+    /// Compared in constant time so the token can't be guessed byte by byte.
+    pub fn has_token(&self, token: &str) -> bool {
+        self.token
+            .as_deref()
+            .map_or(false, |own| own.as_bytes().ct_eq(token.as_bytes()).into())
+    }
+
+    /// Get the URL of this webhook's avatar, if it has one
+    pub fn avatar_url(&self) -> Option<String> {
+        self.avatar
+            .as_ref()
+            .map(|file| format!("{}/avatars/{}", *AUTUMN_URL, file.id))
+    }
+}
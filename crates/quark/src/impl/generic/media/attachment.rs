@@ -35,4 +35,9 @@ impl File {
         db.find_and_use_attachment(id, "emojis", "object", parent)
             .await
     }
+
+    pub async fn use_webhook_avatar(db: &Database, id: &str, parent: &str) -> Result<File> {
+        db.find_and_use_attachment(id, "avatars", "object", parent)
+            .await
+    }
 }
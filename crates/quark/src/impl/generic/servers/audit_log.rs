@@ -0,0 +1,138 @@
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{Map, Value};
+use ulid::Ulid;
+
+use crate::{
+    models::{
+        audit_log::{AuditLogAction, AuditLogChanges},
+        channel::FieldsChannel,
+        server::{FieldsRole, FieldsServer, Role},
+        server_member::FieldsMember,
+        AuditLogEntry, Channel, Member, Server,
+    },
+    Database,
+};
+
+/// Find the fields which differ between two versions of an object
+///
+/// Returns partial objects holding the old and new values respectively,
+/// a missing version (i.e. on creation or deletion) is treated as empty.
+fn diff<T: Serialize, P: DeserializeOwned + Default>(
+    before: Option<&T>,
+    after: Option<&T>,
+) -> (P, P) {
+    let fields = |object: Option<&T>| match object.map(serde_json::to_value) {
+        Some(Ok(Value::Object(map))) => map,
+        _ => Map::new(),
+    };
+
+    let (before, after) = (fields(before), fields(after));
+    let changed = |from: &Map<String, Value>, to: &Map<String, Value>| {
+        Value::Object(
+            from.iter()
+                .filter(|(key, value)| to.get(*key) != Some(value))
+                .map(|(key, value)| (key.clone(), value.clone()))
+                .collect(),
+        )
+    };
+
+    (
+        serde_json::from_value(changed(&before, &after)).unwrap_or_default(),
+        serde_json::from_value(changed(&after, &before)).unwrap_or_default(),
+    )
+}
+
+impl AuditLogChanges {
+    /// Changes made to a server
+    pub fn server(
+        before: Option<&Server>,
+        after: Option<&Server>,
+        remove: Vec<FieldsServer>,
+    ) -> Self {
+        let (before, after) = diff(before, after);
+        AuditLogChanges::Server {
+            before,
+            after,
+            remove,
+        }
+    }
+
+    /// Changes made to a channel
+    pub fn channel(
+        before: Option<&Channel>,
+        after: Option<&Channel>,
+        remove: Vec<FieldsChannel>,
+    ) -> Self {
+        let (before, after) = diff(before, after);
+        AuditLogChanges::Channel {
+            before,
+            after,
+            remove,
+        }
+    }
+
+    /// Changes made to a role
+    pub fn role(before: Option<&Role>, after: Option<&Role>, remove: Vec<FieldsRole>) -> Self {
+        let (before, after) = diff(before, after);
+        AuditLogChanges::Role {
+            before,
+            after,
+            remove,
+        }
+    }
+
+    /// Changes made to a member
+    pub fn member(
+        before: Option<&Member>,
+        after: Option<&Member>,
+        remove: Vec<FieldsMember>,
+    ) -> Self {
+        let (before, after) = diff(before, after);
+        AuditLogChanges::Member {
+            before,
+            after,
+            remove,
+        }
+    }
+}
+
+impl AuditLogEntry {
+    /// Start a new audit log entry
+    pub fn new(server: &str, actor: &str, action: AuditLogAction, target: &str) -> Self {
+        AuditLogEntry {
+            id: Ulid::new().to_string(),
+            server: server.to_string(),
+            actor: actor.to_string(),
+            action,
+            target: target.to_string(),
+            changes: None,
+            reason: None,
+        }
+    }
+
+    /// Attach changes made to the target
+    pub fn with_changes(self, changes: AuditLogChanges) -> Self {
+        AuditLogEntry {
+            changes: Some(changes),
+            ..self
+        }
+    }
+
+    /// Attach the reason given for this action
+    pub fn with_reason(self, reason: Option<String>) -> Self {
+        AuditLogEntry { reason, ..self }
+    }
+
+    /// Record this entry in the server's audit log
+    ///
+    /// The action has already been carried out by the time it is recorded,
+    /// so failures are logged rather than returned to the client.
+    pub async fn record(self, db: &Database) {
+        if let Err(err) = db.insert_audit_log_entry(&self).await {
+            error!(
+                "Failed to record audit log entry {} in {}: {err:?}",
+                self.id, self.server
+            );
+        }
+    }
+}
@@ -0,0 +1,313 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use iso8601_timestamp::Timestamp;
+use regex::{Regex, RegexBuilder};
+use ulid::Ulid;
+
+use crate::{
+    models::{
+        automod_rule::{AutomodAction, AutomodTrigger},
+        message::{MessageSort, SystemMessage},
+        server_member::PartialMember,
+        AutomodRule, Channel, Message, Server,
+    },
+    permissions::PermissionCalculator,
+    Database, Error, Permission, Result,
+};
+
+/// Maximum number of entries in a trigger's keyword, pattern or domain list
+const MAX_TRIGGER_ENTRIES: usize = 100;
+
+/// Maximum length of a single keyword, pattern or domain
+const MAX_TRIGGER_ENTRY_LENGTH: usize = 128;
+
+/// Maximum compiled size of a regex pattern
+const MAX_REGEX_SIZE: usize = 1 << 16;
+
+/// Number of recent messages inspected for duplicate detection
+const DUPLICATE_SCAN_LIMIT: i64 = 50;
+
+lazy_static! {
+    static ref RE_LINK: Regex = Regex::new(r"(?i)https?://([^/\s:?#<>]+)").unwrap();
+
+    /// Compiled regex triggers by rule id, along with the revision they were compiled from
+    static ref COMPILED_PATTERNS: Mutex<HashMap<String, (u64, Arc<Vec<Regex>>)>> =
+        Mutex::new(HashMap::new());
+}
+
+/// Compile user provided patterns with bounded size
+fn compile(patterns: &[String]) -> Result<Vec<Regex>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            RegexBuilder::new(pattern)
+                .size_limit(MAX_REGEX_SIZE)
+                .build()
+                .map_err(|_| Error::InvalidAutomodPattern {
+                    pattern: pattern.clone(),
+                })
+        })
+        .collect()
+}
+
+/// Check whether a host is the given domain or one of its subdomains
+fn matches_domain(host: &str, domain: &str) -> bool {
+    let domain = domain.trim_start_matches("*.").to_lowercase();
+    host == domain || host.ends_with(&format!(".{domain}"))
+}
+
+impl AutomodTrigger {
+    /// Ensure this trigger can be evaluated
+    pub fn validate(&self) -> Result<()> {
+        let entries: Vec<&String> = match self {
+            AutomodTrigger::Keyword { keywords } => keywords.iter().collect(),
+            AutomodTrigger::Regex { patterns } => patterns.iter().collect(),
+            AutomodTrigger::Link { allow, deny } => allow.iter().chain(deny.iter()).collect(),
+            AutomodTrigger::MentionSpam { limit } | AutomodTrigger::Duplicate { limit, .. } => {
+                if *limit == 0 {
+                    return Err(Error::InvalidOperation);
+                }
+
+                vec![]
+            }
+        };
+
+        if entries.len() > MAX_TRIGGER_ENTRIES
+            || entries
+                .iter()
+                .any(|entry| entry.is_empty() || entry.len() > MAX_TRIGGER_ENTRY_LENGTH)
+        {
+            return Err(Error::InvalidOperation);
+        }
+
+        Ok(())
+    }
+
+    /// Check whether a message sets off this trigger
+    ///
+    /// Regex triggers are checked against the given compiled patterns.
+    async fn matches(&self, db: &Database, message: &Message, compiled: &[Regex]) -> Result<bool> {
+        let content = message.content.as_deref().unwrap_or_default();
+
+        Ok(match self {
+            AutomodTrigger::Keyword { keywords } => {
+                let content = content.to_lowercase();
+                keywords
+                    .iter()
+                    .any(|keyword| content.contains(&keyword.to_lowercase()))
+            }
+            AutomodTrigger::Regex { .. } => {
+                compiled.iter().any(|pattern| pattern.is_match(content))
+            }
+            AutomodTrigger::Link { allow, deny } => RE_LINK
+                .captures_iter(content)
+                .filter_map(|capture| capture.get(1))
+                .map(|host| host.as_str().to_lowercase())
+                .any(|host| {
+                    deny.iter().any(|domain| matches_domain(&host, domain))
+                        || (!allow.is_empty()
+                            && !allow.iter().any(|domain| matches_domain(&host, domain)))
+                }),
+            AutomodTrigger::MentionSpam { limit } => {
+                message.mentions.as_ref().map_or(0, Vec::len) > *limit
+            }
+            AutomodTrigger::Duplicate { limit, window } => {
+                if content.is_empty() {
+                    return Ok(false);
+                }
+
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_err(|_| Error::InternalError)?
+                    .as_millis() as i64;
+                let since = now - (*window as i64).saturating_mul(1000);
+
+                db.fetch_messages(
+                    &message.channel,
+                    Some(DUPLICATE_SCAN_LIMIT),
+                    None,
+                    None,
+                    Some(MessageSort::Latest),
+                    None,
+                )
+                .await?
+                .into_iter()
+                .filter(|previous| {
+                    previous.id != message.id
+                        && previous.author == message.author
+                        && previous.content.as_deref() == Some(content)
+                        && Ulid::from_string(&previous.id)
+                            .map(|id| id.datetime().timestamp_millis() >= since)
+                            .unwrap_or_default()
+                })
+                .count()
+                    >= *limit
+            }
+        })
+    }
+}
+
+impl AutomodRule {
+    /// Ensure this rule is valid for the given server
+    ///
+    /// Regex patterns are compiled here so that invalid ones are rejected up front.
+    pub fn validate(&self, server: &Server) -> Result<()> {
+        self.trigger.validate()?;
+        self.compiled_patterns()?;
+
+        if let AutomodAction::Alert { channel } = &self.action {
+            if !server.channels.contains(channel) {
+                return Err(Error::UnknownChannel);
+            }
+        }
+
+        if self
+            .exempt_roles
+            .iter()
+            .any(|role| !server.roles.contains_key(role))
+        {
+            return Err(Error::InvalidRole);
+        }
+
+        if self
+            .exempt_channels
+            .iter()
+            .any(|channel| !server.channels.contains(channel))
+        {
+            return Err(Error::UnknownChannel);
+        }
+
+        Ok(())
+    }
+
+    /// Get the compiled patterns of this rule's regex trigger
+    ///
+    /// Patterns are compiled once per revision of the rule and cached after that.
+    fn compiled_patterns(&self) -> Result<Arc<Vec<Regex>>> {
+        let patterns = match &self.trigger {
+            AutomodTrigger::Regex { patterns } => patterns,
+            _ => return Ok(Arc::default()),
+        };
+
+        if let Some((revision, compiled)) = COMPILED_PATTERNS.lock().unwrap().get(&self.id) {
+            if *revision == self.revision {
+                return Ok(compiled.clone());
+            }
+        }
+
+        let compiled = Arc::new(compile(patterns)?);
+        COMPILED_PATTERNS
+            .lock()
+            .unwrap()
+            .insert(self.id.clone(), (self.revision, compiled.clone()));
+
+        Ok(compiled)
+    }
+
+    /// Check whether a message sets off this rule's trigger
+    pub async fn matches(&self, db: &Database, message: &Message) -> Result<bool> {
+        let compiled = self.compiled_patterns()?;
+        self.trigger.matches(db, message, &compiled).await
+    }
+
+    /// Delete this rule
+    pub async fn delete(&self, db: &Database) -> Result<()> {
+        db.delete_automod_rule(&self.id).await?;
+        COMPILED_PATTERNS.lock().unwrap().remove(&self.id);
+        Ok(())
+    }
+
+    /// Check whether this rule applies to a message in the given channel by a member with the given roles
+    pub fn applies_to(&self, channel: &str, roles: &[String]) -> bool {
+        self.enabled
+            && !self.exempt_channels.iter().any(|id| id == channel)
+            && !self.exempt_roles.iter().any(|id| roles.contains(id))
+    }
+}
+
+impl Message {
+    /// Run the server's automod rules against this message
+    ///
+    /// Timeouts and alerts are carried out immediately, blocked messages produce an error.
+    /// Returns whether the message should be deleted after it has been sent.
+    pub async fn run_automod(
+        &self,
+        db: &Database,
+        channel: &Channel,
+        permissions: &mut PermissionCalculator<'_>,
+    ) -> Result<bool> {
+        let server = match channel.server_id() {
+            Some(server) => server.to_string(),
+            None => return Ok(false),
+        };
+
+        if permissions
+            .has_permission(db, Permission::ManageMessages)
+            .await?
+        {
+            return Ok(false);
+        }
+
+        let rules = db.fetch_automod_rules(&server).await?;
+        if rules.iter().all(|rule| !rule.enabled) {
+            return Ok(false);
+        }
+
+        let mut member = db.fetch_member(&server, &self.author).await?;
+        let mut blocked_by = None;
+        let mut delete = false;
+
+        for rule in rules {
+            if !rule.applies_to(channel.id(), &member.roles) || !rule.matches(db, self).await? {
+                continue;
+            }
+
+            match rule.action {
+                AutomodAction::Block => {
+                    blocked_by.get_or_insert(rule.id);
+                }
+                AutomodAction::Delete => delete = true,
+                AutomodAction::Timeout { duration } => {
+                    let until = Timestamp::from(SystemTime::now() + Duration::from_secs(duration));
+                    if member.timeout.map_or(true, |current| *current < *until) {
+                        member
+                            .update(
+                                db,
+                                PartialMember {
+                                    timeout: Some(until),
+                                    ..Default::default()
+                                },
+                                vec![],
+                            )
+                            .await?;
+                    }
+                }
+                AutomodAction::Alert { channel: target } => {
+                    // Alert channel may have been deleted since the rule was set up.
+                    let target = match db.fetch_channel(&target).await {
+                        Ok(target) => target,
+                        Err(_) => continue,
+                    };
+
+                    SystemMessage::AutomodTriggered {
+                        rule: rule.id,
+                        user: self.author.clone(),
+                        channel: channel.id().to_string(),
+                    }
+                    .into_message(target.id().to_string())
+                    .create(db, &target, None)
+                    .await
+                    .ok();
+                }
+            }
+        }
+
+        if let Some(rule) = blocked_by {
+            return Err(Error::BlockedByAutomod { rule });
+        }
+
+        Ok(delete)
+    }
+}
@@ -146,6 +146,7 @@ impl Server {
             FieldsServer::SystemMessages => self.system_messages = None,
             FieldsServer::Icon => self.icon = None,
             FieldsServer::Banner => self.banner = None,
+            FieldsServer::MessageHistoryRetention => self.message_history_retention = None,
         }
     }
 
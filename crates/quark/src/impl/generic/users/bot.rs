@@ -1,5 +1,7 @@
 use nanoid::nanoid;
 
+use super::bot_command::resolve_interactions_url;
+
 use crate::{
     models::{bot::FieldsBot, Bot},
     Database, Result,
@@ -16,9 +18,15 @@ impl Bot {
         }
     }
 
+    /// Check that an interactions URL is safe for us to send requests to
+    pub async fn validate_interactions_url(url: &str) -> Result<()> {
+        resolve_interactions_url(url).await.map(|_| ())
+    }
+
     /// Delete this bot
     pub async fn delete(&self, db: &Database) -> Result<()> {
         db.fetch_user(&self.id).await?.mark_deleted(db).await?;
+        db.delete_commands_for_bot(&self.id).await?;
         db.delete_bot(&self.id).await
     }
 }
@@ -0,0 +1,307 @@
+use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use async_std::net::ToSocketAddrs;
+use hmac::{Hmac, Mac};
+use indexmap::IndexMap;
+use reqwest::{redirect::Policy, Url};
+use sha2::Sha256;
+
+use crate::{
+    models::{
+        bot_command::{CommandInteraction, CommandOptionType, CommandOptionValue, CommandResponse},
+        Bot, BotCommand, Channel,
+    },
+    Database, Error, Result,
+};
+
+/// How long to wait for a bot to respond to an interaction
+static INTERACTION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest response body we will read from a bot, in bytes
+const MAX_RESPONSE_SIZE: usize = 64 * 1024;
+
+/// Check whether an address is loopback, private or otherwise not publicly routable
+fn is_internal(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => {
+            let [a, b, c, _] = ip.octets();
+            ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_private()
+                || ip.is_link_local()
+                || ip.is_broadcast()
+                || ip.is_documentation()
+                || ip.is_multicast()
+                // 0.0.0.0/8, 100.64.0.0/10, 192.0.0.0/24, 198.18.0.0/15 and 240.0.0.0/4
+                || a == 0
+                || (a == 100 && (64..128).contains(&b))
+                || (a == 192 && b == 0 && c == 0)
+                || (a == 198 && (18..20).contains(&b))
+                || a >= 240
+        }
+        IpAddr::V6(ip) => {
+            let [a, ..] = ip.segments();
+            ip.is_unspecified()
+                || ip.is_loopback()
+                || ip.is_multicast()
+                // fc00::/7 and fe80::/10
+                || (a & 0xfe00) == 0xfc00
+                || (a & 0xffc0) == 0xfe80
+                || ip.to_ipv4().map_or(false, |ip| is_internal(IpAddr::V4(ip)))
+        }
+    }
+}
+
+/// Sign an interaction body by taking a HMAC-SHA256 of the timestamp followed by the body
+fn sign(token: &str, timestamp: &str, body: &str) -> Result<String> {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(token.as_bytes()).map_err(|_| Error::InternalError)?;
+    mac.update(timestamp.as_bytes());
+    mac.update(body.as_bytes());
+    Ok(hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Parse an interactions URL and resolve the addresses it points at
+///
+/// Only HTTPS URLs which resolve exclusively to public addresses are accepted.
+pub(crate) async fn resolve_interactions_url(url: &str) -> Result<(Url, Vec<SocketAddr>)> {
+    let url = Url::parse(url).map_err(|_| Error::InvalidInteractionsURL)?;
+    if url.scheme() != "https" {
+        return Err(Error::InvalidInteractionsURL);
+    }
+
+    let host = url.host_str().ok_or(Error::InvalidInteractionsURL)?;
+    let port = url
+        .port_or_known_default()
+        .ok_or(Error::InvalidInteractionsURL)?;
+
+    let addrs = (host.trim_start_matches('[').trim_end_matches(']'), port)
+        .to_socket_addrs()
+        .await
+        .map_err(|_| Error::InvalidInteractionsURL)?
+        .collect::<Vec<SocketAddr>>();
+
+    if addrs.is_empty() || addrs.iter().any(|addr| is_internal(addr.ip())) {
+        return Err(Error::InvalidInteractionsURL);
+    }
+
+    Ok((url, addrs))
+}
+
+impl CommandOptionType {
+    /// Check whether a given value is acceptable for this option type
+    pub fn accepts(&self, value: &CommandOptionValue) -> bool {
+        matches!(
+            (self, value),
+            (
+                CommandOptionType::String | CommandOptionType::User | CommandOptionType::Channel,
+                CommandOptionValue::String(_)
+            ) | (CommandOptionType::Integer, CommandOptionValue::Integer(_))
+                | (CommandOptionType::Boolean, CommandOptionValue::Boolean(_))
+        )
+    }
+}
+
+impl BotCommand {
+    /// Create a command
+    pub async fn create(&self, db: &Database) -> Result<()> {
+        db.insert_command(self).await
+    }
+
+    /// Delete a command
+    pub async fn delete(self, db: &Database) -> Result<()> {
+        db.delete_command(&self.id).await
+    }
+
+    /// Check whether this command can be invoked in a given channel
+    pub fn is_usable_in(&self, channel: &Channel) -> bool {
+        match (&self.server_id, channel) {
+            (None, _) => true,
+            (
+                Some(id),
+                Channel::TextChannel { server, .. }
+                | Channel::VoiceChannel { server, .. }
+                | Channel::Thread { server, .. },
+            ) => id == server,
+            _ => false,
+        }
+    }
+
+    /// Check given option values against the options this command accepts
+    pub fn validate_options(&self, values: &IndexMap<String, CommandOptionValue>) -> Result<()> {
+        if let Some(name) = values
+            .keys()
+            .find(|name| !self.options.iter().any(|option| &option.name == *name))
+        {
+            return Err(Error::InvalidCommandOption {
+                option: name.to_string(),
+            });
+        }
+
+        for option in &self.options {
+            let valid = match values.get(&option.name) {
+                Some(value) => option.option_type.accepts(value),
+                None => !option.required,
+            };
+
+            if !valid {
+                return Err(Error::InvalidCommandOption {
+                    option: option.name.to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl CommandInteraction {
+    /// Sign and send this interaction to a bot, returning its response
+    ///
+    /// Requests are signed by taking a HMAC-SHA256 of the timestamp followed by
+    /// the request body, keyed with the bot's token.
+    pub async fn dispatch(&self, bot: &Bot) -> Result<CommandResponse> {
+        let url = bot
+            .interactions_url
+            .as_ref()
+            .ok_or(Error::InvalidOperation)?;
+
+        // Resolve again in case the host changed where it points since it was set,
+        // then pin the connection to the address we checked.
+        let (url, addrs) = resolve_interactions_url(url)
+            .await
+            .map_err(|_| Error::InteractionFailed)?;
+
+        let mut client = reqwest::Client::builder().redirect(Policy::none());
+        if let Some(domain) = url.domain() {
+            client = client.resolve(domain, addrs[0]);
+        }
+
+        let client = client.build().map_err(|_| Error::InternalError)?;
+
+        let body = serde_json::to_string(self).map_err(|_| Error::InternalError)?;
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| Error::InternalError)?
+            .as_secs()
+            .to_string();
+
+        let signature = sign(&bot.token, &timestamp, &body)?;
+
+        let mut response = client
+            .post(url)
+            .timeout(INTERACTION_TIMEOUT)
+            .header("Content-Type", "application/json")
+            .header("X-Revolt-Timestamp", timestamp)
+            .header("X-Revolt-Signature", signature)
+            .body(body)
+            .send()
+            .await
+            .map_err(|_| Error::InteractionFailed)?;
+
+        if !response.status().is_success() {
+            return Err(Error::InteractionFailed);
+        }
+
+        if response
+            .content_length()
+            .map_or(false, |length| length > MAX_RESPONSE_SIZE as u64)
+        {
+            return Err(Error::InteractionFailed);
+        }
+
+        // Bodies may be chunked, so keep checking the size as we read.
+        let mut buffer = Vec::new();
+        while let Some(chunk) = response
+            .chunk()
+            .await
+            .map_err(|_| Error::InteractionFailed)?
+        {
+            if buffer.len() + chunk.len() > MAX_RESPONSE_SIZE {
+                return Err(Error::InteractionFailed);
+            }
+
+            buffer.extend_from_slice(&chunk);
+        }
+
+        serde_json::from_slice(&buffer).map_err(|_| Error::InteractionFailed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn internal_addresses() {
+        for ip in [
+            "0.0.0.0",
+            "10.0.0.1",
+            "100.64.0.1",
+            "127.0.0.1",
+            "169.254.169.254",
+            "172.16.0.1",
+            "192.168.1.1",
+            "198.18.0.1",
+            "255.255.255.255",
+            "::",
+            "::1",
+            "fc00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::ffff:10.0.0.1",
+        ] {
+            assert!(is_internal(ip.parse().unwrap()), "{ip} should be internal");
+        }
+
+        for ip in [
+            "1.1.1.1",
+            "8.8.8.8",
+            "2606:4700:4700::1111",
+            "::ffff:1.1.1.1",
+        ] {
+            assert!(!is_internal(ip.parse().unwrap()), "{ip} should be public");
+        }
+    }
+
+    #[async_std::test]
+    async fn interactions_url() {
+        for url in [
+            "not a url",
+            "http://1.1.1.1/interactions",
+            "https://127.0.0.1/interactions",
+            "https://[::1]/interactions",
+            "https://[::ffff:192.168.0.1]/interactions",
+            "https://10.0.0.1:8443/interactions",
+        ] {
+            assert!(
+                matches!(
+                    resolve_interactions_url(url).await,
+                    Err(Error::InvalidInteractionsURL)
+                ),
+                "{url} should be rejected"
+            );
+        }
+
+        let (url, addrs) = resolve_interactions_url("https://1.1.1.1/interactions")
+            .await
+            .unwrap();
+        assert_eq!(url.path(), "/interactions");
+        assert_eq!(addrs, vec!["1.1.1.1:443".parse().unwrap()]);
+    }
+
+    #[test]
+    fn signature() {
+        assert_eq!(
+            sign("token", "1700000000", r#"{"id":"01"}"#).unwrap(),
+            "754de2af8c58711bda3877061c5a87c72221a96b20dfb5b1c57e72fcf5c0fc7d"
+        );
+
+        // Changing any part of the input changes the signature.
+        let signature = sign("token", "1700000000", "{}").unwrap();
+        assert_ne!(signature, sign("token", "1700000001", "{}").unwrap());
+        assert_ne!(signature, sign("other", "1700000000", "{}").unwrap());
+        assert_ne!(signature, sign("token", "1700000000", "[]").unwrap());
+    }
+}
@@ -1,10 +1,19 @@
-mod dummy;
+#[cfg(any(test, feature = "conformance"))]
+pub mod conformance;
+
 mod generic;
 mod mongo;
+mod reference;
+
+#[cfg(feature = "postgres")]
+mod postgres;
 
 #[cfg(feature = "rocket_impl")]
 mod rocket;
 
 pub use self::generic::users::user_settings::UserSettingsImpl;
-pub use dummy::DummyDb;
 pub use mongo::MongoDb;
+pub use reference::ReferenceDb;
+
+#[cfg(feature = "postgres")]
+pub use postgres::PostgresDb;
@@ -0,0 +1,128 @@
+use bson::Document;
+use mongodb::options::{FindOneAndUpdateOptions, FindOptions, ReturnDocument};
+
+use crate::models::Job;
+use crate::{AbstractJobQueue, Error, Result};
+
+use super::super::MongoDb;
+
+static COL: &str = "jobs";
+
+impl MongoDb {
+    async fn set_job_fields(&self, id: &str, fields: Document) -> Result<()> {
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id
+                },
+                doc! {
+                    "$set": fields
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "update_one",
+                with: "job",
+            })
+    }
+}
+
+#[async_trait]
+impl AbstractJobQueue for MongoDb {
+    async fn insert_job(&self, job: &Job) -> Result<()> {
+        self.insert_one(COL, job).await.map(|_| ())
+    }
+
+    async fn claim_job(&self, queue: &str, now: i64, available_at: i64) -> Result<Option<Job>> {
+        self.col::<Job>(COL)
+            .find_one_and_update(
+                doc! {
+                    "queue": queue,
+                    "dead": false,
+                    "available_at": {
+                        "$lte": now
+                    }
+                },
+                doc! {
+                    "$set": {
+                        "available_at": available_at
+                    },
+                    "$inc": {
+                        "attempts": 1_i32
+                    }
+                },
+                FindOneAndUpdateOptions::builder()
+                    .sort(doc! {
+                        "available_at": 1_i32
+                    })
+                    .return_document(ReturnDocument::After)
+                    .build(),
+            )
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "find_one_and_update",
+                with: "job",
+            })
+    }
+
+    async fn complete_job(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(COL, id).await.map(|_| ())
+    }
+
+    async fn retry_job(&self, id: &str, available_at: i64, error: &str) -> Result<()> {
+        self.set_job_fields(
+            id,
+            doc! {
+                "available_at": available_at,
+                "last_error": error
+            },
+        )
+        .await
+    }
+
+    async fn bury_job(&self, id: &str, error: &str) -> Result<()> {
+        self.set_job_fields(
+            id,
+            doc! {
+                "dead": true,
+                "last_error": error
+            },
+        )
+        .await
+    }
+
+    async fn fetch_dead_jobs(&self, queue: &str) -> Result<Vec<Job>> {
+        self.find_with_options(
+            COL,
+            doc! {
+                "queue": queue,
+                "dead": true
+            },
+            FindOptions::builder()
+                .sort(doc! {
+                    "_id": 1_i32
+                })
+                .build(),
+        )
+        .await
+    }
+
+    async fn count_jobs(&self, queue: &str) -> Result<usize> {
+        self.col::<Document>(COL)
+            .count_documents(
+                doc! {
+                    "queue": queue,
+                    "dead": false
+                },
+                None,
+            )
+            .await
+            .map(|c| c as usize)
+            .map_err(|_| Error::DatabaseError {
+                operation: "count_documents",
+                with: "job",
+            })
+    }
+}
@@ -37,6 +37,26 @@ pub async fn create_database(db: &MongoDb) {
         .await
         .expect("Failed to create server_bans collection.");
 
+    db.create_collection("audit_log", None)
+        .await
+        .expect("Failed to create audit_log collection.");
+
+    db.create_collection("automod_rules", None)
+        .await
+        .expect("Failed to create automod_rules collection.");
+
+    db.create_collection("jobs", None)
+        .await
+        .expect("Failed to create jobs collection.");
+
+    db.create_collection("message_revisions", None)
+        .await
+        .expect("Failed to create message_revisions collection.");
+
+    db.create_collection("deleted_messages", None)
+        .await
+        .expect("Failed to create deleted_messages collection.");
+
     db.create_collection("channel_invites", None)
         .await
         .expect("Failed to create channel_invites collection.");
@@ -61,6 +81,14 @@ pub async fn create_database(db: &MongoDb) {
         .await
         .expect("Failed to create bots collection.");
 
+    db.create_collection("bot_commands", None)
+        .await
+        .expect("Failed to create bot_commands collection.");
+
+    db.create_collection("webhooks", None)
+        .await
+        .expect("Failed to create webhooks collection.");
+
     db.create_collection(
         "pubsub",
         CreateCollectionOptions::builder()
@@ -171,6 +199,142 @@ pub async fn create_database(db: &MongoDb) {
     .await
     .expect("Failed to create server_members index.");
 
+    db.run_command(
+        doc! {
+            "createIndexes": "webhooks",
+            "indexes": [
+                {
+                    "key": {
+                        "channel_id": 1_i32,
+                    },
+                    "name": "channel_id"
+                }
+            ]
+        },
+        None,
+    )
+    .await
+    .expect("Failed to create webhooks index.");
+
+    db.run_command(
+        doc! {
+            "createIndexes": "bot_commands",
+            "indexes": [
+                {
+                    "key": {
+                        "bot_id": 1_i32,
+                    },
+                    "name": "bot_id"
+                }
+            ]
+        },
+        None,
+    )
+    .await
+    .expect("Failed to create bot_commands index.");
+
+    db.run_command(
+        doc! {
+            "createIndexes": "audit_log",
+            "indexes": [
+                {
+                    "key": {
+                        "server": 1_i32,
+                        "_id": -1_i32
+                    },
+                    "name": "server"
+                }
+            ]
+        },
+        None,
+    )
+    .await
+    .expect("Failed to create audit_log index.");
+
+    db.run_command(
+        doc! {
+            "createIndexes": "automod_rules",
+            "indexes": [
+                {
+                    "key": {
+                        "server": 1_i32
+                    },
+                    "name": "server"
+                }
+            ]
+        },
+        None,
+    )
+    .await
+    .expect("Failed to create automod_rules index.");
+
+    db.run_command(
+        doc! {
+            "createIndexes": "jobs",
+            "indexes": [
+                {
+                    "key": {
+                        "queue": 1_i32,
+                        "dead": 1_i32,
+                        "available_at": 1_i32
+                    },
+                    "name": "queue"
+                }
+            ]
+        },
+        None,
+    )
+    .await
+    .expect("Failed to create jobs index.");
+
+    db.run_command(
+        doc! {
+            "createIndexes": "message_revisions",
+            "indexes": [
+                {
+                    "key": {
+                        "message": 1_i32,
+                        "_id": 1_i32
+                    },
+                    "name": "message"
+                },
+                {
+                    "key": {
+                        "channel": 1_i32
+                    },
+                    "name": "channel"
+                }
+            ]
+        },
+        None,
+    )
+    .await
+    .expect("Failed to create message_revisions index.");
+
+    db.run_command(
+        doc! {
+            "createIndexes": "deleted_messages",
+            "indexes": [
+                {
+                    "key": {
+                        "channel": 1_i32,
+                        "deleted_at": -1_i32
+                    },
+                    "name": "channel"
+                },
+                {
+                    "key": {
+                        "deleted_at": 1_i32
+                    },
+                    "name": "deleted_at"
+                }
+            ]
+        },
+        None,
+    )
+    .await
+    .expect("Failed to create deleted_messages index.");
+
     db.collection("migrations")
         .insert_one(
             doc! {
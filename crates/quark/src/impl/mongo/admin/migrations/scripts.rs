@@ -16,7 +16,7 @@ struct MigrationInfo {
     revision: i32,
 }
 
-pub const LATEST_REVISION: i32 = 18;
+pub const LATEST_REVISION: i32 = 25;
 
 pub async fn migrate_database(db: &MongoDb) {
     let migrations = db.col::<Document>("migrations");
@@ -661,6 +661,212 @@ pub async fn run_migrations(db: &MongoDb, revision: i32) -> i32 {
             .expect("Failed to update server members.");
     }
 
+    if revision <= 18 {
+        info!("Running migration [revision 18 / 18-10-2026]: Add `webhooks` collection.");
+
+        db.db()
+            .create_collection("webhooks", None)
+            .await
+            .expect("Failed to create webhooks collection.");
+
+        db.db()
+            .run_command(
+                doc! {
+                    "createIndexes": "webhooks",
+                    "indexes": [
+                        {
+                            "key": {
+                                "channel_id": 1_i32,
+                            },
+                            "name": "channel_id"
+                        }
+                    ]
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create webhook channel index.");
+    }
+
+    if revision <= 19 {
+        info!("Running migration [revision 19 / 18-10-2026]: Add `bot_commands` collection.");
+
+        db.db()
+            .create_collection("bot_commands", None)
+            .await
+            .expect("Failed to create bot_commands collection.");
+
+        db.db()
+            .run_command(
+                doc! {
+                    "createIndexes": "bot_commands",
+                    "indexes": [
+                        {
+                            "key": {
+                                "bot_id": 1_i32,
+                            },
+                            "name": "bot_id"
+                        }
+                    ]
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create bot_commands bot index.");
+    }
+
+    if revision <= 20 {
+        info!("Running migration [revision 20 / 18-10-2026]: Add `audit_log` collection.");
+
+        db.db()
+            .create_collection("audit_log", None)
+            .await
+            .expect("Failed to create audit_log collection.");
+
+        db.db()
+            .run_command(
+                doc! {
+                    "createIndexes": "audit_log",
+                    "indexes": [
+                        {
+                            "key": {
+                                "server": 1_i32,
+                                "_id": -1_i32
+                            },
+                            "name": "server"
+                        }
+                    ]
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create audit_log server index.");
+    }
+
+    if revision <= 21 {
+        info!("Running migration [revision 21 / 18-10-2026]: Add `automod_rules` collection.");
+
+        db.db()
+            .create_collection("automod_rules", None)
+            .await
+            .expect("Failed to create automod_rules collection.");
+
+        db.db()
+            .run_command(
+                doc! {
+                    "createIndexes": "automod_rules",
+                    "indexes": [
+                        {
+                            "key": {
+                                "server": 1_i32
+                            },
+                            "name": "server"
+                        }
+                    ]
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create automod_rules server index.");
+    }
+
+    if revision <= 22 {
+        info!("Running migration [revision 22 / 18-10-2026]: Add `jobs` collection.");
+
+        db.db()
+            .create_collection("jobs", None)
+            .await
+            .expect("Failed to create jobs collection.");
+
+        db.db()
+            .run_command(
+                doc! {
+                    "createIndexes": "jobs",
+                    "indexes": [
+                        {
+                            "key": {
+                                "queue": 1_i32,
+                                "dead": 1_i32,
+                                "available_at": 1_i32
+                            },
+                            "name": "queue"
+                        }
+                    ]
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create jobs queue index.");
+    }
+
+    if revision <= 23 {
+        info!("Running migration [revision 23 / 18-10-2026]: Add `message_revisions` collection.");
+
+        db.db()
+            .create_collection("message_revisions", None)
+            .await
+            .expect("Failed to create message_revisions collection.");
+
+        db.db()
+            .run_command(
+                doc! {
+                    "createIndexes": "message_revisions",
+                    "indexes": [
+                        {
+                            "key": {
+                                "message": 1_i32,
+                                "_id": 1_i32
+                            },
+                            "name": "message"
+                        },
+                        {
+                            "key": {
+                                "channel": 1_i32
+                            },
+                            "name": "channel"
+                        }
+                    ]
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create message_revisions indexes.");
+    }
+
+    if revision <= 24 {
+        info!("Running migration [revision 24 / 18-10-2026]: Add `deleted_messages` collection.");
+
+        db.db()
+            .create_collection("deleted_messages", None)
+            .await
+            .expect("Failed to create deleted_messages collection.");
+
+        db.db()
+            .run_command(
+                doc! {
+                    "createIndexes": "deleted_messages",
+                    "indexes": [
+                        {
+                            "key": {
+                                "channel": 1_i32,
+                                "deleted_at": -1_i32
+                            },
+                            "name": "channel"
+                        },
+                        {
+                            "key": {
+                                "deleted_at": 1_i32
+                            },
+                            "name": "deleted_at"
+                        }
+                    ]
+                },
+                None,
+            )
+            .await
+            .expect("Failed to create deleted_messages indexes.");
+    }
+
     // Need to migrate fields on attachments, change `user_id`, `object_id`, etc to `parent`.
 
     // Reminder to update LATEST_REVISION when adding new migrations.
@@ -36,6 +36,20 @@ impl MongoDb {
             .map_err(|_| Error::DatabaseError {
                 operation: "delete_many",
                 with: "channel_unreads",
+            })?;
+
+        // Delete all webhooks on these channels.
+        self.col::<Document>("webhooks")
+            .delete_many(
+                doc! {
+                    "channel_id": &id
+                },
+                None,
+            )
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "webhooks",
             })
             .map(|_| ())
 
@@ -82,8 +96,70 @@ impl AbstractChannel for MongoDb {
         .map(|_| ())
     }
 
+    async fn update_last_message_id(
+        &self,
+        id: &str,
+        message_id: &str,
+        activate: bool,
+    ) -> Result<()> {
+        let map_err = |_| Error::DatabaseError {
+            operation: "update_one",
+            with: "channel",
+        };
+
+        if activate {
+            self.col::<Document>(COL)
+                .update_one(
+                    doc! {
+                        "_id": id,
+                        "channel_type": "DirectMessage"
+                    },
+                    doc! {
+                        "$set": {
+                            "active": true
+                        }
+                    },
+                    None,
+                )
+                .await
+                .map_err(map_err)?;
+        }
+
+        // Only move forward, ids are ordered by time.
+        self.col::<Document>(COL)
+            .update_one(
+                doc! {
+                    "_id": id,
+                    "$or": [
+                        {
+                            "last_message_id": null
+                        },
+                        {
+                            "last_message_id": {
+                                "$lt": message_id
+                            }
+                        }
+                    ]
+                },
+                doc! {
+                    "$set": {
+                        "last_message_id": message_id
+                    }
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(map_err)
+    }
+
     async fn delete_channel(&self, channel: &Channel) -> Result<()> {
         let id = channel.id().to_string();
+
+        // Delete threads started in this channel.
+        for thread in self.fetch_threads(&[id.clone()]).await? {
+            self.delete_channel(&thread).await?;
+        }
         let server_id = match channel {
             Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } => {
                 Some(server)
@@ -91,7 +167,7 @@ impl AbstractChannel for MongoDb {
             _ => None,
         };
 
-        // Delete invites and unreads.
+        // Delete invites, unreads and webhooks.
         self.delete_associated_channel_objects(Bson::String(id.to_string()))
             .await?;
 
@@ -167,6 +243,19 @@ impl AbstractChannel for MongoDb {
         self.delete_one_by_id(COL, &id).await.map(|_| ())
     }
 
+    async fn fetch_threads(&self, parents: &[String]) -> Result<Vec<Channel>> {
+        self.find(
+            COL,
+            doc! {
+                "channel_type": "Thread",
+                "parent": {
+                    "$in": parents
+                }
+            },
+        )
+        .await
+    }
+
     async fn find_direct_messages(&self, user_id: &str) -> Result<Vec<Channel>> {
         self.find(
             COL,
@@ -1,16 +1,100 @@
-use bson::{to_bson, Document};
-use futures::try_join;
-use mongodb::options::FindOptions;
+use std::time::Duration;
 
-use crate::models::message::{AppendMessage, Message, MessageSort, PartialMessage};
+use bson::{to_bson, DateTime, Document};
+use futures::{future::try_join_all, try_join};
+use mongodb::error::{ErrorKind, WriteError, WriteFailure};
+use mongodb::options::{FindOptions, UpdateOptions};
+
+use crate::models::message::{AppendMessage, Message, MessageFilter, MessageSort, PartialMessage};
+use crate::models::DeletedMessage;
 use crate::r#impl::mongo::DocumentId;
 use crate::{AbstractMessage, Error, Result};
 
 use super::super::MongoDb;
 
 static COL: &str = "messages";
+static DELETED: &str = "deleted_messages";
+static PIN_LEASES: &str = "pin_leases";
+
+/// How long a pin lease is held for at most, in milliseconds
+static PIN_LEASE_MILLIS: i64 = 5_000;
+
+/// How long to wait before trying to take a held pin lease again
+static PIN_LEASE_RETRY: Duration = Duration::from_millis(10);
+
+/// Check whether an operation failed because of a duplicate key
+fn is_duplicate_key(error: &mongodb::error::Error) -> bool {
+    matches!(
+        error.kind.as_ref(),
+        ErrorKind::Write(WriteFailure::WriteError(WriteError { code: 11000, .. }))
+    )
+}
 
 impl MongoDb {
+    /// Take the lease which serialises pins within a channel, returning its expiry
+    ///
+    /// Leases expire on their own so a crashed node can't hold a channel forever.
+    async fn acquire_pin_lease(&self, channel: &str) -> Result<i64> {
+        let map_err = |_| Error::DatabaseError {
+            operation: "update_one",
+            with: "pin_lease",
+        };
+
+        let give_up = DateTime::now().timestamp_millis() + PIN_LEASE_MILLIS;
+        loop {
+            let now = DateTime::now().timestamp_millis();
+            let expires = now + PIN_LEASE_MILLIS;
+
+            // Either create the lease or take over an expired one. If someone else
+            // holds it, the upsert collides with their document instead.
+            match self
+                .col::<Document>(PIN_LEASES)
+                .update_one(
+                    doc! {
+                        "_id": channel,
+                        "expires": {
+                            "$lt": now
+                        }
+                    },
+                    doc! {
+                        "$set": {
+                            "expires": expires
+                        }
+                    },
+                    UpdateOptions::builder().upsert(true).build(),
+                )
+                .await
+            {
+                Ok(_) => return Ok(expires),
+                Err(error) if is_duplicate_key(&error) && now < give_up => {
+                    async_std::task::sleep(PIN_LEASE_RETRY).await
+                }
+                Err(error) => return Err(map_err(error)),
+            }
+        }
+    }
+
+    /// Give up a pin lease, unless it already expired and was taken by someone else
+    async fn release_pin_lease(&self, channel: &str, expires: i64) -> Result<()> {
+        self.col::<Document>(PIN_LEASES)
+            .delete_one(
+                doc! {
+                    "_id": channel,
+                    "expires": expires
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_one",
+                with: "pin_lease",
+            })
+    }
+
+    /// Permanently delete messages matching a projection
+    ///
+    /// This includes any deleted messages which are still awaiting purge.
     pub async fn delete_bulk_messages(&self, projection: Document) -> Result<()> {
         let mut for_attachments = projection.clone();
         for_attachments.insert(
@@ -20,11 +104,56 @@ impl MongoDb {
             },
         );
 
+        let mut for_deleted_attachments = projection.clone();
+        for_deleted_attachments.insert(
+            "message.attachments",
+            doc! {
+                "$exists": 1_i32
+            },
+        );
+
         // Check if there are any attachments we need to delete.
-        let message_ids_with_attachments = self
+        let mut message_ids_with_attachments = self.find_message_ids(COL, for_attachments).await?;
+        message_ids_with_attachments.extend(
+            self.find_message_ids(DELETED, for_deleted_attachments)
+                .await?,
+        );
+
+        self.mark_message_attachments_as_deleted(message_ids_with_attachments)
+            .await?;
+
+        // Remove any stored revisions of these messages.
+        let mut for_revisions = projection.clone();
+        if let Some(ids) = for_revisions.remove("_id") {
+            for_revisions.insert("message", ids);
+        }
+
+        self.delete_revisions(for_revisions).await?;
+
+        // And then delete said messages.
+        for collection in [COL, DELETED] {
+            self.col::<Document>(collection)
+                .delete_many(projection.clone(), None)
+                .await
+                .map_err(|_| Error::DatabaseError {
+                    operation: "delete_many",
+                    with: collection,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Find the ids of messages matching a projection
+    async fn find_message_ids(
+        &self,
+        collection: &'static str,
+        projection: Document,
+    ) -> Result<Vec<String>> {
+        Ok(self
             .find_with_options::<_, DocumentId>(
-                COL,
-                for_attachments,
+                collection,
+                projection,
                 FindOptions::builder()
                     .projection(doc! { "_id": 1_i32 })
                     .build(),
@@ -32,41 +161,105 @@ impl MongoDb {
             .await?
             .into_iter()
             .map(|x| x.id)
-            .collect::<Vec<String>>();
+            .collect())
+    }
 
-        // If we found any, mark them as deleted.
-        if !message_ids_with_attachments.is_empty() {
-            self.col::<Document>("attachments")
-                .update_many(
-                    doc! {
-                        "message_id": {
-                            "$in": message_ids_with_attachments
-                        }
-                    },
-                    doc! {
-                        "$set": {
-                            "deleted": true
-                        }
-                    },
-                    None,
-                )
-                .await
-                .map_err(|_| Error::DatabaseError {
-                    operation: "update_many",
-                    with: "attachments",
-                })?;
+    /// Mark attachments on the given messages as deleted
+    async fn mark_message_attachments_as_deleted(&self, ids: Vec<String>) -> Result<()> {
+        if ids.is_empty() {
+            return Ok(());
         }
 
-        // And then delete said messages.
-        self.col::<Document>(COL)
+        self.col::<Document>("attachments")
+            .update_many(
+                doc! {
+                    "message_id": {
+                        "$in": ids
+                    }
+                },
+                doc! {
+                    "$set": {
+                        "deleted": true
+                    }
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "update_many",
+                with: "attachments",
+            })
+    }
+
+    /// Delete stored revisions of messages matching a projection
+    async fn delete_revisions(&self, projection: Document) -> Result<()> {
+        self.col::<Document>("message_revisions")
             .delete_many(projection, None)
             .await
             .map(|_| ())
             .map_err(|_| Error::DatabaseError {
                 operation: "delete_many",
-                with: "messages",
+                with: "message_revisions",
             })
     }
+
+    /// Move messages matching a projection into tombstones
+    ///
+    /// Each message is taken out of the collection before its tombstone is written,
+    /// so an edit landing in between can't be lost and nothing is tombstoned twice.
+    async fn tombstone_messages(&self, projection: Document, deleted_at: i64) -> Result<()> {
+        let ids = self.find_message_ids(COL, projection.clone()).await?;
+
+        try_join_all(ids.into_iter().map(|id| {
+            let mut filter = projection.clone();
+            filter.insert("_id", id);
+            self.tombstone_message(filter, deleted_at)
+        }))
+        .await
+        .map(|_| ())
+    }
+
+    /// Move a single message matching a filter into a tombstone, if it still exists
+    async fn tombstone_message(&self, filter: Document, deleted_at: i64) -> Result<()> {
+        let message = self
+            .col::<Message>(COL)
+            .find_one_and_delete(filter, None)
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "find_one_and_delete",
+                with: "messages",
+            })?;
+
+        let message = match message {
+            Some(message) => message,
+            // Someone else got to it first.
+            None => return Ok(()),
+        };
+
+        let tombstone = DeletedMessage {
+            id: message.id.to_string(),
+            channel: message.channel.to_string(),
+            deleted_at,
+            message,
+        };
+
+        if self
+            .col::<DeletedMessage>(DELETED)
+            .insert_one(&tombstone, None)
+            .await
+            .is_err()
+        {
+            // Put the message back rather than lose it.
+            self.insert_one(COL, &tombstone.message).await?;
+            return Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "deleted_messages",
+            });
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -125,18 +318,138 @@ impl AbstractMessage for MongoDb {
             .map(|_| ())
     }
 
-    async fn delete_message(&self, id: &str) -> Result<()> {
-        self.delete_one_by_id(COL, id).await.map(|_| ())
+    async fn delete_message(&self, id: &str, deleted_at: i64) -> Result<()> {
+        self.tombstone_messages(
+            doc! {
+                "_id": id
+            },
+            deleted_at,
+        )
+        .await
+    }
+
+    async fn delete_messages(
+        &self,
+        channel: &str,
+        ids: Vec<String>,
+        deleted_at: i64,
+    ) -> Result<()> {
+        self.tombstone_messages(
+            doc! {
+                "channel": channel,
+                "_id": {
+                    "$in": ids
+                }
+            },
+            deleted_at,
+        )
+        .await
+    }
+
+    async fn fetch_deleted_messages(
+        &self,
+        channel: &str,
+        limit: i64,
+    ) -> Result<Vec<DeletedMessage>> {
+        self.find_with_options(
+            DELETED,
+            doc! {
+                "channel": channel
+            },
+            FindOptions::builder()
+                .limit(limit)
+                .sort(doc! {
+                    "deleted_at": -1_i32,
+                    "_id": -1_i32
+                })
+                .build(),
+        )
+        .await
     }
 
-    async fn delete_messages(&self, channel: &str, ids: Vec<String>) -> Result<()> {
-        self.delete_bulk_messages(doc! {
-            "channel": channel,
-            "_id": {
-                "$in": ids
+    async fn restore_message(
+        &self,
+        channel: &str,
+        id: &str,
+        deleted_after: i64,
+    ) -> Result<Message> {
+        // Claim the tombstone first, so only one of several concurrent restores proceeds.
+        let deleted = self
+            .col::<DeletedMessage>(DELETED)
+            .find_one_and_delete(
+                doc! {
+                    "_id": id,
+                    "channel": channel,
+                    "deleted_at": {
+                        "$gte": deleted_after
+                    }
+                },
+                None,
+            )
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "find_one_and_delete",
+                with: "deleted_messages",
+            })?
+            .ok_or(Error::NotFound)?;
+
+        if let Err(err) = self.insert_one(COL, &deleted.message).await {
+            // Keep the message recoverable rather than lose it.
+            self.col::<DeletedMessage>(DELETED)
+                .insert_one(&deleted, None)
+                .await
+                .map_err(|_| Error::DatabaseError {
+                    operation: "insert_one",
+                    with: "deleted_messages",
+                })?;
+
+            return Err(err);
+        }
+
+        Ok(deleted.message)
+    }
+
+    async fn purge_deleted_messages(&self, before: i64) -> Result<()> {
+        let ids = self
+            .find_message_ids(
+                DELETED,
+                doc! {
+                    "deleted_at": {
+                        "$lt": before
+                    }
+                },
+            )
+            .await?;
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        self.mark_message_attachments_as_deleted(ids.clone())
+            .await?;
+
+        self.delete_revisions(doc! {
+            "message": {
+                "$in": &ids
             }
         })
-        .await
+        .await?;
+
+        self.col::<Document>(DELETED)
+            .delete_many(
+                doc! {
+                    "_id": {
+                        "$in": ids
+                    }
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "deleted_messages",
+            })
     }
 
     async fn fetch_messages(
@@ -214,45 +527,172 @@ impl AbstractMessage for MongoDb {
         })
     }
 
+    async fn fetch_pinned_messages(&self, channel: &str) -> Result<Vec<Message>> {
+        self.find_with_options(
+            COL,
+            doc! {
+                "channel": channel,
+                "pinned": true
+            },
+            FindOptions::builder()
+                .sort(doc! {
+                    "_id": -1_i32
+                })
+                .build(),
+        )
+        .await
+    }
+
+    async fn pin_message(&self, channel: &str, id: &str, max: usize) -> Result<()> {
+        let map_err = |_| Error::DatabaseError {
+            operation: "update_one",
+            with: "message",
+        };
+
+        // MongoDB can't constrain across documents, so serialise pins within the
+        // channel behind a short lease and check the count while holding it.
+        let lease = self.acquire_pin_lease(channel).await?;
+        let result = async {
+            let pinned = self
+                .col::<Document>(COL)
+                .count_documents(
+                    doc! {
+                        "channel": channel,
+                        "pinned": true
+                    },
+                    None,
+                )
+                .await
+                .map_err(map_err)?;
+
+            let filter = doc! {
+                "_id": id,
+                "channel": channel,
+                "pinned": {
+                    "$ne": true
+                }
+            };
+
+            if pinned as usize >= max {
+                let unpinned = self
+                    .col::<Document>(COL)
+                    .count_documents(filter, None)
+                    .await
+                    .map_err(map_err)?;
+
+                return if unpinned > 0 {
+                    Err(Error::TooManyPins { max })
+                } else {
+                    Ok(())
+                };
+            }
+
+            self.col::<Document>(COL)
+                .update_one(
+                    filter,
+                    doc! {
+                        "$set": {
+                            "pinned": true
+                        }
+                    },
+                    None,
+                )
+                .await
+                .map(|_| ())
+                .map_err(map_err)
+        }
+        .await;
+
+        self.release_pin_lease(channel, lease).await?;
+        result
+    }
+
     async fn search_messages(
         &self,
-        channel: &str,
-        query: &str,
+        channels: &[String],
+        filter: &MessageFilter,
         limit: Option<i64>,
-        before: Option<String>,
-        after: Option<String>,
         sort: MessageSort,
     ) -> Result<Vec<Message>> {
         let limit = limit.unwrap_or(50);
 
-        let mut filter = doc! {
-            "channel": channel,
-            "$text": {
-                "$search": query
+        let mut projection = doc! {
+            "channel": {
+                "$in": channels
             }
         };
 
-        if let Some(doc) = match (before, after) {
+        if let Some(query) = &filter.query {
+            projection.insert(
+                "$text",
+                doc! {
+                    "$search": query
+                },
+            );
+        }
+
+        if let Some(author) = &filter.author {
+            projection.insert("author", author);
+        }
+
+        if let Some(mention) = &filter.mention {
+            projection.insert("mentions", mention);
+        }
+
+        if filter.has_attachment {
+            projection.insert(
+                "attachments.0",
+                doc! {
+                    "$exists": true
+                },
+            );
+        }
+
+        if filter.has_embed {
+            projection.insert(
+                "embeds.0",
+                doc! {
+                    "$exists": true
+                },
+            );
+        }
+
+        if filter.has_link {
+            projection.insert(
+                "content",
+                doc! {
+                    "$regex": "https?://",
+                    "$options": "i"
+                },
+            );
+        }
+
+        if filter.pinned {
+            projection.insert("pinned", true);
+        }
+
+        if let Some(doc) = match (&filter.before, &filter.after) {
             (Some(before), Some(after)) => Some(doc! {
-                "lt": before,
-                "gt": after
+                "$lt": before,
+                "$gt": after
             }),
             (Some(before), _) => Some(doc! {
-                "lt": before
+                "$lt": before
             }),
             (_, Some(after)) => Some(doc! {
-                "gt": after
+                "$gt": after
             }),
             _ => None,
         } {
-            filter.insert("_id", doc);
+            projection.insert("_id", doc);
         }
 
+        let relevance = filter.query.is_some() && matches!(sort, MessageSort::Relevance);
         self.find_with_options(
             COL,
-            filter,
+            projection,
             FindOptions::builder()
-                .projection(if let MessageSort::Relevance = &sort {
+                .projection(if relevance {
                     doc! {
                         "score": {
                             "$meta": "textScore"
@@ -263,12 +703,12 @@ impl AbstractMessage for MongoDb {
                 })
                 .limit(limit)
                 .sort(match &sort {
-                    MessageSort::Relevance => doc! {
+                    MessageSort::Relevance if relevance => doc! {
                         "score": {
                             "$meta": "textScore"
                         }
                     },
-                    MessageSort::Latest => doc! {
+                    MessageSort::Relevance | MessageSort::Latest => doc! {
                         "_id": -1_i32
                     },
                     MessageSort::Oldest => doc! {
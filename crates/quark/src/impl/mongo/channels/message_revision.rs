@@ -0,0 +1,89 @@
+use bson::Document;
+use mongodb::options::FindOptions;
+
+use crate::models::MessageRevision;
+use crate::{AbstractMessageRevision, Error, Result};
+
+use super::super::MongoDb;
+
+static COL: &str = "message_revisions";
+
+#[async_trait]
+impl AbstractMessageRevision for MongoDb {
+    async fn insert_message_revision(&self, revision: &MessageRevision) -> Result<()> {
+        self.insert_one(COL, revision).await.map(|_| ())
+    }
+
+    async fn fetch_message_revisions(&self, message: &str) -> Result<Vec<MessageRevision>> {
+        self.find_with_options(
+            COL,
+            doc! {
+                "message": message
+            },
+            FindOptions::builder()
+                .sort(doc! {
+                    "_id": 1_i32
+                })
+                .build(),
+        )
+        .await
+    }
+
+    async fn delete_message_revisions_before(&self, message: &str, before: &str) -> Result<()> {
+        self.col::<Document>(COL)
+            .delete_many(
+                doc! {
+                    "message": message,
+                    "_id": {
+                        "$lt": before
+                    }
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "message_revisions",
+            })
+    }
+
+    async fn fetch_message_revision_channels(&self) -> Result<Vec<String>> {
+        self.col::<Document>(COL)
+            .distinct("channel", None, None)
+            .await
+            .map(|channels| {
+                channels
+                    .into_iter()
+                    .filter_map(|channel| channel.as_str().map(|id| id.to_string()))
+                    .collect()
+            })
+            .map_err(|_| Error::DatabaseError {
+                operation: "distinct",
+                with: "message_revisions",
+            })
+    }
+
+    async fn delete_channel_message_revisions_before(
+        &self,
+        channel: &str,
+        before: &str,
+    ) -> Result<()> {
+        self.col::<Document>(COL)
+            .delete_many(
+                doc! {
+                    "channel": channel,
+                    "_id": {
+                        "$lt": before
+                    }
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "message_revisions",
+            })
+    }
+}
@@ -0,0 +1,57 @@
+use crate::models::webhook::{FieldsWebhook, PartialWebhook, Webhook};
+use crate::r#impl::mongo::IntoDocumentPath;
+use crate::{AbstractWebhook, Result};
+
+use super::super::MongoDb;
+
+static COL: &str = "webhooks";
+
+#[async_trait]
+impl AbstractWebhook for MongoDb {
+    async fn insert_webhook(&self, webhook: &Webhook) -> Result<()> {
+        self.insert_one(COL, webhook).await.map(|_| ())
+    }
+
+    async fn fetch_webhook(&self, id: &str) -> Result<Webhook> {
+        self.find_one_by_id(COL, id).await
+    }
+
+    async fn fetch_webhooks_for_channel(&self, channel: &str) -> Result<Vec<Webhook>> {
+        self.find(
+            COL,
+            doc! {
+                "channel_id": channel
+            },
+        )
+        .await
+    }
+
+    async fn update_webhook(
+        &self,
+        id: &str,
+        partial: &PartialWebhook,
+        remove: Vec<FieldsWebhook>,
+    ) -> Result<()> {
+        self.update_one_by_id(
+            COL,
+            id,
+            partial,
+            remove.iter().map(|x| x as &dyn IntoDocumentPath).collect(),
+            None,
+        )
+        .await
+        .map(|_| ())
+    }
+
+    async fn delete_webhook(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(COL, id).await.map(|_| ())
+    }
+}
+
+impl IntoDocumentPath for FieldsWebhook {
+    fn as_path(&self) -> Option<&'static str> {
+        Some(match self {
+            FieldsWebhook::Avatar => "avatar",
+        })
+    }
+}
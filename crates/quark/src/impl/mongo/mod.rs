@@ -12,6 +12,7 @@ use serde::{Deserialize, Serialize};
 use crate::{util::manipulation::prefix_keys, AbstractDatabase, Error, Result};
 
 pub mod admin {
+    pub mod job;
     pub mod migrations;
 }
 
@@ -25,9 +26,13 @@ pub mod channels {
     pub mod channel_invite;
     pub mod channel_unread;
     pub mod message;
+    pub mod message_revision;
+    pub mod webhook;
 }
 
 pub mod servers {
+    pub mod audit_log;
+    pub mod automod_rule;
     pub mod server;
     pub mod server_ban;
     pub mod server_member;
@@ -35,6 +40,7 @@ pub mod servers {
 
 pub mod users {
     pub mod bot;
+    pub mod bot_command;
     pub mod user;
     pub mod user_settings;
 }
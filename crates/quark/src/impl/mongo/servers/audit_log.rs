@@ -0,0 +1,57 @@
+use bson::to_bson;
+use mongodb::options::FindOptions;
+
+use crate::models::audit_log::AuditLogAction;
+use crate::models::AuditLogEntry;
+use crate::{AbstractAuditLog, Error, Result};
+
+use super::super::MongoDb;
+
+static COL: &str = "audit_log";
+
+#[async_trait]
+impl AbstractAuditLog for MongoDb {
+    async fn insert_audit_log_entry(&self, entry: &AuditLogEntry) -> Result<()> {
+        self.insert_one(COL, entry).await.map(|_| ())
+    }
+
+    async fn fetch_audit_log(
+        &self,
+        server: &str,
+        limit: i64,
+        before: Option<String>,
+        actor: Option<String>,
+        action: Option<AuditLogAction>,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let mut query = doc! { "server": server };
+        if let Some(before) = before {
+            query.insert("_id", doc! { "$lt": before });
+        }
+
+        if let Some(actor) = actor {
+            query.insert("actor", actor);
+        }
+
+        if let Some(action) = action {
+            query.insert(
+                "action",
+                to_bson(&action).map_err(|_| Error::DatabaseError {
+                    operation: "to_bson",
+                    with: "audit_log",
+                })?,
+            );
+        }
+
+        self.find_with_options(
+            COL,
+            query,
+            FindOptions::builder()
+                .limit(limit)
+                .sort(doc! {
+                    "_id": -1_i32
+                })
+                .build(),
+        )
+        .await
+    }
+}
@@ -0,0 +1,37 @@
+use crate::models::automod_rule::{AutomodRule, PartialAutomodRule};
+use crate::{AbstractAutomodRule, Result};
+
+use super::super::MongoDb;
+
+static COL: &str = "automod_rules";
+
+#[async_trait]
+impl AbstractAutomodRule for MongoDb {
+    async fn insert_automod_rule(&self, rule: &AutomodRule) -> Result<()> {
+        self.insert_one(COL, rule).await.map(|_| ())
+    }
+
+    async fn fetch_automod_rule(&self, id: &str) -> Result<AutomodRule> {
+        self.find_one_by_id(COL, id).await
+    }
+
+    async fn fetch_automod_rules(&self, server: &str) -> Result<Vec<AutomodRule>> {
+        self.find(
+            COL,
+            doc! {
+                "server": server
+            },
+        )
+        .await
+    }
+
+    async fn update_automod_rule(&self, id: &str, partial: &PartialAutomodRule) -> Result<()> {
+        self.update_one_by_id(COL, id, partial, vec![], None)
+            .await
+            .map(|_| ())
+    }
+
+    async fn delete_automod_rule(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(COL, id).await.map(|_| ())
+    }
+}
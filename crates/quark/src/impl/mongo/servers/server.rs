@@ -2,7 +2,7 @@ use bson::{to_document, Bson, Document};
 
 use crate::models::server::{FieldsRole, FieldsServer, PartialRole, PartialServer, Role, Server};
 use crate::r#impl::mongo::IntoDocumentPath;
-use crate::{AbstractServer, Database, Error, Result};
+use crate::{AbstractChannel, AbstractServer, Database, Error, Result};
 
 use super::super::MongoDb;
 
@@ -66,6 +66,34 @@ impl MongoDb {
                 })?;
         }
 
+        // Delete audit log.
+        self.col::<Document>("audit_log")
+            .delete_many(
+                doc! {
+                    "server": &server.id
+                },
+                None,
+            )
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "audit_log",
+            })?;
+
+        // Delete automod rules.
+        self.col::<Document>("automod_rules")
+            .delete_many(
+                doc! {
+                    "server": &server.id
+                },
+                None,
+            )
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "automod_rules",
+            })?;
+
         // Update many attachments with parent id.
         self.delete_many_attachments(doc! {
             "object_id": &server.id
@@ -116,6 +144,10 @@ impl AbstractServer for MongoDb {
     }
 
     async fn delete_server(&self, server: &Server) -> Result<()> {
+        for thread in self.fetch_threads(&server.channels).await? {
+            self.delete_channel(&thread).await?;
+        }
+
         self.delete_associated_server_objects(server).await?;
         self.delete_one_by_id(COL, &server.id).await.map(|_| ())
     }
@@ -229,6 +261,7 @@ impl IntoDocumentPath for FieldsServer {
             FieldsServer::Description => "description",
             FieldsServer::Icon => "icon",
             FieldsServer::SystemMessages => "system_messages",
+            FieldsServer::MessageHistoryRetention => "message_history_retention",
         })
     }
 }
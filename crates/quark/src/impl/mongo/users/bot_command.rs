@@ -0,0 +1,49 @@
+use bson::Document;
+
+use crate::models::BotCommand;
+use crate::{AbstractBotCommand, Error, Result};
+
+use super::super::MongoDb;
+
+static COL: &str = "bot_commands";
+
+#[async_trait]
+impl AbstractBotCommand for MongoDb {
+    async fn insert_command(&self, command: &BotCommand) -> Result<()> {
+        self.insert_one(COL, command).await.map(|_| ())
+    }
+
+    async fn fetch_command(&self, id: &str) -> Result<BotCommand> {
+        self.find_one_by_id(COL, id).await
+    }
+
+    async fn fetch_commands(&self, bot: &str) -> Result<Vec<BotCommand>> {
+        self.find(
+            COL,
+            doc! {
+                "bot_id": bot
+            },
+        )
+        .await
+    }
+
+    async fn delete_command(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(COL, id).await.map(|_| ())
+    }
+
+    async fn delete_commands_for_bot(&self, bot: &str) -> Result<()> {
+        self.col::<Document>(COL)
+            .delete_many(
+                doc! {
+                    "bot_id": bot
+                },
+                None,
+            )
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "bot_commands",
+            })
+    }
+}
@@ -0,0 +1,107 @@
+use sqlx::types::Json;
+
+use crate::models::Job;
+use crate::{AbstractJobQueue, Error, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "jobs";
+
+#[async_trait]
+impl AbstractJobQueue for PostgresDb {
+    async fn insert_job(&self, job: &Job) -> Result<()> {
+        self.insert_one(TABLE, job).await
+    }
+
+    async fn claim_job(&self, queue: &str, now: i64, available_at: i64) -> Result<Option<Job>> {
+        sqlx::query_as::<_, Document<Job>>(
+            "UPDATE jobs SET data = data || jsonb_build_object(
+                'available_at', $3::bigint,
+                'attempts', (data->>'attempts')::int + 1
+            )
+            WHERE id = (
+                SELECT id FROM jobs WHERE queue = $1 AND NOT dead AND available_at <= $2
+                ORDER BY available_at LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING data",
+        )
+        .bind(queue)
+        .bind(now)
+        .bind(available_at)
+        .fetch_optional(&self.0)
+        .await
+        .map(|row| row.map(|(Json(job),)| job))
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_one",
+            with: "job",
+        })
+    }
+
+    async fn complete_job(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, id).await
+    }
+
+    async fn retry_job(&self, id: &str, available_at: i64, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE jobs SET data = data || jsonb_build_object(
+                'available_at', $2::bigint,
+                'last_error', $3::text
+            ) WHERE id = $1",
+        )
+        .bind(id)
+        .bind(available_at)
+        .bind(error)
+        .execute(&self.0)
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_one",
+            with: "job",
+        })
+    }
+
+    async fn bury_job(&self, id: &str, error: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE jobs SET data = data || jsonb_build_object(
+                'dead', true,
+                'last_error', $2::text
+            ) WHERE id = $1",
+        )
+        .bind(id)
+        .bind(error)
+        .execute(&self.0)
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_one",
+            with: "job",
+        })
+    }
+
+    async fn fetch_dead_jobs(&self, queue: &str) -> Result<Vec<Job>> {
+        sqlx::query_as::<_, Document<Job>>(
+            "SELECT data FROM jobs WHERE queue = $1 AND dead ORDER BY id",
+        )
+        .bind(queue)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(Json(job),)| job).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "job",
+        })
+    }
+
+    async fn count_jobs(&self, queue: &str) -> Result<usize> {
+        sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM jobs WHERE queue = $1 AND NOT dead")
+            .bind(queue)
+            .fetch_one(&self.0)
+            .await
+            .map(|(count,)| count as usize)
+            .map_err(|_| Error::DatabaseError {
+                operation: "count_documents",
+                with: "job",
+            })
+    }
+}
@@ -0,0 +1,29 @@
+use crate::{AbstractMigrations, Error, Result};
+
+use super::super::PostgresDb;
+
+mod init;
+mod scripts;
+
+#[async_trait]
+impl AbstractMigrations for PostgresDb {
+    async fn migrate_database(&self) -> Result<()> {
+        info!("Migrating the database.");
+
+        let exists: (bool,) = sqlx::query_as("SELECT to_regclass('migrations') IS NOT NULL")
+            .fetch_one(&self.0)
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "to_regclass",
+                with: "migrations",
+            })?;
+
+        if exists.0 {
+            scripts::migrate_database(self).await;
+        } else {
+            init::create_database(self).await;
+        }
+
+        Ok(())
+    }
+}
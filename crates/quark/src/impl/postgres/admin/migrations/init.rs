@@ -0,0 +1,174 @@
+use crate::r#impl::postgres::PostgresDb;
+
+use super::scripts::LATEST_REVISION;
+
+/// Tables and indexes making up the initial schema
+///
+/// Requires PostgreSQL 12 or newer for generated columns.
+static SCHEMA: &[&str] = &[
+    "CREATE TABLE migrations (
+        id INTEGER PRIMARY KEY,
+        revision INTEGER NOT NULL
+    )",
+    "CREATE TABLE users (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        username TEXT GENERATED ALWAYS AS (data->>'username') STORED
+    )",
+    "CREATE UNIQUE INDEX users_username ON users (lower(username))",
+    "CREATE INDEX users_relations ON users USING GIN ((data->'relations') jsonb_path_ops)",
+    "CREATE TABLE sessions (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        token TEXT GENERATED ALWAYS AS (data->>'token') STORED UNIQUE,
+        user_id TEXT GENERATED ALWAYS AS (data->>'user_id') STORED
+    )",
+    "CREATE TABLE user_settings (
+        id TEXT PRIMARY KEY,
+        data JSONB NOT NULL
+    )",
+    "CREATE TABLE bots (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        owner TEXT GENERATED ALWAYS AS (data->>'owner') STORED,
+        token TEXT GENERATED ALWAYS AS (data->>'token') STORED UNIQUE
+    )",
+    "CREATE INDEX bots_owner ON bots (owner)",
+    "CREATE TABLE bot_commands (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        bot TEXT GENERATED ALWAYS AS (data->>'bot_id') STORED
+    )",
+    "CREATE INDEX bot_commands_bot ON bot_commands (bot)",
+    "CREATE TABLE channels (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        channel_type TEXT GENERATED ALWAYS AS (data->>'channel_type') STORED,
+        server TEXT GENERATED ALWAYS AS (data->>'server') STORED,
+        user_id TEXT GENERATED ALWAYS AS (data->>'user') STORED
+    )",
+    "CREATE INDEX channels_server ON channels (server)",
+    "CREATE INDEX channels_user ON channels (user_id)",
+    "CREATE INDEX channels_recipients ON channels USING GIN ((data->'recipients'))",
+    "CREATE TABLE channel_invites (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        server TEXT GENERATED ALWAYS AS (data->>'server') STORED,
+        channel TEXT GENERATED ALWAYS AS (data->>'channel') STORED
+    )",
+    "CREATE INDEX channel_invites_server ON channel_invites (server)",
+    "CREATE INDEX channel_invites_channel ON channel_invites (channel)",
+    "CREATE TABLE channel_unreads (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS ((data->'_id'->>'channel') || ':' || (data->'_id'->>'user')) STORED PRIMARY KEY,
+        channel TEXT GENERATED ALWAYS AS (data->'_id'->>'channel') STORED,
+        user_id TEXT GENERATED ALWAYS AS (data->'_id'->>'user') STORED
+    )",
+    "CREATE INDEX channel_unreads_channel ON channel_unreads (channel)",
+    "CREATE INDEX channel_unreads_user ON channel_unreads (user_id)",
+    "CREATE TABLE messages (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        channel TEXT GENERATED ALWAYS AS (data->>'channel') STORED,
+        content TEXT GENERATED ALWAYS AS (data->>'content') STORED
+    )",
+    "CREATE INDEX messages_channel ON messages (channel, id)",
+    "CREATE INDEX messages_content ON messages USING GIN (to_tsvector('english', coalesce(content, '')))",
+    "CREATE TABLE message_revisions (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        message TEXT GENERATED ALWAYS AS (data->>'message') STORED,
+        channel TEXT GENERATED ALWAYS AS (data->>'channel') STORED
+    )",
+    "CREATE INDEX message_revisions_message ON message_revisions (message, id)",
+    "CREATE INDEX message_revisions_channel ON message_revisions (channel)",
+    "CREATE TABLE deleted_messages (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        channel TEXT GENERATED ALWAYS AS (data->>'channel') STORED,
+        deleted_at BIGINT GENERATED ALWAYS AS ((data->>'deleted_at')::bigint) STORED
+    )",
+    "CREATE INDEX deleted_messages_channel ON deleted_messages (channel, deleted_at DESC)",
+    "CREATE INDEX deleted_messages_deleted_at ON deleted_messages (deleted_at)",
+    "CREATE TABLE attachments (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        message_id TEXT GENERATED ALWAYS AS (data->>'message_id') STORED,
+        object_id TEXT GENERATED ALWAYS AS (data->>'object_id') STORED
+    )",
+    "CREATE INDEX attachments_message_id ON attachments (message_id)",
+    "CREATE INDEX attachments_object_id ON attachments (object_id)",
+    "CREATE TABLE emojis (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        parent_id TEXT GENERATED ALWAYS AS (data->'parent'->>'id') STORED
+    )",
+    "CREATE INDEX emojis_parent_id ON emojis (parent_id)",
+    "CREATE TABLE servers (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY
+    )",
+    "CREATE TABLE server_members (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS ((data->'_id'->>'server') || ':' || (data->'_id'->>'user')) STORED PRIMARY KEY,
+        server TEXT GENERATED ALWAYS AS (data->'_id'->>'server') STORED,
+        user_id TEXT GENERATED ALWAYS AS (data->'_id'->>'user') STORED
+    )",
+    "CREATE INDEX server_members_server ON server_members (server)",
+    "CREATE INDEX server_members_user ON server_members (user_id)",
+    "CREATE TABLE server_bans (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS ((data->'_id'->>'server') || ':' || (data->'_id'->>'user')) STORED PRIMARY KEY,
+        server TEXT GENERATED ALWAYS AS (data->'_id'->>'server') STORED
+    )",
+    "CREATE INDEX server_bans_server ON server_bans (server)",
+    "CREATE TABLE audit_log (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        server TEXT GENERATED ALWAYS AS (data->>'server') STORED
+    )",
+    "CREATE INDEX audit_log_server ON audit_log (server, id DESC)",
+    "CREATE TABLE automod_rules (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        server TEXT GENERATED ALWAYS AS (data->>'server') STORED
+    )",
+    "CREATE INDEX automod_rules_server ON automod_rules (server)",
+    "CREATE TABLE jobs (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        queue TEXT GENERATED ALWAYS AS (data->>'queue') STORED,
+        available_at BIGINT GENERATED ALWAYS AS ((data->>'available_at')::bigint) STORED,
+        dead BOOLEAN GENERATED ALWAYS AS ((data->>'dead')::boolean) STORED
+    )",
+    "CREATE INDEX jobs_queue ON jobs (queue, dead, available_at)",
+    "CREATE TABLE webhooks (
+        data JSONB NOT NULL,
+        id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+        channel TEXT GENERATED ALWAYS AS (data->>'channel_id') STORED
+    )",
+    "CREATE INDEX webhooks_channel ON webhooks (channel)",
+];
+
+pub async fn create_database(db: &PostgresDb) {
+    info!("Creating database.");
+
+    let mut tx = db.begin().await.expect("Failed to start transaction.");
+
+    for statement in SCHEMA {
+        sqlx::query(statement)
+            .execute(&mut tx)
+            .await
+            .expect("Failed to create schema.");
+    }
+
+    sqlx::query("INSERT INTO migrations (id, revision) VALUES (0, $1)")
+        .bind(LATEST_REVISION)
+        .execute(&mut tx)
+        .await
+        .expect("Failed to save migration info.");
+
+    tx.commit().await.expect("Failed to commit schema.");
+
+    info!("Created database.");
+}
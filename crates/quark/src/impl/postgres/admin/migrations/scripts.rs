@@ -0,0 +1,168 @@
+use crate::r#impl::postgres::PostgresDb;
+
+pub const LATEST_REVISION: i32 = 8;
+
+pub async fn migrate_database(db: &PostgresDb) {
+    let info: Option<(i32, i32)> = sqlx::query_as("SELECT id, revision FROM migrations LIMIT 1")
+        .fetch_optional(&db.0)
+        .await
+        .expect("Failed to fetch migration data.");
+
+    if let Some((id, revision)) = info {
+        let revision = run_migrations(db, revision).await;
+
+        sqlx::query("UPDATE migrations SET revision = $2 WHERE id = $1")
+            .bind(id)
+            .bind(revision)
+            .execute(&db.0)
+            .await
+            .expect("Failed to commit migration information.");
+
+        info!("Migration complete. Currently at revision {}.", revision);
+    } else {
+        panic!("Database was configured incorrectly, possibly because initalization failed.")
+    }
+}
+
+pub async fn run_migrations(db: &PostgresDb, revision: i32) -> i32 {
+    info!("Starting database migration.");
+
+    if revision <= 0 {
+        info!("Running migration [revision 0]: Test migration system.");
+    }
+
+    if revision <= 1 {
+        info!("Running migration [revision 1 / 18-10-2026]: Add `webhooks` table.");
+
+        for statement in [
+            "CREATE TABLE webhooks (
+                data JSONB NOT NULL,
+                id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+                channel TEXT GENERATED ALWAYS AS (data->>'channel_id') STORED
+            )",
+            "CREATE INDEX webhooks_channel ON webhooks (channel)",
+        ] {
+            sqlx::query(statement)
+                .execute(&db.0)
+                .await
+                .expect("Failed to create webhooks table.");
+        }
+    }
+
+    if revision <= 2 {
+        info!("Running migration [revision 2 / 18-10-2026]: Add `bot_commands` table.");
+
+        for statement in [
+            "CREATE TABLE bot_commands (
+                data JSONB NOT NULL,
+                id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+                bot TEXT GENERATED ALWAYS AS (data->>'bot_id') STORED
+            )",
+            "CREATE INDEX bot_commands_bot ON bot_commands (bot)",
+        ] {
+            sqlx::query(statement)
+                .execute(&db.0)
+                .await
+                .expect("Failed to create bot_commands table.");
+        }
+    }
+
+    if revision <= 3 {
+        info!("Running migration [revision 3 / 18-10-2026]: Add `audit_log` table.");
+
+        for statement in [
+            "CREATE TABLE audit_log (
+                data JSONB NOT NULL,
+                id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+                server TEXT GENERATED ALWAYS AS (data->>'server') STORED
+            )",
+            "CREATE INDEX audit_log_server ON audit_log (server, id DESC)",
+        ] {
+            sqlx::query(statement)
+                .execute(&db.0)
+                .await
+                .expect("Failed to create audit_log table.");
+        }
+    }
+
+    if revision <= 4 {
+        info!("Running migration [revision 4 / 18-10-2026]: Add `automod_rules` table.");
+
+        for statement in [
+            "CREATE TABLE automod_rules (
+                data JSONB NOT NULL,
+                id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+                server TEXT GENERATED ALWAYS AS (data->>'server') STORED
+            )",
+            "CREATE INDEX automod_rules_server ON automod_rules (server)",
+        ] {
+            sqlx::query(statement)
+                .execute(&db.0)
+                .await
+                .expect("Failed to create automod_rules table.");
+        }
+    }
+
+    if revision <= 5 {
+        info!("Running migration [revision 5 / 18-10-2026]: Add `jobs` table.");
+
+        for statement in [
+            "CREATE TABLE jobs (
+                data JSONB NOT NULL,
+                id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+                queue TEXT GENERATED ALWAYS AS (data->>'queue') STORED,
+                available_at BIGINT GENERATED ALWAYS AS ((data->>'available_at')::bigint) STORED,
+                dead BOOLEAN GENERATED ALWAYS AS ((data->>'dead')::boolean) STORED
+            )",
+            "CREATE INDEX jobs_queue ON jobs (queue, dead, available_at)",
+        ] {
+            sqlx::query(statement)
+                .execute(&db.0)
+                .await
+                .expect("Failed to create jobs table.");
+        }
+    }
+
+    if revision <= 6 {
+        info!("Running migration [revision 6 / 18-10-2026]: Add `message_revisions` table.");
+
+        for statement in [
+            "CREATE TABLE message_revisions (
+                data JSONB NOT NULL,
+                id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+                message TEXT GENERATED ALWAYS AS (data->>'message') STORED,
+                channel TEXT GENERATED ALWAYS AS (data->>'channel') STORED
+            )",
+            "CREATE INDEX message_revisions_message ON message_revisions (message, id)",
+            "CREATE INDEX message_revisions_channel ON message_revisions (channel)",
+        ] {
+            sqlx::query(statement)
+                .execute(&db.0)
+                .await
+                .expect("Failed to create message_revisions table.");
+        }
+    }
+
+    if revision <= 7 {
+        info!("Running migration [revision 7 / 18-10-2026]: Add `deleted_messages` table.");
+
+        for statement in [
+            "CREATE TABLE deleted_messages (
+                data JSONB NOT NULL,
+                id TEXT GENERATED ALWAYS AS (data->>'_id') STORED PRIMARY KEY,
+                channel TEXT GENERATED ALWAYS AS (data->>'channel') STORED,
+                deleted_at BIGINT GENERATED ALWAYS AS ((data->>'deleted_at')::bigint) STORED
+            )",
+            "CREATE INDEX deleted_messages_channel ON deleted_messages (channel, deleted_at DESC)",
+            "CREATE INDEX deleted_messages_deleted_at ON deleted_messages (deleted_at)",
+        ] {
+            sqlx::query(statement)
+                .execute(&db.0)
+                .await
+                .expect("Failed to create deleted_messages table.");
+        }
+    }
+
+    // Reminder to update LATEST_REVISION when adding new migrations.
+    LATEST_REVISION
+}
@@ -0,0 +1,257 @@
+use sqlx::types::Json;
+
+use crate::models::channel::{Channel, FieldsChannel, PartialChannel};
+use crate::models::Server;
+use crate::{AbstractChannel, Error, OverrideField, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "channels";
+
+impl PostgresDb {
+    /// Delete invites, unreads and webhooks belonging to any of the given channels
+    pub async fn delete_associated_channel_objects(&self, channels: &[String]) -> Result<()> {
+        // Delete all invites to these channels.
+        sqlx::query("DELETE FROM channel_invites WHERE channel = ANY($1)")
+            .bind(channels)
+            .execute(&self.0)
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "channel_invites",
+            })?;
+
+        // Delete unread message objects on channels.
+        sqlx::query("DELETE FROM channel_unreads WHERE channel = ANY($1)")
+            .bind(channels)
+            .execute(&self.0)
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "channel_unreads",
+            })?;
+
+        // Delete all webhooks on these channels.
+        sqlx::query("DELETE FROM webhooks WHERE channel = ANY($1)")
+            .bind(channels)
+            .execute(&self.0)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "webhooks",
+            })
+    }
+
+    async fn find_channels(&self, condition: &str, values: &[&str]) -> Result<Vec<Channel>> {
+        let sql = format!("SELECT data FROM {TABLE} WHERE {condition}");
+        let mut query = sqlx::query_as::<_, Document<Channel>>(&sql);
+        for value in values {
+            query = query.bind(*value);
+        }
+
+        query
+            .fetch_all(&self.0)
+            .await
+            .map(|rows| rows.into_iter().map(|(Json(channel),)| channel).collect())
+            .map_err(|_| Error::DatabaseError {
+                operation: "find",
+                with: "channels",
+            })
+    }
+}
+
+#[async_trait]
+impl AbstractChannel for PostgresDb {
+    async fn fetch_channel(&self, id: &str) -> Result<Channel> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_channels<'a>(&self, ids: &'a [String]) -> Result<Vec<Channel>> {
+        self.find_by_ids(TABLE, ids).await
+    }
+
+    async fn insert_channel(&self, channel: &Channel) -> Result<()> {
+        self.insert_one(TABLE, channel).await
+    }
+
+    async fn update_channel(
+        &self,
+        id: &str,
+        partial: &PartialChannel,
+        remove: Vec<FieldsChannel>,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |channel: &mut Channel| {
+            for field in &remove {
+                channel.remove(field);
+            }
+
+            channel.apply_options(partial.clone());
+
+            // Not covered by apply_options as clients never receive it in updates.
+            if let Some(message_id) = &partial.last_message_id {
+                match channel {
+                    Channel::DirectMessage {
+                        last_message_id, ..
+                    }
+                    | Channel::Group {
+                        last_message_id, ..
+                    }
+                    | Channel::TextChannel {
+                        last_message_id, ..
+                    }
+                    | Channel::Thread {
+                        last_message_id, ..
+                    } => {
+                        last_message_id.replace(message_id.to_string());
+                    }
+                    _ => {}
+                }
+            }
+        })
+        .await
+    }
+
+    async fn update_last_message_id(
+        &self,
+        id: &str,
+        message_id: &str,
+        activate: bool,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |channel: &mut Channel| {
+            channel.advance_last_message_id(message_id, activate)
+        })
+        .await
+    }
+
+    async fn delete_channel(&self, channel: &Channel) -> Result<()> {
+        let id = channel.id().to_string();
+        let ids = [id.clone()];
+
+        // Delete threads started in this channel.
+        for thread in self.fetch_threads(&ids).await? {
+            self.delete_channel(&thread).await?;
+        }
+
+        // Delete invites, unreads and webhooks.
+        self.delete_associated_channel_objects(&ids).await?;
+
+        // Delete messages.
+        self.delete_bulk_messages(&ids, None).await?;
+
+        // Remove from server object.
+        if let Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } = channel
+        {
+            self.update_one_by_id("servers", server, |server: &mut Server| {
+                server.channels.retain(|channel| channel != &id);
+
+                if let Some(sys) = &mut server.system_messages {
+                    for cid in [
+                        &mut sys.user_joined,
+                        &mut sys.user_left,
+                        &mut sys.user_kicked,
+                        &mut sys.user_banned,
+                    ] {
+                        if cid.as_ref() == Some(&id) {
+                            cid.take();
+                        }
+                    }
+                }
+            })
+            .await?;
+        }
+
+        // Delete associated attachments
+        self.delete_many_attachments("object_id", &ids).await?;
+
+        // Delete the channel itself
+        self.delete_one_by_id(TABLE, &id).await
+    }
+
+    async fn fetch_threads(&self, parents: &[String]) -> Result<Vec<Channel>> {
+        sqlx::query_as::<_, Document<Channel>>(
+            "SELECT data FROM channels WHERE channel_type = 'Thread' AND data->>'parent' = ANY($1)",
+        )
+        .bind(parents)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(Json(channel),)| channel).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "channels",
+        })
+    }
+
+    async fn find_direct_messages(&self, user_id: &str) -> Result<Vec<Channel>> {
+        self.find_channels(
+            "(channel_type IN ('DirectMessage', 'Group') AND data->'recipients' ? $1)
+            OR (channel_type = 'SavedMessages' AND user_id = $1)",
+            &[user_id],
+        )
+        .await
+    }
+
+    async fn find_saved_messages_channel(&self, user_id: &str) -> Result<Channel> {
+        self.find_channels(
+            "channel_type = 'SavedMessages' AND user_id = $1",
+            &[user_id],
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(Error::NotFound)
+    }
+
+    async fn find_direct_message_channel(&self, user_a: &str, user_b: &str) -> Result<Channel> {
+        if user_a == user_b {
+            return self.find_saved_messages_channel(user_a).await;
+        }
+
+        self.find_channels(
+            "channel_type = 'DirectMessage' AND data->'recipients' ?& ARRAY[$1, $2]",
+            &[user_a, user_b],
+        )
+        .await?
+        .into_iter()
+        .next()
+        .ok_or(Error::NotFound)
+    }
+
+    async fn add_user_to_group(&self, channel: &str, user: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, channel, |channel: &mut Channel| {
+            if let Channel::Group { recipients, .. } = channel {
+                recipients.push(user.to_string());
+            }
+        })
+        .await
+    }
+
+    async fn remove_user_from_group(&self, channel: &str, user: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, channel, |channel: &mut Channel| {
+            if let Channel::Group { recipients, .. } = channel {
+                recipients.retain(|id| id != user);
+            }
+        })
+        .await
+    }
+
+    async fn set_channel_role_permission(
+        &self,
+        channel: &str,
+        role: &str,
+        permissions: OverrideField,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, channel, |channel: &mut Channel| {
+            if let Channel::TextChannel {
+                role_permissions, ..
+            }
+            | Channel::VoiceChannel {
+                role_permissions, ..
+            } = channel
+            {
+                role_permissions.insert(role.to_string(), permissions);
+            }
+        })
+        .await
+    }
+}
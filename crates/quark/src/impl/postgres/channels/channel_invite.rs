@@ -0,0 +1,25 @@
+use crate::models::Invite;
+use crate::{AbstractChannelInvite, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "channel_invites";
+
+#[async_trait]
+impl AbstractChannelInvite for PostgresDb {
+    async fn fetch_invite(&self, code: &str) -> Result<Invite> {
+        self.find_one_by_id(TABLE, code).await
+    }
+
+    async fn insert_invite(&self, invite: &Invite) -> Result<()> {
+        self.insert_one(TABLE, invite).await
+    }
+
+    async fn delete_invite(&self, code: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, code).await
+    }
+
+    async fn fetch_invites_for_server(&self, server: &str) -> Result<Vec<Invite>> {
+        self.find_by_column(TABLE, "server", server).await
+    }
+}
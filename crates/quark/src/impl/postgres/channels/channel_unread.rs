@@ -0,0 +1,105 @@
+use sqlx::types::Json;
+use ulid::Ulid;
+
+use crate::models::channel_unread::{ChannelCompositeKey, ChannelUnread};
+use crate::{AbstractChannelUnread, Error, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "channel_unreads";
+
+#[async_trait]
+impl AbstractChannelUnread for PostgresDb {
+    async fn acknowledge_message(&self, channel: &str, user: &str, message: &str) -> Result<()> {
+        let unread = ChannelUnread {
+            id: ChannelCompositeKey {
+                channel: channel.to_string(),
+                user: user.to_string(),
+            },
+            last_id: Some(message.to_string()),
+            mentions: None,
+        };
+
+        sqlx::query(
+            "INSERT INTO channel_unreads (data) VALUES ($1)
+            ON CONFLICT (id) DO UPDATE SET data = (channel_unreads.data - 'mentions') || jsonb_build_object('last_id', $2::text)",
+        )
+        .bind(Json(&unread))
+        .bind(message)
+        .execute(&self.0)
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_one",
+            with: "channel_unread",
+        })
+    }
+
+    async fn acknowledge_channels(&self, user: &str, channels: &[String]) -> Result<()> {
+        let current_time = Ulid::new().to_string();
+        let unreads = channels
+            .iter()
+            .map(|channel| {
+                Json(ChannelUnread {
+                    id: ChannelCompositeKey {
+                        channel: channel.to_string(),
+                        user: user.to_string(),
+                    },
+                    last_id: Some(current_time.clone()),
+                    mentions: None,
+                })
+            })
+            .collect::<Vec<Json<ChannelUnread>>>();
+
+        sqlx::query(
+            "INSERT INTO channel_unreads (data) SELECT * FROM UNNEST($1::jsonb[])
+            ON CONFLICT (id) DO UPDATE SET data = EXCLUDED.data",
+        )
+        .bind(unreads)
+        .execute(&self.0)
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_many",
+            with: "channel_unreads",
+        })
+    }
+
+    async fn add_mention_to_unread<'a>(
+        &self,
+        channel: &str,
+        user: &str,
+        ids: &[String],
+    ) -> Result<()> {
+        let unread = ChannelUnread {
+            id: ChannelCompositeKey {
+                channel: channel.to_string(),
+                user: user.to_string(),
+            },
+            last_id: None,
+            mentions: Some(ids.to_vec()),
+        };
+
+        sqlx::query(
+            "INSERT INTO channel_unreads (data) VALUES ($1)
+            ON CONFLICT (id) DO UPDATE SET data = jsonb_set(
+                channel_unreads.data,
+                '{mentions}',
+                coalesce(channel_unreads.data->'mentions', '[]'::jsonb) || $2
+            )",
+        )
+        .bind(Json(&unread))
+        .bind(Json(ids))
+        .execute(&self.0)
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_one",
+            with: "channel_unread",
+        })
+    }
+
+    async fn fetch_unreads(&self, user: &str) -> Result<Vec<ChannelUnread>> {
+        self.find_by_column(TABLE, "user_id", user).await
+    }
+}
@@ -0,0 +1,443 @@
+use sqlx::types::Json;
+
+use crate::models::message::{AppendMessage, Message, MessageFilter, MessageSort, PartialMessage};
+use crate::models::DeletedMessage;
+use crate::{AbstractMessage, Error, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "messages";
+
+impl PostgresDb {
+    /// Permanently delete messages in the given channels, optionally restricted to some ids
+    ///
+    /// This includes any deleted messages which are still awaiting purge.
+    pub async fn delete_bulk_messages(
+        &self,
+        channels: &[String],
+        ids: Option<&[String]>,
+    ) -> Result<()> {
+        // Mark any attachments on these messages as deleted.
+        sqlx::query(
+            "UPDATE attachments SET data = data || '{\"deleted\":true}' WHERE message_id IN (
+                SELECT id FROM messages WHERE channel = ANY($1) AND ($2::text[] IS NULL OR id = ANY($2))
+                UNION ALL
+                SELECT id FROM deleted_messages WHERE channel = ANY($1) AND ($2::text[] IS NULL OR id = ANY($2))
+            )",
+        )
+        .bind(channels)
+        .bind(ids)
+        .execute(&self.0)
+        .await
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_many",
+            with: "attachments",
+        })?;
+
+        // Remove any stored revisions of these messages.
+        sqlx::query(
+            "DELETE FROM message_revisions WHERE channel = ANY($1) AND ($2::text[] IS NULL OR message = ANY($2))",
+        )
+        .bind(channels)
+        .bind(ids)
+        .execute(&self.0)
+        .await
+        .map_err(|_| Error::DatabaseError {
+            operation: "delete_many",
+            with: "message_revisions",
+        })?;
+
+        // And then delete said messages.
+        for table in ["messages", "deleted_messages"] {
+            let sql = format!(
+                "DELETE FROM {table} WHERE channel = ANY($1) AND ($2::text[] IS NULL OR id = ANY($2))"
+            );
+
+            sqlx::query(&sql)
+                .bind(channels)
+                .bind(ids)
+                .execute(&self.0)
+                .await
+                .map_err(|_| Error::DatabaseError {
+                    operation: "delete_many",
+                    with: table,
+                })?;
+        }
+
+        Ok(())
+    }
+
+    /// Move messages into tombstones, optionally restricted to a channel
+    async fn tombstone_messages(
+        &self,
+        channel: Option<&str>,
+        ids: &[String],
+        deleted_at: i64,
+    ) -> Result<()> {
+        sqlx::query(
+            "WITH deleted AS (
+                DELETE FROM messages WHERE id = ANY($1) AND ($2::text IS NULL OR channel = $2)
+                RETURNING data
+            )
+            INSERT INTO deleted_messages (data)
+            SELECT jsonb_build_object(
+                '_id', data->'_id',
+                'channel', data->'channel',
+                'deleted_at', $3::bigint,
+                'message', data
+            ) FROM deleted",
+        )
+        .bind(ids)
+        .bind(channel)
+        .bind(deleted_at)
+        .execute(&self.0)
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::DatabaseError {
+            operation: "delete_many",
+            with: "messages",
+        })
+    }
+
+    async fn find_messages(&self, sql: &str, values: &[&str], limit: i64) -> Result<Vec<Message>> {
+        let mut query = sqlx::query_as::<_, Document<Message>>(sql);
+        for value in values {
+            query = query.bind(*value);
+        }
+
+        query
+            .bind(limit)
+            .fetch_all(&self.0)
+            .await
+            .map(|rows| rows.into_iter().map(|(Json(message),)| message).collect())
+            .map_err(|_| Error::DatabaseError {
+                operation: "find",
+                with: "messages",
+            })
+    }
+}
+
+#[async_trait]
+impl AbstractMessage for PostgresDb {
+    async fn fetch_message(&self, id: &str) -> Result<Message> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn insert_message(&self, message: &Message) -> Result<()> {
+        self.insert_one(TABLE, message).await
+    }
+
+    async fn update_message(&self, id: &str, partial: &PartialMessage) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |message: &mut Message| {
+            message.apply_options(partial.clone());
+        })
+        .await
+    }
+
+    async fn append_message(&self, id: &str, append: &AppendMessage) -> Result<()> {
+        if let Some(embeds) = &append.embeds {
+            if !embeds.is_empty() {
+                return self
+                    .update_one_by_id(TABLE, id, |message: &mut Message| {
+                        message
+                            .embeds
+                            .get_or_insert_with(Vec::new)
+                            .extend(embeds.iter().cloned());
+                    })
+                    .await;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn delete_message(&self, id: &str, deleted_at: i64) -> Result<()> {
+        self.tombstone_messages(None, &[id.to_string()], deleted_at)
+            .await
+    }
+
+    async fn delete_messages(
+        &self,
+        channel: &str,
+        ids: Vec<String>,
+        deleted_at: i64,
+    ) -> Result<()> {
+        self.tombstone_messages(Some(channel), &ids, deleted_at)
+            .await
+    }
+
+    async fn fetch_deleted_messages(
+        &self,
+        channel: &str,
+        limit: i64,
+    ) -> Result<Vec<DeletedMessage>> {
+        sqlx::query_as::<_, Document<DeletedMessage>>(
+            "SELECT data FROM deleted_messages WHERE channel = $1
+            ORDER BY deleted_at DESC, id DESC LIMIT $2",
+        )
+        .bind(channel)
+        .bind(limit)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(Json(deleted),)| deleted).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "deleted_messages",
+        })
+    }
+
+    async fn restore_message(
+        &self,
+        channel: &str,
+        id: &str,
+        deleted_after: i64,
+    ) -> Result<Message> {
+        sqlx::query_as::<_, Document<Message>>(
+            "WITH restored AS (
+                DELETE FROM deleted_messages WHERE id = $1 AND channel = $2 AND deleted_at >= $3
+                RETURNING data
+            )
+            INSERT INTO messages (data) SELECT data->'message' FROM restored
+            RETURNING data",
+        )
+        .bind(id)
+        .bind(channel)
+        .bind(deleted_after)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(|_| Error::DatabaseError {
+            operation: "insert_one",
+            with: "messages",
+        })?
+        .map(|(Json(message),)| message)
+        .ok_or(Error::NotFound)
+    }
+
+    async fn purge_deleted_messages(&self, before: i64) -> Result<()> {
+        for (sql, operation, with) in [
+            (
+                "UPDATE attachments SET data = data || '{\"deleted\":true}' WHERE message_id IN (
+                    SELECT id FROM deleted_messages WHERE deleted_at < $1
+                )",
+                "update_many",
+                "attachments",
+            ),
+            (
+                "DELETE FROM message_revisions WHERE message IN (
+                    SELECT id FROM deleted_messages WHERE deleted_at < $1
+                )",
+                "delete_many",
+                "message_revisions",
+            ),
+            (
+                "DELETE FROM deleted_messages WHERE deleted_at < $1",
+                "delete_many",
+                "deleted_messages",
+            ),
+        ] {
+            sqlx::query(sql)
+                .bind(before)
+                .execute(&self.0)
+                .await
+                .map_err(|_| Error::DatabaseError { operation, with })?;
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_messages(
+        &self,
+        channel: &str,
+        limit: Option<i64>,
+        before: Option<String>,
+        after: Option<String>,
+        sort: Option<MessageSort>,
+        nearby: Option<String>,
+    ) -> Result<Vec<Message>> {
+        let limit = limit.unwrap_or(50);
+
+        if let Some(nearby) = nearby {
+            let a = self
+                .find_messages(
+                    "SELECT data FROM messages WHERE channel = $1 AND id >= $2 ORDER BY id ASC LIMIT $3",
+                    &[channel, nearby.as_str()],
+                    limit / 2 + 1,
+                )
+                .await?;
+
+            let b = self
+                .find_messages(
+                    "SELECT data FROM messages WHERE channel = $1 AND id < $2 ORDER BY id DESC LIMIT $3",
+                    &[channel, nearby.as_str()],
+                    limit / 2,
+                )
+                .await?;
+
+            Ok([a, b].concat())
+        } else {
+            let order = if let MessageSort::Latest = sort.unwrap_or(MessageSort::Latest) {
+                "DESC"
+            } else {
+                "ASC"
+            };
+
+            // Same as the MongoDB implementation, `after` takes precedence over `before`.
+            let (condition, bound) = if let Some(after) = &after {
+                ("AND id > $2", Some(after))
+            } else if let Some(before) = &before {
+                ("AND id < $2", Some(before))
+            } else {
+                ("", None)
+            };
+
+            if let Some(bound) = bound {
+                self.find_messages(
+                    &format!("SELECT data FROM messages WHERE channel = $1 {condition} ORDER BY id {order} LIMIT $3"),
+                    &[channel, bound.as_str()],
+                    limit,
+                )
+                .await
+            } else {
+                self.find_messages(
+                    &format!(
+                        "SELECT data FROM messages WHERE channel = $1 ORDER BY id {order} LIMIT $2"
+                    ),
+                    &[channel],
+                    limit,
+                )
+                .await
+            }
+        }
+    }
+
+    async fn fetch_pinned_messages(&self, channel: &str) -> Result<Vec<Message>> {
+        sqlx::query_as::<_, Document<Message>>(
+            "SELECT data FROM messages WHERE channel = $1 AND (data->>'pinned')::boolean IS TRUE ORDER BY id DESC",
+        )
+        .bind(channel)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(Json(message),)| message).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "messages",
+        })
+    }
+
+    async fn pin_message(&self, channel: &str, id: &str, max: usize) -> Result<()> {
+        let map_err = |_| Error::DatabaseError {
+            operation: "update_one",
+            with: "message",
+        };
+
+        // Serialise pins within the channel so the count can't go stale.
+        let mut tx = self.0.begin().await.map_err(map_err)?;
+        sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1))")
+            .bind(format!("pins:{channel}"))
+            .execute(&mut tx)
+            .await
+            .map_err(map_err)?;
+
+        let (pinned,) = sqlx::query_as::<_, (i64,)>(
+            "SELECT count(*) FROM messages WHERE channel = $1 AND (data->>'pinned')::boolean IS TRUE",
+        )
+        .bind(channel)
+        .fetch_one(&mut tx)
+        .await
+        .map_err(map_err)?;
+
+        let result = sqlx::query(
+            "UPDATE messages SET data = jsonb_set(data, '{pinned}', 'true')
+            WHERE id = $1 AND channel = $2 AND (data->>'pinned')::boolean IS NOT TRUE",
+        )
+        .bind(id)
+        .bind(channel)
+        .execute(&mut tx)
+        .await
+        .map_err(map_err)?;
+
+        if result.rows_affected() > 0 && pinned as usize >= max {
+            return Err(Error::TooManyPins { max });
+        }
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn search_messages(
+        &self,
+        channels: &[String],
+        filter: &MessageFilter,
+        limit: Option<i64>,
+        sort: MessageSort,
+    ) -> Result<Vec<Message>> {
+        let limit = limit.unwrap_or(50);
+        let order = match sort {
+            MessageSort::Relevance if filter.query.is_some() => "ts_rank(to_tsvector('english', coalesce(content, '')), plainto_tsquery('english', $2)) DESC",
+            MessageSort::Relevance | MessageSort::Latest => "id DESC",
+            MessageSort::Oldest => "id ASC",
+        };
+
+        sqlx::query_as::<_, Document<Message>>(&format!(
+            "SELECT data FROM messages
+            WHERE channel = ANY($1)
+            AND ($2::text IS NULL OR to_tsvector('english', coalesce(content, '')) @@ plainto_tsquery('english', $2))
+            AND ($3::text IS NULL OR data->>'author' = $3)
+            AND ($4::text IS NULL OR coalesce(data->'mentions', '[]'::jsonb) ? $4)
+            AND (NOT $5 OR jsonb_array_length(coalesce(data->'attachments', '[]'::jsonb)) > 0)
+            AND (NOT $6 OR jsonb_array_length(coalesce(data->'embeds', '[]'::jsonb)) > 0)
+            AND (NOT $7 OR content ~* 'https?://')
+            AND (NOT $8 OR (data->>'pinned')::boolean IS TRUE)
+            AND ($9::text IS NULL OR id < $9)
+            AND ($10::text IS NULL OR id > $10)
+            ORDER BY {order} LIMIT $11"
+        ))
+        .bind(channels)
+        .bind(&filter.query)
+        .bind(&filter.author)
+        .bind(&filter.mention)
+        .bind(filter.has_attachment)
+        .bind(filter.has_embed)
+        .bind(filter.has_link)
+        .bind(filter.pinned)
+        .bind(&filter.before)
+        .bind(&filter.after)
+        .bind(limit)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(Json(message),)| message).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "messages",
+        })
+    }
+
+    /// Add a new reaction to a message
+    async fn add_reaction(&self, id: &str, emoji: &str, user: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |message: &mut Message| {
+            message
+                .reactions
+                .entry(emoji.to_string())
+                .or_default()
+                .insert(user.to_string());
+        })
+        .await
+    }
+
+    /// Remove a reaction from a message
+    async fn remove_reaction(&self, id: &str, emoji: &str, user: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |message: &mut Message| {
+            if let Some(users) = message.reactions.get_mut(emoji) {
+                users.shift_remove(user);
+            }
+        })
+        .await
+    }
+
+    /// Remove reaction from a message
+    async fn clear_reaction(&self, id: &str, emoji: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |message: &mut Message| {
+            message.reactions.shift_remove(emoji);
+        })
+        .await
+    }
+}
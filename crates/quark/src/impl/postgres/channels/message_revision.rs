@@ -0,0 +1,70 @@
+use sqlx::types::Json;
+
+use crate::models::MessageRevision;
+use crate::{AbstractMessageRevision, Error, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "message_revisions";
+
+#[async_trait]
+impl AbstractMessageRevision for PostgresDb {
+    async fn insert_message_revision(&self, revision: &MessageRevision) -> Result<()> {
+        self.insert_one(TABLE, revision).await
+    }
+
+    async fn fetch_message_revisions(&self, message: &str) -> Result<Vec<MessageRevision>> {
+        sqlx::query_as::<_, Document<MessageRevision>>(
+            "SELECT data FROM message_revisions WHERE message = $1 ORDER BY id",
+        )
+        .bind(message)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(Json(revision),)| revision).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "message_revisions",
+        })
+    }
+
+    async fn delete_message_revisions_before(&self, message: &str, before: &str) -> Result<()> {
+        sqlx::query("DELETE FROM message_revisions WHERE message = $1 AND id < $2")
+            .bind(message)
+            .bind(before)
+            .execute(&self.0)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "message_revisions",
+            })
+    }
+
+    async fn fetch_message_revision_channels(&self) -> Result<Vec<String>> {
+        sqlx::query_as::<_, (String,)>("SELECT DISTINCT channel FROM message_revisions")
+            .fetch_all(&self.0)
+            .await
+            .map(|rows| rows.into_iter().map(|(channel,)| channel).collect())
+            .map_err(|_| Error::DatabaseError {
+                operation: "distinct",
+                with: "message_revisions",
+            })
+    }
+
+    async fn delete_channel_message_revisions_before(
+        &self,
+        channel: &str,
+        before: &str,
+    ) -> Result<()> {
+        sqlx::query("DELETE FROM message_revisions WHERE channel = $1 AND id < $2")
+            .bind(channel)
+            .bind(before)
+            .execute(&self.0)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "message_revisions",
+            })
+    }
+}
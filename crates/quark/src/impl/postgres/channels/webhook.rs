@@ -0,0 +1,41 @@
+use crate::models::webhook::{FieldsWebhook, PartialWebhook, Webhook};
+use crate::{AbstractWebhook, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "webhooks";
+
+#[async_trait]
+impl AbstractWebhook for PostgresDb {
+    async fn insert_webhook(&self, webhook: &Webhook) -> Result<()> {
+        self.insert_one(TABLE, webhook).await
+    }
+
+    async fn fetch_webhook(&self, id: &str) -> Result<Webhook> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_webhooks_for_channel(&self, channel: &str) -> Result<Vec<Webhook>> {
+        self.find_by_column(TABLE, "channel", channel).await
+    }
+
+    async fn update_webhook(
+        &self,
+        id: &str,
+        partial: &PartialWebhook,
+        remove: Vec<FieldsWebhook>,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |webhook: &mut Webhook| {
+            for field in &remove {
+                webhook.remove(field);
+            }
+
+            webhook.apply_options(partial.clone());
+        })
+        .await
+    }
+
+    async fn delete_webhook(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, id).await
+    }
+}
@@ -0,0 +1,83 @@
+use sqlx::types::Json;
+
+use crate::models::attachment::File;
+use crate::{AbstractAttachment, Error, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "attachments";
+
+impl PostgresDb {
+    /// Mark all attachments where a given column matches any of the values as deleted
+    pub async fn delete_many_attachments(
+        &self,
+        column: &'static str,
+        values: &[String],
+    ) -> Result<()> {
+        let sql = format!(
+            "UPDATE {TABLE} SET data = data || '{{\"deleted\":true}}' WHERE {column} = ANY($1)"
+        );
+
+        sqlx::query(&sql)
+            .bind(values)
+            .execute(&self.0)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "update_many",
+                with: "attachment",
+            })
+    }
+}
+
+#[async_trait]
+impl AbstractAttachment for PostgresDb {
+    async fn find_and_use_attachment(
+        &self,
+        id: &str,
+        tag: &str,
+        parent_type: &str,
+        parent_id: &str,
+    ) -> Result<File> {
+        let key = format!("{}_id", parent_type);
+        sqlx::query_as::<_, Document<File>>(
+            "UPDATE attachments SET data = jsonb_set(data, ARRAY[$3::text], to_jsonb($4::text))
+            WHERE id = $1 AND data->>'tag' = $2 AND NOT data ? $3::text
+            RETURNING data",
+        )
+        .bind(id)
+        .bind(tag)
+        .bind(key)
+        .bind(parent_id)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_one",
+            with: "attachment",
+        })?
+        .map(|(Json(file),)| file)
+        .ok_or(Error::UnknownAttachment)
+    }
+
+    async fn insert_attachment(&self, attachment: &File) -> Result<()> {
+        self.insert_one(TABLE, attachment).await
+    }
+
+    async fn mark_attachment_as_reported(&self, id: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |file: &mut File| {
+            file.reported = Some(true);
+        })
+        .await
+    }
+
+    async fn mark_attachment_as_deleted(&self, id: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |file: &mut File| {
+            file.deleted = Some(true);
+        })
+        .await
+    }
+
+    async fn mark_attachments_as_deleted(&self, ids: &[String]) -> Result<()> {
+        self.delete_many_attachments("id", ids).await
+    }
+}
@@ -0,0 +1,48 @@
+use sqlx::types::Json;
+
+use crate::models::emoji::EmojiParent;
+use crate::models::Emoji;
+use crate::{AbstractEmoji, Error, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "emojis";
+
+#[async_trait]
+impl AbstractEmoji for PostgresDb {
+    /// Fetch an emoji by its id
+    async fn fetch_emoji(&self, id: &str) -> Result<Emoji> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    /// Fetch emoji by their ids
+    async fn fetch_emoji_by_parent_id(&self, parent_id: &str) -> Result<Vec<Emoji>> {
+        self.find_by_column(TABLE, "parent_id", parent_id).await
+    }
+
+    /// Fetch emoji by their parent ids
+    async fn fetch_emoji_by_parent_ids(&self, parent_ids: &[String]) -> Result<Vec<Emoji>> {
+        sqlx::query_as::<_, Document<Emoji>>("SELECT data FROM emojis WHERE parent_id = ANY($1)")
+            .bind(parent_ids)
+            .fetch_all(&self.0)
+            .await
+            .map(|rows| rows.into_iter().map(|(Json(emoji),)| emoji).collect())
+            .map_err(|_| Error::DatabaseError {
+                operation: "find",
+                with: "emojis",
+            })
+    }
+
+    /// Insert emoji into database.
+    async fn insert_emoji(&self, emoji: &Emoji) -> Result<()> {
+        self.insert_one(TABLE, emoji).await
+    }
+
+    /// Delete an emoji by its id
+    async fn detach_emoji(&self, emoji: &Emoji) -> Result<()> {
+        self.update_one_by_id(TABLE, &emoji.id, |emoji: &mut Emoji| {
+            emoji.parent = EmojiParent::Detached;
+        })
+        .await
+    }
+}
@@ -0,0 +1,186 @@
+use std::ops::Deref;
+
+use serde::{de::DeserializeOwned, Serialize};
+use sqlx::{postgres::PgPool, types::Json};
+
+use crate::{AbstractDatabase, Error, Result};
+
+pub mod admin {
+    pub mod job;
+    pub mod migrations;
+}
+
+pub mod media {
+    pub mod attachment;
+    pub mod emoji;
+}
+
+pub mod channels {
+    pub mod channel;
+    pub mod channel_invite;
+    pub mod channel_unread;
+    pub mod message;
+    pub mod message_revision;
+    pub mod webhook;
+}
+
+pub mod servers {
+    pub mod audit_log;
+    pub mod automod_rule;
+    pub mod server;
+    pub mod server_ban;
+    pub mod server_member;
+}
+
+pub mod users {
+    pub mod bot;
+    pub mod bot_command;
+    pub mod user;
+    pub mod user_settings;
+}
+
+/// Row consisting of a single JSON document
+///
+/// Every table stores the serialised model in a `data` column,
+/// any columns used for lookups are generated from this document.
+pub type Document<T> = (Json<T>,);
+
+#[derive(Debug, Clone)]
+pub struct PostgresDb(pub PgPool);
+
+impl AbstractDatabase for PostgresDb {}
+
+impl Deref for PostgresDb {
+    type Target = PgPool;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// Generate the primary key for tables with composite ids
+pub fn composite_key(a: &str, b: &str) -> String {
+    format!("{a}:{b}")
+}
+
+impl PostgresDb {
+    async fn insert_one<T: Serialize + Sync>(
+        &self,
+        table: &'static str,
+        document: &T,
+    ) -> Result<()> {
+        let sql = format!("INSERT INTO {table} (data) VALUES ($1)");
+        sqlx::query(&sql)
+            .bind(Json(document))
+            .execute(&self.0)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "insert_one",
+                with: table,
+            })
+    }
+
+    async fn find_one_by_id<T: DeserializeOwned + Send + Unpin>(
+        &self,
+        table: &'static str,
+        id: &str,
+    ) -> Result<T> {
+        let sql = format!("SELECT data FROM {table} WHERE id = $1");
+        sqlx::query_as::<_, Document<T>>(&sql)
+            .bind(id)
+            .fetch_optional(&self.0)
+            .await
+            .map_err(|_| Error::DatabaseError {
+                operation: "find_one",
+                with: table,
+            })?
+            .map(|(Json(document),)| document)
+            .ok_or(Error::NotFound)
+    }
+
+    async fn find_by_ids<T: DeserializeOwned + Send + Unpin>(
+        &self,
+        table: &'static str,
+        ids: &[String],
+    ) -> Result<Vec<T>> {
+        let sql = format!("SELECT data FROM {table} WHERE id = ANY($1)");
+        sqlx::query_as::<_, Document<T>>(&sql)
+            .bind(ids)
+            .fetch_all(&self.0)
+            .await
+            .map(|rows| rows.into_iter().map(|(Json(document),)| document).collect())
+            .map_err(|_| Error::DatabaseError {
+                operation: "find",
+                with: table,
+            })
+    }
+
+    async fn find_by_column<T: DeserializeOwned + Send + Unpin>(
+        &self,
+        table: &'static str,
+        column: &'static str,
+        value: &str,
+    ) -> Result<Vec<T>> {
+        let sql = format!("SELECT data FROM {table} WHERE {column} = $1");
+        sqlx::query_as::<_, Document<T>>(&sql)
+            .bind(value)
+            .fetch_all(&self.0)
+            .await
+            .map(|rows| rows.into_iter().map(|(Json(document),)| document).collect())
+            .map_err(|_| Error::DatabaseError {
+                operation: "find",
+                with: table,
+            })
+    }
+
+    /// Apply a mutation to a document while holding a row lock
+    ///
+    /// Like MongoDB updates, this does nothing if the document does not exist.
+    async fn update_one_by_id<T, F>(&self, table: &'static str, id: &str, mutate: F) -> Result<()>
+    where
+        T: Serialize + DeserializeOwned + Send + Sync + Unpin,
+        F: FnOnce(&mut T) + Send,
+    {
+        let map_err = |_| Error::DatabaseError {
+            operation: "update_one",
+            with: table,
+        };
+
+        let mut tx = self.0.begin().await.map_err(map_err)?;
+
+        let sql = format!("SELECT data FROM {table} WHERE id = $1 FOR UPDATE");
+        let row = sqlx::query_as::<_, Document<T>>(&sql)
+            .bind(id)
+            .fetch_optional(&mut tx)
+            .await
+            .map_err(map_err)?;
+
+        if let Some((Json(mut document),)) = row {
+            mutate(&mut document);
+
+            let sql = format!("UPDATE {table} SET data = $2 WHERE id = $1");
+            sqlx::query(&sql)
+                .bind(id)
+                .bind(Json(&document))
+                .execute(&mut tx)
+                .await
+                .map_err(map_err)?;
+        }
+
+        tx.commit().await.map_err(map_err)
+    }
+
+    async fn delete_one_by_id(&self, table: &'static str, id: &str) -> Result<()> {
+        let sql = format!("DELETE FROM {table} WHERE id = $1");
+        sqlx::query(&sql)
+            .bind(id)
+            .execute(&self.0)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_one",
+                with: table,
+            })
+    }
+}
@@ -0,0 +1,54 @@
+use sqlx::types::Json;
+
+use crate::models::audit_log::AuditLogAction;
+use crate::models::AuditLogEntry;
+use crate::{AbstractAuditLog, Error, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "audit_log";
+
+#[async_trait]
+impl AbstractAuditLog for PostgresDb {
+    async fn insert_audit_log_entry(&self, entry: &AuditLogEntry) -> Result<()> {
+        self.insert_one(TABLE, entry).await
+    }
+
+    async fn fetch_audit_log(
+        &self,
+        server: &str,
+        limit: i64,
+        before: Option<String>,
+        actor: Option<String>,
+        action: Option<AuditLogAction>,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let action =
+            action
+                .map(serde_json::to_value)
+                .transpose()
+                .map_err(|_| Error::DatabaseError {
+                    operation: "to_json",
+                    with: "audit_log",
+                })?;
+
+        sqlx::query_as::<_, Document<AuditLogEntry>>(
+            "SELECT data FROM audit_log WHERE server = $1
+                AND ($2::text IS NULL OR id < $2)
+                AND ($3::text IS NULL OR data->>'actor' = $3)
+                AND ($4::jsonb IS NULL OR data->'action' = $4)
+            ORDER BY id DESC LIMIT $5",
+        )
+        .bind(server)
+        .bind(before)
+        .bind(actor)
+        .bind(action.map(Json))
+        .bind(limit)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(Json(entry),)| entry).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "audit_log",
+        })
+    }
+}
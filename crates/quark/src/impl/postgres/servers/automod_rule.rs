@@ -0,0 +1,32 @@
+use crate::models::automod_rule::{AutomodRule, PartialAutomodRule};
+use crate::{AbstractAutomodRule, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "automod_rules";
+
+#[async_trait]
+impl AbstractAutomodRule for PostgresDb {
+    async fn insert_automod_rule(&self, rule: &AutomodRule) -> Result<()> {
+        self.insert_one(TABLE, rule).await
+    }
+
+    async fn fetch_automod_rule(&self, id: &str) -> Result<AutomodRule> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_automod_rules(&self, server: &str) -> Result<Vec<AutomodRule>> {
+        self.find_by_column(TABLE, "server", server).await
+    }
+
+    async fn update_automod_rule(&self, id: &str, partial: &PartialAutomodRule) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |rule: &mut AutomodRule| {
+            rule.apply_options(partial.clone());
+        })
+        .await
+    }
+
+    async fn delete_automod_rule(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, id).await
+    }
+}
@@ -0,0 +1,141 @@
+use crate::models::server::{FieldsRole, FieldsServer, PartialRole, PartialServer, Role, Server};
+use crate::{AbstractChannel, AbstractServer, Error, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "servers";
+
+impl PostgresDb {
+    /// Delete all objects that belong to a server
+    pub async fn delete_associated_server_objects(&self, server: &Server) -> Result<()> {
+        // Delete all messages.
+        self.delete_bulk_messages(&server.channels, None).await?;
+
+        // Delete all emoji, channels, members, bans and audit log entries.
+        for (with, column) in [
+            ("emojis", "parent_id"),
+            ("channels", "server"),
+            ("server_members", "server"),
+            ("server_bans", "server"),
+            ("audit_log", "server"),
+            ("automod_rules", "server"),
+        ] {
+            let sql = format!("DELETE FROM {with} WHERE {column} = $1");
+            sqlx::query(&sql)
+                .bind(&server.id)
+                .execute(&self.0)
+                .await
+                .map_err(|_| Error::DatabaseError {
+                    operation: "delete_many",
+                    with,
+                })?;
+        }
+
+        // Delete any associated objects, e.g. unreads and invites.
+        self.delete_associated_channel_objects(&server.channels)
+            .await?;
+
+        // Update many attachments with parent id.
+        self.delete_many_attachments("object_id", &[server.id.to_string()])
+            .await
+    }
+}
+
+#[async_trait]
+impl AbstractServer for PostgresDb {
+    async fn fetch_server(&self, id: &str) -> Result<Server> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_servers<'a>(&self, ids: &'a [String]) -> Result<Vec<Server>> {
+        self.find_by_ids(TABLE, ids).await
+    }
+
+    async fn insert_server(&self, server: &Server) -> Result<()> {
+        self.insert_one(TABLE, server).await
+    }
+
+    async fn update_server(
+        &self,
+        id: &str,
+        partial: &PartialServer,
+        remove: Vec<FieldsServer>,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |server: &mut Server| {
+            for field in &remove {
+                server.remove(field);
+            }
+
+            server.apply_options(partial.clone());
+        })
+        .await
+    }
+
+    async fn delete_server(&self, server: &Server) -> Result<()> {
+        for thread in self.fetch_threads(&server.channels).await? {
+            self.delete_channel(&thread).await?;
+        }
+
+        self.delete_associated_server_objects(server).await?;
+        self.delete_one_by_id(TABLE, &server.id).await
+    }
+
+    async fn insert_role(&self, server_id: &str, role_id: &str, role: &Role) -> Result<()> {
+        self.update_one_by_id(TABLE, server_id, |server: &mut Server| {
+            server.roles.insert(role_id.to_string(), role.clone());
+        })
+        .await
+    }
+
+    async fn update_role(
+        &self,
+        server_id: &str,
+        role_id: &str,
+        partial: &PartialRole,
+        remove: Vec<FieldsRole>,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, server_id, |server: &mut Server| {
+            if let Some(role) = server.roles.get_mut(role_id) {
+                for field in &remove {
+                    role.remove(field);
+                }
+
+                role.apply_options(partial.clone());
+            }
+        })
+        .await
+    }
+
+    async fn delete_role(&self, server_id: &str, role_id: &str) -> Result<()> {
+        sqlx::query(
+            "UPDATE server_members SET data = jsonb_set(data, '{roles}', (data->'roles') - $2)
+            WHERE server = $1 AND data ? 'roles'",
+        )
+        .bind(server_id)
+        .bind(role_id)
+        .execute(&self.0)
+        .await
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_many",
+            with: "server_members",
+        })?;
+
+        sqlx::query(
+            "UPDATE channels SET data = data #- ARRAY['role_permissions', $2]
+            WHERE server = $1",
+        )
+        .bind(server_id)
+        .bind(role_id)
+        .execute(&self.0)
+        .await
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_many",
+            with: "channels",
+        })?;
+
+        self.update_one_by_id(TABLE, server_id, |server: &mut Server| {
+            server.roles.remove(role_id);
+        })
+        .await
+    }
+}
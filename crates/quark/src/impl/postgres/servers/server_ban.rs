@@ -2,31 +2,27 @@ use crate::models::server_member::MemberCompositeKey;
 use crate::models::ServerBan;
 use crate::{AbstractServerBan, Result};
 
-use super::super::DummyDb;
+use super::super::{composite_key, PostgresDb};
+
+static TABLE: &str = "server_bans";
 
 #[async_trait]
-impl AbstractServerBan for DummyDb {
+impl AbstractServerBan for PostgresDb {
     async fn fetch_ban(&self, server: &str, user: &str) -> Result<ServerBan> {
-        Ok(ServerBan {
-            id: MemberCompositeKey {
-                server: server.into(),
-                user: user.into(),
-            },
-            reason: Some("ban reason".into()),
-        })
+        self.find_one_by_id(TABLE, &composite_key(server, user))
+            .await
     }
 
     async fn fetch_bans(&self, server: &str) -> Result<Vec<ServerBan>> {
-        Ok(vec![self.fetch_ban(server, "user").await.unwrap()])
+        self.find_by_column(TABLE, "server", server).await
     }
 
     async fn insert_ban(&self, ban: &ServerBan) -> Result<()> {
-        info!("Insert {ban:?}");
-        Ok(())
+        self.insert_one(TABLE, ban).await
     }
 
     async fn delete_ban(&self, id: &MemberCompositeKey) -> Result<()> {
-        info!("Delete {id:?}");
-        Ok(())
+        self.delete_one_by_id(TABLE, &composite_key(&id.server, &id.user))
+            .await
     }
 }
@@ -0,0 +1,89 @@
+use crate::models::server_member::{FieldsMember, Member, MemberCompositeKey, PartialMember};
+use crate::{AbstractServerMember, Error, Result};
+
+use super::super::{composite_key, Document, PostgresDb};
+
+static TABLE: &str = "server_members";
+
+impl PostgresDb {
+    async fn count_members(&self, column: &'static str, value: &str) -> Result<usize> {
+        let sql = format!("SELECT COUNT(*) FROM {TABLE} WHERE {column} = $1");
+        sqlx::query_as::<_, (i64,)>(&sql)
+            .bind(value)
+            .fetch_one(&self.0)
+            .await
+            .map(|(count,)| count as usize)
+            .map_err(|_| Error::DatabaseError {
+                operation: "count_documents",
+                with: "server_members",
+            })
+    }
+}
+
+#[async_trait]
+impl AbstractServerMember for PostgresDb {
+    async fn fetch_member(&self, server: &str, user: &str) -> Result<Member> {
+        self.find_one_by_id(TABLE, &composite_key(server, user))
+            .await
+    }
+
+    async fn insert_member(&self, member: &Member) -> Result<()> {
+        self.insert_one(TABLE, member).await
+    }
+
+    async fn update_member(
+        &self,
+        id: &MemberCompositeKey,
+        partial: &PartialMember,
+        remove: Vec<FieldsMember>,
+    ) -> Result<()> {
+        self.update_one_by_id(
+            TABLE,
+            &composite_key(&id.server, &id.user),
+            |member: &mut Member| {
+                for field in &remove {
+                    member.remove(field);
+                }
+
+                member.apply_options(partial.clone());
+            },
+        )
+        .await
+    }
+
+    async fn delete_member(&self, id: &MemberCompositeKey) -> Result<()> {
+        self.delete_one_by_id(TABLE, &composite_key(&id.server, &id.user))
+            .await
+    }
+
+    async fn fetch_all_members<'a>(&self, server: &str) -> Result<Vec<Member>> {
+        self.find_by_column(TABLE, "server", server).await
+    }
+
+    async fn fetch_all_memberships<'a>(&self, user: &str) -> Result<Vec<Member>> {
+        self.find_by_column(TABLE, "user_id", user).await
+    }
+
+    async fn fetch_members<'a>(&self, server: &str, ids: &'a [String]) -> Result<Vec<Member>> {
+        sqlx::query_as::<_, Document<Member>>(
+            "SELECT data FROM server_members WHERE server = $1 AND user_id = ANY($2)",
+        )
+        .bind(server)
+        .bind(ids)
+        .fetch_all(&self.0)
+        .await
+        .map(|rows| rows.into_iter().map(|(member,)| member.0).collect())
+        .map_err(|_| Error::DatabaseError {
+            operation: "find",
+            with: "server_members",
+        })
+    }
+
+    async fn fetch_member_count(&self, server: &str) -> Result<usize> {
+        self.count_members("server", server).await
+    }
+
+    async fn fetch_server_count(&self, user: &str) -> Result<usize> {
+        self.count_members("user_id", user).await
+    }
+}
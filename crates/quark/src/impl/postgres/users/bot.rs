@@ -0,0 +1,64 @@
+use crate::models::bot::{Bot, FieldsBot, PartialBot};
+use crate::{AbstractBot, Error, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "bots";
+
+#[async_trait]
+impl AbstractBot for PostgresDb {
+    async fn fetch_bot(&self, id: &str) -> Result<Bot> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_bot_by_token(&self, token: &str) -> Result<Bot> {
+        self.find_by_column(TABLE, "token", token)
+            .await?
+            .into_iter()
+            .next()
+            .ok_or(Error::NotFound)
+    }
+
+    async fn insert_bot(&self, bot: &Bot) -> Result<()> {
+        self.insert_one(TABLE, bot).await
+    }
+
+    async fn update_bot(
+        &self,
+        id: &str,
+        partial: &PartialBot,
+        remove: Vec<FieldsBot>,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |bot: &mut Bot| {
+            for field in &remove {
+                // New tokens are provided through the partial object.
+                if let FieldsBot::InteractionsURL = field {
+                    bot.remove(field);
+                }
+            }
+
+            bot.apply_options(partial.clone());
+        })
+        .await
+    }
+
+    async fn delete_bot(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_bots_by_user(&self, user_id: &str) -> Result<Vec<Bot>> {
+        self.find_by_column(TABLE, "owner", user_id).await
+    }
+
+    async fn get_number_of_bots_by_user(&self, user_id: &str) -> Result<usize> {
+        sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM bots WHERE owner = $1")
+            .bind(user_id)
+            .fetch_one(&self.0)
+            .await
+            .map(|(count,)| count as usize)
+            .map_err(|_| Error::DatabaseError {
+                operation: "count_documents",
+                with: "bots",
+            })
+    }
+}
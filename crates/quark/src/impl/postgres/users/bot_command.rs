@@ -0,0 +1,37 @@
+use crate::models::BotCommand;
+use crate::{AbstractBotCommand, Error, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "bot_commands";
+
+#[async_trait]
+impl AbstractBotCommand for PostgresDb {
+    async fn insert_command(&self, command: &BotCommand) -> Result<()> {
+        self.insert_one(TABLE, command).await
+    }
+
+    async fn fetch_command(&self, id: &str) -> Result<BotCommand> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_commands(&self, bot: &str) -> Result<Vec<BotCommand>> {
+        self.find_by_column(TABLE, "bot", bot).await
+    }
+
+    async fn delete_command(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, id).await
+    }
+
+    async fn delete_commands_for_bot(&self, bot: &str) -> Result<()> {
+        sqlx::query("DELETE FROM bot_commands WHERE bot = $1")
+            .bind(bot)
+            .execute(&self.0)
+            .await
+            .map(|_| ())
+            .map_err(|_| Error::DatabaseError {
+                operation: "delete_many",
+                with: "bot_commands",
+            })
+    }
+}
@@ -0,0 +1,159 @@
+use sqlx::types::Json;
+
+use crate::models::user::{FieldsUser, PartialUser, Relationship, RelationshipStatus, User};
+use crate::{AbstractUser, Error, Result};
+
+use super::super::{Document, PostgresDb};
+
+static TABLE: &str = "users";
+
+impl PostgresDb {
+    async fn find_ids(&self, sql: &str, user_a: &str, user_b: &str) -> Result<Vec<String>> {
+        sqlx::query_as::<_, (String,)>(sql)
+            .bind(user_a)
+            .bind(user_b)
+            .fetch_all(&self.0)
+            .await
+            .map(|rows| rows.into_iter().map(|(id,)| id).collect())
+            .map_err(|_| Error::DatabaseError {
+                operation: "find",
+                with: "users",
+            })
+    }
+}
+
+#[async_trait]
+impl AbstractUser for PostgresDb {
+    async fn fetch_user(&self, id: &str) -> Result<User> {
+        self.find_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_user_by_username(&self, username: &str) -> Result<User> {
+        sqlx::query_as::<_, Document<User>>(
+            "SELECT data FROM users WHERE lower(username) = lower($1)",
+        )
+        .bind(username)
+        .fetch_optional(&self.0)
+        .await
+        .map_err(|_| Error::DatabaseError {
+            operation: "find_one",
+            with: "user",
+        })?
+        .map(|(Json(user),)| user)
+        .ok_or(Error::NotFound)
+    }
+
+    async fn fetch_user_by_token(&self, token: &str) -> Result<User> {
+        let (user_id,) =
+            sqlx::query_as::<_, (String,)>("SELECT user_id FROM sessions WHERE token = $1")
+                .bind(token)
+                .fetch_optional(&self.0)
+                .await
+                .map_err(|_| Error::DatabaseError {
+                    operation: "find_one",
+                    with: "session",
+                })?
+                .ok_or(Error::InvalidSession)?;
+
+        self.fetch_user(&user_id).await
+    }
+
+    async fn insert_user(&self, user: &User) -> Result<()> {
+        self.insert_one(TABLE, user).await
+    }
+
+    async fn update_user(
+        &self,
+        id: &str,
+        partial: &PartialUser,
+        remove: Vec<FieldsUser>,
+    ) -> Result<()> {
+        self.update_one_by_id(TABLE, id, |user: &mut User| {
+            for field in &remove {
+                user.remove(field);
+            }
+
+            user.apply_options(partial.clone());
+        })
+        .await
+    }
+
+    async fn delete_user(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, id).await
+    }
+
+    async fn fetch_users<'a>(&self, ids: &'a [String]) -> Result<Vec<User>> {
+        self.find_by_ids(TABLE, ids).await
+    }
+
+    async fn is_username_taken(&self, username: &str) -> Result<bool> {
+        match self.fetch_user_by_username(username).await {
+            Ok(_) => Ok(true),
+            Err(Error::NotFound) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn fetch_mutual_user_ids(&self, user_a: &str, user_b: &str) -> Result<Vec<String>> {
+        self.find_ids(
+            "SELECT id FROM users
+            WHERE data->'relations' @> jsonb_build_array(jsonb_build_object('_id', $1::text, 'status', 'Friend'))
+            AND data->'relations' @> jsonb_build_array(jsonb_build_object('_id', $2::text, 'status', 'Friend'))",
+            user_a,
+            user_b,
+        )
+        .await
+    }
+
+    async fn fetch_mutual_channel_ids(&self, user_a: &str, user_b: &str) -> Result<Vec<String>> {
+        self.find_ids(
+            "SELECT id FROM channels
+            WHERE channel_type IN ('Group', 'DirectMessage')
+            AND data->'recipients' ?& ARRAY[$1, $2]",
+            user_a,
+            user_b,
+        )
+        .await
+    }
+
+    async fn fetch_mutual_server_ids(&self, user_a: &str, user_b: &str) -> Result<Vec<String>> {
+        self.find_ids(
+            "SELECT a.server FROM server_members a
+            JOIN server_members b ON a.server = b.server
+            WHERE a.user_id = $1 AND b.user_id = $2",
+            user_a,
+            user_b,
+        )
+        .await
+    }
+
+    async fn set_relationship(
+        &self,
+        user_id: &str,
+        target_id: &str,
+        relationship: &RelationshipStatus,
+    ) -> Result<()> {
+        if let RelationshipStatus::None = relationship {
+            return self.pull_relationship(user_id, target_id).await;
+        }
+
+        self.update_one_by_id(TABLE, user_id, |user: &mut User| {
+            let relations = user.relations.get_or_insert_with(Vec::new);
+            relations.retain(|entry| entry.id != target_id);
+            relations.push(Relationship {
+                id: target_id.to_string(),
+                status: relationship.clone(),
+            });
+        })
+        .await
+    }
+
+    async fn pull_relationship(&self, user_id: &str, target_id: &str) -> Result<()> {
+        self.update_one_by_id(TABLE, user_id, |user: &mut User| {
+            if let Some(relations) = user.relations.as_mut() {
+                relations.retain(|entry| entry.id != target_id);
+            }
+        })
+        .await
+    }
+}
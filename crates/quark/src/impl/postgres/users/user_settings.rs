@@ -0,0 +1,44 @@
+use sqlx::types::Json;
+
+use crate::models::UserSettings;
+use crate::{AbstractUserSettings, Error, Result};
+
+use super::super::PostgresDb;
+
+static TABLE: &str = "user_settings";
+
+#[async_trait]
+impl AbstractUserSettings for PostgresDb {
+    async fn fetch_user_settings(&'_ self, id: &str, filter: &'_ [String]) -> Result<UserSettings> {
+        let settings: UserSettings = self.find_one_by_id(TABLE, id).await?;
+
+        Ok(filter
+            .iter()
+            .filter_map(|key| {
+                settings
+                    .get(key)
+                    .map(|value| (key.to_string(), value.clone()))
+            })
+            .collect())
+    }
+
+    async fn set_user_settings(&self, id: &str, settings: &UserSettings) -> Result<()> {
+        sqlx::query(
+            "INSERT INTO user_settings (id, data) VALUES ($1, $2)
+            ON CONFLICT (id) DO UPDATE SET data = user_settings.data || EXCLUDED.data",
+        )
+        .bind(id)
+        .bind(Json(settings))
+        .execute(&self.0)
+        .await
+        .map(|_| ())
+        .map_err(|_| Error::DatabaseError {
+            operation: "update_one",
+            with: "user_settings",
+        })
+    }
+
+    async fn delete_user_settings(&self, id: &str) -> Result<()> {
+        self.delete_one_by_id(TABLE, id).await
+    }
+}
@@ -0,0 +1,76 @@
+use crate::models::Job;
+use crate::{AbstractJobQueue, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractJobQueue for ReferenceDb {
+    async fn insert_job(&self, job: &Job) -> Result<()> {
+        let mut jobs = self.jobs.lock().await;
+        if jobs.contains_key(&job.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "job",
+            })
+        } else {
+            jobs.insert(job.id.to_string(), job.clone());
+            Ok(())
+        }
+    }
+
+    async fn claim_job(&self, queue: &str, now: i64, available_at: i64) -> Result<Option<Job>> {
+        let mut jobs = self.jobs.lock().await;
+        Ok(jobs
+            .values_mut()
+            .filter(|job| job.queue == queue && !job.dead && job.available_at <= now)
+            .min_by_key(|job| job.available_at)
+            .map(|job| {
+                job.available_at = available_at;
+                job.attempts += 1;
+                job.clone()
+            }))
+    }
+
+    async fn complete_job(&self, id: &str) -> Result<()> {
+        self.jobs.lock().await.remove(id);
+        Ok(())
+    }
+
+    async fn retry_job(&self, id: &str, available_at: i64, error: &str) -> Result<()> {
+        if let Some(job) = self.jobs.lock().await.get_mut(id) {
+            job.available_at = available_at;
+            job.last_error = Some(error.to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn bury_job(&self, id: &str, error: &str) -> Result<()> {
+        if let Some(job) = self.jobs.lock().await.get_mut(id) {
+            job.dead = true;
+            job.last_error = Some(error.to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_dead_jobs(&self, queue: &str) -> Result<Vec<Job>> {
+        let jobs = self.jobs.lock().await;
+        let mut dead = jobs
+            .values()
+            .filter(|job| job.queue == queue && job.dead)
+            .cloned()
+            .collect::<Vec<Job>>();
+
+        dead.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(dead)
+    }
+
+    async fn count_jobs(&self, queue: &str) -> Result<usize> {
+        let jobs = self.jobs.lock().await;
+        Ok(jobs
+            .values()
+            .filter(|job| job.queue == queue && !job.dead)
+            .count())
+    }
+}
@@ -0,0 +1,11 @@
+use crate::{AbstractMigrations, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractMigrations for ReferenceDb {
+    async fn migrate_database(&self) -> Result<()> {
+        info!("Migrations not required for in-memory database.");
+        Ok(())
+    }
+}
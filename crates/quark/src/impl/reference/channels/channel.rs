@@ -0,0 +1,247 @@
+use crate::models::channel::{Channel, FieldsChannel, PartialChannel};
+use crate::models::Invite;
+use crate::{AbstractChannel, Error, OverrideField, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractChannel for ReferenceDb {
+    async fn fetch_channel(&self, id: &str) -> Result<Channel> {
+        let channels = self.channels.lock().await;
+        channels.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn fetch_channels<'a>(&self, ids: &'a [String]) -> Result<Vec<Channel>> {
+        let channels = self.channels.lock().await;
+        Ok(ids
+            .iter()
+            .filter_map(|id| channels.get(id).cloned())
+            .collect())
+    }
+
+    async fn insert_channel(&self, channel: &Channel) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+        if channels.contains_key(channel.id()) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "channel",
+            })
+        } else {
+            channels.insert(channel.id().to_string(), channel.clone());
+            Ok(())
+        }
+    }
+
+    async fn update_channel(
+        &self,
+        id: &str,
+        partial: &PartialChannel,
+        remove: Vec<FieldsChannel>,
+    ) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+        if let Some(channel) = channels.get_mut(id) {
+            for field in &remove {
+                channel.remove(field);
+            }
+
+            channel.apply_options(partial.clone());
+
+            // Not covered by apply_options as clients never receive it in updates.
+            if let Some(message_id) = &partial.last_message_id {
+                match channel {
+                    Channel::DirectMessage {
+                        last_message_id, ..
+                    }
+                    | Channel::Group {
+                        last_message_id, ..
+                    }
+                    | Channel::TextChannel {
+                        last_message_id, ..
+                    }
+                    | Channel::Thread {
+                        last_message_id, ..
+                    } => {
+                        last_message_id.replace(message_id.to_string());
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn update_last_message_id(
+        &self,
+        id: &str,
+        message_id: &str,
+        activate: bool,
+    ) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+        if let Some(channel) = channels.get_mut(id) {
+            channel.advance_last_message_id(message_id, activate);
+        }
+
+        Ok(())
+    }
+
+    async fn delete_channel(&self, channel: &Channel) -> Result<()> {
+        let id = channel.id().to_string();
+
+        // Delete threads started in this channel.
+        for thread in self.fetch_threads(&[id.clone()]).await? {
+            self.delete_channel(&thread).await?;
+        }
+
+        // Delete invites, unreads and webhooks.
+        self.channel_invites
+            .lock()
+            .await
+            .retain(|_, invite| match invite {
+                Invite::Server { channel, .. } | Invite::Group { channel, .. } => channel != &id,
+            });
+
+        self.channel_unreads
+            .lock()
+            .await
+            .retain(|(channel, _), _| channel != &id);
+
+        self.webhooks
+            .lock()
+            .await
+            .retain(|_, webhook| webhook.channel_id != id);
+
+        // Delete messages.
+        self.delete_bulk_messages(|message| message.channel == id)
+            .await;
+
+        // Remove from server object.
+        if let Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } = channel
+        {
+            let mut servers = self.servers.lock().await;
+            let server = servers.get_mut(server).ok_or(Error::NotFound)?;
+            server.channels.retain(|channel| channel != &id);
+
+            if let Some(sys) = &mut server.system_messages {
+                for cid in [
+                    &mut sys.user_joined,
+                    &mut sys.user_left,
+                    &mut sys.user_kicked,
+                    &mut sys.user_banned,
+                ] {
+                    if cid.as_ref() == Some(&id) {
+                        cid.take();
+                    }
+                }
+            }
+        }
+
+        // Delete associated attachments
+        for file in self.attachments.lock().await.values_mut() {
+            if file.object_id.as_ref() == Some(&id) {
+                file.deleted = Some(true);
+            }
+        }
+
+        // Delete the channel itself
+        self.channels.lock().await.remove(&id);
+        Ok(())
+    }
+
+    async fn fetch_threads(&self, parents: &[String]) -> Result<Vec<Channel>> {
+        let channels = self.channels.lock().await;
+        Ok(channels
+            .values()
+            .filter(|channel| {
+                matches!(channel, Channel::Thread { parent, .. } if parents.contains(parent))
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_direct_messages(&self, user_id: &str) -> Result<Vec<Channel>> {
+        let channels = self.channels.lock().await;
+        Ok(channels
+            .values()
+            .filter(|channel| match channel {
+                Channel::DirectMessage { recipients, .. } | Channel::Group { recipients, .. } => {
+                    recipients.iter().any(|id| id == user_id)
+                }
+                Channel::SavedMessages { user, .. } => user == user_id,
+                _ => false,
+            })
+            .cloned()
+            .collect())
+    }
+
+    async fn find_saved_messages_channel(&self, user_id: &str) -> Result<Channel> {
+        let channels = self.channels.lock().await;
+        channels
+            .values()
+            .find(
+                |channel| matches!(channel, Channel::SavedMessages { user, .. } if user == user_id),
+            )
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    async fn find_direct_message_channel(&self, user_a: &str, user_b: &str) -> Result<Channel> {
+        if user_a == user_b {
+            return self.find_saved_messages_channel(user_a).await;
+        }
+
+        let channels = self.channels.lock().await;
+        channels
+            .values()
+            .find(|channel| {
+                if let Channel::DirectMessage { recipients, .. } = channel {
+                    recipients.iter().any(|id| id == user_a)
+                        && recipients.iter().any(|id| id == user_b)
+                } else {
+                    false
+                }
+            })
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    async fn add_user_to_group(&self, channel: &str, user: &str) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+        if let Some(Channel::Group { recipients, .. }) = channels.get_mut(channel) {
+            recipients.push(user.to_string());
+        }
+
+        Ok(())
+    }
+
+    async fn remove_user_from_group(&self, channel: &str, user: &str) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+        if let Some(Channel::Group { recipients, .. }) = channels.get_mut(channel) {
+            recipients.retain(|id| id != user);
+        }
+
+        Ok(())
+    }
+
+    async fn set_channel_role_permission(
+        &self,
+        channel: &str,
+        role: &str,
+        permissions: OverrideField,
+    ) -> Result<()> {
+        let mut channels = self.channels.lock().await;
+        if let Some(
+            Channel::TextChannel {
+                role_permissions, ..
+            }
+            | Channel::VoiceChannel {
+                role_permissions, ..
+            },
+        ) = channels.get_mut(channel)
+        {
+            role_permissions.insert(role.to_string(), permissions);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,39 @@
+use crate::models::Invite;
+use crate::{AbstractChannelInvite, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractChannelInvite for ReferenceDb {
+    async fn fetch_invite(&self, code: &str) -> Result<Invite> {
+        let invites = self.channel_invites.lock().await;
+        invites.get(code).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn insert_invite(&self, invite: &Invite) -> Result<()> {
+        let mut invites = self.channel_invites.lock().await;
+        if invites.contains_key(invite.code()) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "invite",
+            })
+        } else {
+            invites.insert(invite.code().to_string(), invite.clone());
+            Ok(())
+        }
+    }
+
+    async fn delete_invite(&self, code: &str) -> Result<()> {
+        self.channel_invites.lock().await.remove(code);
+        Ok(())
+    }
+
+    async fn fetch_invites_for_server(&self, server_id: &str) -> Result<Vec<Invite>> {
+        let invites = self.channel_invites.lock().await;
+        Ok(invites
+            .values()
+            .filter(|invite| matches!(invite, Invite::Server { server, .. } if server == server_id))
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,83 @@
+use ulid::Ulid;
+
+use crate::models::channel_unread::{ChannelCompositeKey, ChannelUnread};
+use crate::{AbstractChannelUnread, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractChannelUnread for ReferenceDb {
+    async fn acknowledge_message(&self, channel: &str, user: &str, message: &str) -> Result<()> {
+        let mut unreads = self.channel_unreads.lock().await;
+        let unread = unreads
+            .entry((channel.to_string(), user.to_string()))
+            .or_insert_with(|| ChannelUnread {
+                id: ChannelCompositeKey {
+                    channel: channel.to_string(),
+                    user: user.to_string(),
+                },
+                last_id: None,
+                mentions: None,
+            });
+
+        unread.mentions = None;
+        unread.last_id = Some(message.to_string());
+        Ok(())
+    }
+
+    async fn acknowledge_channels(&self, user: &str, channels: &[String]) -> Result<()> {
+        let current_time = Ulid::new().to_string();
+
+        let mut unreads = self.channel_unreads.lock().await;
+        for channel in channels {
+            unreads.insert(
+                (channel.to_string(), user.to_string()),
+                ChannelUnread {
+                    id: ChannelCompositeKey {
+                        channel: channel.to_string(),
+                        user: user.to_string(),
+                    },
+                    last_id: Some(current_time.clone()),
+                    mentions: None,
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn add_mention_to_unread<'a>(
+        &self,
+        channel: &str,
+        user: &str,
+        ids: &[String],
+    ) -> Result<()> {
+        let mut unreads = self.channel_unreads.lock().await;
+        let unread = unreads
+            .entry((channel.to_string(), user.to_string()))
+            .or_insert_with(|| ChannelUnread {
+                id: ChannelCompositeKey {
+                    channel: channel.to_string(),
+                    user: user.to_string(),
+                },
+                last_id: None,
+                mentions: None,
+            });
+
+        unread
+            .mentions
+            .get_or_insert_with(Vec::new)
+            .extend(ids.iter().cloned());
+
+        Ok(())
+    }
+
+    async fn fetch_unreads(&self, user: &str) -> Result<Vec<ChannelUnread>> {
+        let unreads = self.channel_unreads.lock().await;
+        Ok(unreads
+            .values()
+            .filter(|unread| unread.id.user == user)
+            .cloned()
+            .collect())
+    }
+}
@@ -0,0 +1,438 @@
+use crate::models::message::{AppendMessage, Message, MessageFilter, MessageSort, PartialMessage};
+use crate::models::DeletedMessage;
+use crate::{AbstractMessage, Error, Result};
+
+use super::super::ReferenceDb;
+
+impl ReferenceDb {
+    /// Permanently delete all messages matching a predicate, including any awaiting purge
+    pub async fn delete_bulk_messages<F>(&self, predicate: F)
+    where
+        F: Fn(&Message) -> bool + Send + Sync,
+    {
+        let mut messages = self.messages.lock().await;
+        let mut deleted_messages = self.deleted_messages.lock().await;
+        let ids = messages
+            .values()
+            .chain(deleted_messages.values().map(|deleted| &deleted.message))
+            .filter(|&message| predicate(message))
+            .map(|message| message.id.to_string())
+            .collect::<Vec<String>>();
+
+        if ids.is_empty() {
+            return;
+        }
+
+        self.finalise_messages(&ids).await;
+
+        for id in ids {
+            messages.remove(&id);
+            deleted_messages.remove(&id);
+        }
+    }
+
+    /// Mark attachments on the given messages as deleted and drop their revisions
+    async fn finalise_messages(&self, ids: &[String]) {
+        for file in self.attachments.lock().await.values_mut() {
+            if let Some(id) = &file.message_id {
+                if ids.contains(id) {
+                    file.deleted = Some(true);
+                }
+            }
+        }
+
+        self.message_revisions
+            .lock()
+            .await
+            .retain(|_, revision| !ids.contains(&revision.message));
+    }
+
+    /// Move all messages matching a predicate into tombstones
+    async fn tombstone_messages<F>(&self, predicate: F, deleted_at: i64)
+    where
+        F: Fn(&Message) -> bool + Send + Sync,
+    {
+        let mut messages = self.messages.lock().await;
+        let ids = messages
+            .values()
+            .filter(|&message| predicate(message))
+            .map(|message| message.id.to_string())
+            .collect::<Vec<String>>();
+
+        let mut deleted_messages = self.deleted_messages.lock().await;
+        for id in ids {
+            if let Some(message) = messages.remove(&id) {
+                deleted_messages.insert(
+                    id.clone(),
+                    DeletedMessage {
+                        id,
+                        channel: message.channel.to_string(),
+                        deleted_at,
+                        message,
+                    },
+                );
+            }
+        }
+    }
+
+    /// Collect messages in a channel sorted by id in ascending order
+    async fn fetch_sorted_messages(&self, channel: &str) -> Vec<Message> {
+        let messages = self.messages.lock().await;
+        let mut list = messages
+            .values()
+            .filter(|message| message.channel == channel)
+            .cloned()
+            .collect::<Vec<Message>>();
+
+        list.sort_by(|a, b| a.id.cmp(&b.id));
+        list
+    }
+}
+
+#[async_trait]
+impl AbstractMessage for ReferenceDb {
+    async fn fetch_message(&self, id: &str) -> Result<Message> {
+        let messages = self.messages.lock().await;
+        messages.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn insert_message(&self, message: &Message) -> Result<()> {
+        let mut messages = self.messages.lock().await;
+        if messages.contains_key(&message.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "message",
+            })
+        } else {
+            messages.insert(message.id.to_string(), message.clone());
+            Ok(())
+        }
+    }
+
+    async fn update_message(&self, id: &str, partial: &PartialMessage) -> Result<()> {
+        let mut messages = self.messages.lock().await;
+        if let Some(message) = messages.get_mut(id) {
+            message.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn append_message(&self, id: &str, append: &AppendMessage) -> Result<()> {
+        let mut messages = self.messages.lock().await;
+        if let Some(message) = messages.get_mut(id) {
+            if let Some(embeds) = &append.embeds {
+                if !embeds.is_empty() {
+                    message
+                        .embeds
+                        .get_or_insert_with(Vec::new)
+                        .extend(embeds.iter().cloned());
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn delete_message(&self, id: &str, deleted_at: i64) -> Result<()> {
+        self.tombstone_messages(|message| message.id == id, deleted_at)
+            .await;
+
+        Ok(())
+    }
+
+    async fn delete_messages(
+        &self,
+        channel: &str,
+        ids: Vec<String>,
+        deleted_at: i64,
+    ) -> Result<()> {
+        self.tombstone_messages(
+            |message| message.channel == channel && ids.contains(&message.id),
+            deleted_at,
+        )
+        .await;
+
+        Ok(())
+    }
+
+    async fn fetch_deleted_messages(
+        &self,
+        channel: &str,
+        limit: i64,
+    ) -> Result<Vec<DeletedMessage>> {
+        let deleted_messages = self.deleted_messages.lock().await;
+        let mut list = deleted_messages
+            .values()
+            .filter(|deleted| deleted.channel == channel)
+            .cloned()
+            .collect::<Vec<DeletedMessage>>();
+
+        list.sort_by(|a, b| (b.deleted_at, &b.id).cmp(&(a.deleted_at, &a.id)));
+        list.truncate(limit.max(0) as usize);
+        Ok(list)
+    }
+
+    async fn restore_message(
+        &self,
+        channel: &str,
+        id: &str,
+        deleted_after: i64,
+    ) -> Result<Message> {
+        let mut messages = self.messages.lock().await;
+        let mut deleted_messages = self.deleted_messages.lock().await;
+        if deleted_messages.get(id).map_or(true, |deleted| {
+            deleted.channel != channel || deleted.deleted_at < deleted_after
+        }) {
+            return Err(Error::NotFound);
+        }
+
+        let DeletedMessage { message, .. } = deleted_messages.remove(id).ok_or(Error::NotFound)?;
+
+        messages.insert(id.to_string(), message.clone());
+        Ok(message)
+    }
+
+    async fn purge_deleted_messages(&self, before: i64) -> Result<()> {
+        let mut deleted_messages = self.deleted_messages.lock().await;
+        let ids = deleted_messages
+            .values()
+            .filter(|deleted| deleted.deleted_at < before)
+            .map(|deleted| deleted.id.to_string())
+            .collect::<Vec<String>>();
+
+        if ids.is_empty() {
+            return Ok(());
+        }
+
+        self.finalise_messages(&ids).await;
+
+        for id in ids {
+            deleted_messages.remove(&id);
+        }
+
+        Ok(())
+    }
+
+    async fn fetch_messages(
+        &self,
+        channel: &str,
+        limit: Option<i64>,
+        before: Option<String>,
+        after: Option<String>,
+        sort: Option<MessageSort>,
+        nearby: Option<String>,
+    ) -> Result<Vec<Message>> {
+        let limit = limit.unwrap_or(50).max(0) as usize;
+        let messages = self.fetch_sorted_messages(channel).await;
+
+        Ok(if let Some(nearby) = nearby {
+            let a = messages
+                .iter()
+                .filter(|message| message.id >= nearby)
+                .take(limit / 2 + 1);
+
+            let b = messages
+                .iter()
+                .rev()
+                .filter(|message| message.id < nearby)
+                .take(limit / 2);
+
+            a.chain(b).cloned().collect()
+        } else {
+            // Same as the MongoDB implementation, `after` takes precedence over `before`.
+            let filtered = messages.into_iter().filter(|message| {
+                if let Some(after) = &after {
+                    &message.id > after
+                } else if let Some(before) = &before {
+                    &message.id < before
+                } else {
+                    true
+                }
+            });
+
+            if let MessageSort::Latest = sort.unwrap_or(MessageSort::Latest) {
+                filtered.rev().take(limit).collect()
+            } else {
+                filtered.take(limit).collect()
+            }
+        })
+    }
+
+    async fn fetch_pinned_messages(&self, channel: &str) -> Result<Vec<Message>> {
+        Ok(self
+            .fetch_sorted_messages(channel)
+            .await
+            .into_iter()
+            .rev()
+            .filter(|message| message.pinned)
+            .collect())
+    }
+
+    async fn pin_message(&self, channel: &str, id: &str, max: usize) -> Result<()> {
+        let mut messages = self.messages.lock().await;
+        let pinned = messages
+            .values()
+            .filter(|message| message.channel == channel && message.pinned)
+            .count();
+
+        if let Some(message) = messages.get_mut(id) {
+            if !message.pinned {
+                if pinned >= max {
+                    return Err(Error::TooManyPins { max });
+                }
+
+                message.pinned = true;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn search_messages(
+        &self,
+        channels: &[String],
+        filter: &MessageFilter,
+        limit: Option<i64>,
+        sort: MessageSort,
+    ) -> Result<Vec<Message>> {
+        let limit = limit.unwrap_or(50).max(0) as usize;
+        let terms = filter.query.as_ref().map(|query| {
+            query
+                .to_lowercase()
+                .split_whitespace()
+                .map(|term| term.to_string())
+                .collect::<Vec<String>>()
+        });
+
+        let mut messages = self
+            .messages
+            .lock()
+            .await
+            .values()
+            .filter(|message| channels.contains(&message.channel))
+            .cloned()
+            .collect::<Vec<Message>>();
+
+        messages.sort_by(|a, b| a.id.cmp(&b.id));
+
+        // Approximate text search by counting how many terms appear in the content.
+        let mut scored = messages
+            .into_iter()
+            .filter(|message| {
+                filter
+                    .author
+                    .as_ref()
+                    .map_or(true, |author| &message.author == author)
+            })
+            .filter(|message| {
+                filter.mention.as_ref().map_or(true, |mention| {
+                    message
+                        .mentions
+                        .as_ref()
+                        .map_or(false, |mentions| mentions.contains(mention))
+                })
+            })
+            .filter(|message| {
+                !filter.has_attachment
+                    || message
+                        .attachments
+                        .as_ref()
+                        .map_or(false, |attachments| !attachments.is_empty())
+            })
+            .filter(|message| {
+                !filter.has_embed
+                    || message
+                        .embeds
+                        .as_ref()
+                        .map_or(false, |embeds| !embeds.is_empty())
+            })
+            .filter(|message| {
+                !filter.has_link
+                    || message.content.as_ref().map_or(false, |content| {
+                        let content = content.to_lowercase();
+                        content.contains("http://") || content.contains("https://")
+                    })
+            })
+            .filter(|message| !filter.pinned || message.pinned)
+            .filter(|message| {
+                filter
+                    .before
+                    .as_ref()
+                    .map_or(true, |before| &message.id < before)
+            })
+            .filter(|message| {
+                filter
+                    .after
+                    .as_ref()
+                    .map_or(true, |after| &message.id > after)
+            })
+            .filter_map(|message| {
+                let terms = match &terms {
+                    Some(terms) => terms,
+                    None => return Some((0, message)),
+                };
+
+                let content = message.content.as_ref()?.to_lowercase();
+                let score = terms
+                    .iter()
+                    .filter(|term| content.contains(term.as_str()))
+                    .count();
+
+                if score > 0 {
+                    Some((score, message))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<(usize, Message)>>();
+
+        match sort {
+            MessageSort::Relevance if terms.is_some() => scored.sort_by(|(a, _), (b, _)| b.cmp(a)),
+            MessageSort::Relevance | MessageSort::Latest => scored.reverse(),
+            MessageSort::Oldest => {}
+        }
+
+        Ok(scored
+            .into_iter()
+            .take(limit)
+            .map(|(_, message)| message)
+            .collect())
+    }
+
+    /// Add a new reaction to a message
+    async fn add_reaction(&self, id: &str, emoji: &str, user: &str) -> Result<()> {
+        let mut messages = self.messages.lock().await;
+        if let Some(message) = messages.get_mut(id) {
+            message
+                .reactions
+                .entry(emoji.to_string())
+                .or_default()
+                .insert(user.to_string());
+        }
+
+        Ok(())
+    }
+
+    /// Remove a reaction from a message
+    async fn remove_reaction(&self, id: &str, emoji: &str, user: &str) -> Result<()> {
+        let mut messages = self.messages.lock().await;
+        if let Some(message) = messages.get_mut(id) {
+            if let Some(users) = message.reactions.get_mut(emoji) {
+                users.shift_remove(user);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove reaction from a message
+    async fn clear_reaction(&self, id: &str, emoji: &str) -> Result<()> {
+        let mut messages = self.messages.lock().await;
+        if let Some(message) = messages.get_mut(id) {
+            message.reactions.shift_remove(emoji);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use crate::models::MessageRevision;
+use crate::{AbstractMessageRevision, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractMessageRevision for ReferenceDb {
+    async fn insert_message_revision(&self, revision: &MessageRevision) -> Result<()> {
+        let mut revisions = self.message_revisions.lock().await;
+        if revisions.contains_key(&revision.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "message_revisions",
+            })
+        } else {
+            revisions.insert(revision.id.to_string(), revision.clone());
+            Ok(())
+        }
+    }
+
+    async fn fetch_message_revisions(&self, message: &str) -> Result<Vec<MessageRevision>> {
+        let revisions = self.message_revisions.lock().await;
+        let mut list = revisions
+            .values()
+            .filter(|revision| revision.message == message)
+            .cloned()
+            .collect::<Vec<MessageRevision>>();
+
+        list.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(list)
+    }
+
+    async fn delete_message_revisions_before(&self, message: &str, before: &str) -> Result<()> {
+        self.message_revisions
+            .lock()
+            .await
+            .retain(|_, revision| revision.message != message || revision.id.as_str() >= before);
+
+        Ok(())
+    }
+
+    async fn fetch_message_revision_channels(&self) -> Result<Vec<String>> {
+        let revisions = self.message_revisions.lock().await;
+        let mut channels = revisions
+            .values()
+            .map(|revision| revision.channel.clone())
+            .collect::<Vec<String>>();
+
+        channels.sort();
+        channels.dedup();
+        Ok(channels)
+    }
+
+    async fn delete_channel_message_revisions_before(
+        &self,
+        channel: &str,
+        before: &str,
+    ) -> Result<()> {
+        self.message_revisions
+            .lock()
+            .await
+            .retain(|_, revision| revision.channel != channel || revision.id.as_str() >= before);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use crate::models::webhook::{FieldsWebhook, PartialWebhook, Webhook};
+use crate::{AbstractWebhook, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractWebhook for ReferenceDb {
+    async fn insert_webhook(&self, webhook: &Webhook) -> Result<()> {
+        let mut webhooks = self.webhooks.lock().await;
+        if webhooks.contains_key(&webhook.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "webhook",
+            })
+        } else {
+            webhooks.insert(webhook.id.to_string(), webhook.clone());
+            Ok(())
+        }
+    }
+
+    async fn fetch_webhook(&self, id: &str) -> Result<Webhook> {
+        let webhooks = self.webhooks.lock().await;
+        webhooks.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn fetch_webhooks_for_channel(&self, channel: &str) -> Result<Vec<Webhook>> {
+        let webhooks = self.webhooks.lock().await;
+        Ok(webhooks
+            .values()
+            .filter(|webhook| webhook.channel_id == channel)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_webhook(
+        &self,
+        id: &str,
+        partial: &PartialWebhook,
+        remove: Vec<FieldsWebhook>,
+    ) -> Result<()> {
+        let mut webhooks = self.webhooks.lock().await;
+        if let Some(webhook) = webhooks.get_mut(id) {
+            for field in &remove {
+                webhook.remove(field);
+            }
+
+            webhook.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_webhook(&self, id: &str) -> Result<()> {
+        self.webhooks.lock().await.remove(id);
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::models::attachment::File;
+use crate::{AbstractAttachment, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractAttachment for ReferenceDb {
+    async fn find_and_use_attachment(
+        &self,
+        id: &str,
+        tag: &str,
+        parent_type: &str,
+        parent_id: &str,
+    ) -> Result<File> {
+        let mut attachments = self.attachments.lock().await;
+        if let Some(file) = attachments.get_mut(id) {
+            if file.tag != tag {
+                return Err(Error::UnknownAttachment);
+            }
+
+            let key = match parent_type {
+                "message" => &mut file.message_id,
+                "user" => &mut file.user_id,
+                "server" => &mut file.server_id,
+                "object" => &mut file.object_id,
+                _ => return Err(Error::InvalidOperation),
+            };
+
+            if key.is_some() {
+                return Err(Error::UnknownAttachment);
+            }
+
+            key.replace(parent_id.to_string());
+            Ok(file.clone())
+        } else {
+            Err(Error::UnknownAttachment)
+        }
+    }
+
+    async fn insert_attachment(&self, attachment: &File) -> Result<()> {
+        let mut attachments = self.attachments.lock().await;
+        if attachments.contains_key(&attachment.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "attachment",
+            })
+        } else {
+            attachments.insert(attachment.id.to_string(), attachment.clone());
+            Ok(())
+        }
+    }
+
+    async fn mark_attachment_as_reported(&self, id: &str) -> Result<()> {
+        let mut attachments = self.attachments.lock().await;
+        if let Some(file) = attachments.get_mut(id) {
+            file.reported = Some(true);
+        }
+
+        Ok(())
+    }
+
+    async fn mark_attachment_as_deleted(&self, id: &str) -> Result<()> {
+        let mut attachments = self.attachments.lock().await;
+        if let Some(file) = attachments.get_mut(id) {
+            file.deleted = Some(true);
+        }
+
+        Ok(())
+    }
+
+    async fn mark_attachments_as_deleted(&self, ids: &[String]) -> Result<()> {
+        let mut attachments = self.attachments.lock().await;
+        for id in ids {
+            if let Some(file) = attachments.get_mut(id) {
+                file.deleted = Some(true);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,62 @@
+use crate::models::emoji::EmojiParent;
+use crate::models::Emoji;
+use crate::{AbstractEmoji, Error, Result};
+
+use super::super::ReferenceDb;
+
+/// Check whether an emoji belongs to any of the given parents
+fn has_parent(emoji: &Emoji, parent_ids: &[String]) -> bool {
+    match &emoji.parent {
+        EmojiParent::Server { id } => parent_ids.contains(id),
+        EmojiParent::Detached => false,
+    }
+}
+
+#[async_trait]
+impl AbstractEmoji for ReferenceDb {
+    /// Fetch an emoji by its id
+    async fn fetch_emoji(&self, id: &str) -> Result<Emoji> {
+        let emojis = self.emojis.lock().await;
+        emojis.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    /// Fetch emoji by their ids
+    async fn fetch_emoji_by_parent_id(&self, parent_id: &str) -> Result<Vec<Emoji>> {
+        self.fetch_emoji_by_parent_ids(&[parent_id.to_string()])
+            .await
+    }
+
+    /// Fetch emoji by their parent ids
+    async fn fetch_emoji_by_parent_ids(&self, parent_ids: &[String]) -> Result<Vec<Emoji>> {
+        let emojis = self.emojis.lock().await;
+        Ok(emojis
+            .values()
+            .filter(|emoji| has_parent(emoji, parent_ids))
+            .cloned()
+            .collect())
+    }
+
+    /// Insert emoji into database.
+    async fn insert_emoji(&self, emoji: &Emoji) -> Result<()> {
+        let mut emojis = self.emojis.lock().await;
+        if emojis.contains_key(&emoji.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "emoji",
+            })
+        } else {
+            emojis.insert(emoji.id.to_string(), emoji.clone());
+            Ok(())
+        }
+    }
+
+    /// Detach an emoji by its id
+    async fn detach_emoji(&self, emoji: &Emoji) -> Result<()> {
+        let mut emojis = self.emojis.lock().await;
+        if let Some(emoji) = emojis.get_mut(&emoji.id) {
+            emoji.parent = EmojiParent::Detached;
+        }
+
+        Ok(())
+    }
+}
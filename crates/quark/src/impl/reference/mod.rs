@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::lock::Mutex;
+
+use crate::models::{
+    channel_unread::ChannelUnread, AuditLogEntry, AutomodRule, Bot, BotCommand, Channel,
+    DeletedMessage, Emoji, File, Invite, Job, Member, Message, MessageRevision, Server, ServerBan,
+    User, UserSettings, Webhook,
+};
+use crate::AbstractDatabase;
+
+pub mod admin {
+    pub mod job;
+    pub mod migrations;
+}
+
+pub mod media {
+    pub mod attachment;
+    pub mod emoji;
+}
+
+pub mod channels {
+    pub mod channel;
+    pub mod channel_invite;
+    pub mod channel_unread;
+    pub mod message;
+    pub mod message_revision;
+    pub mod webhook;
+}
+
+pub mod servers {
+    pub mod audit_log;
+    pub mod automod_rule;
+    pub mod server;
+    pub mod server_ban;
+    pub mod server_member;
+}
+
+pub mod users {
+    pub mod bot;
+    pub mod bot_command;
+    pub mod user;
+    pub mod user_settings;
+}
+
+/// Composite key used for collections keyed by (server / channel, user)
+pub type CompositeKey = (String, String);
+
+/// In-memory collection shared between all clones of the database
+pub type Collection<K, T> = Arc<Mutex<HashMap<K, T>>>;
+
+/// Stateful in-memory reference implementation of the database
+///
+/// Every clone refers to the same underlying collections,
+/// behaviour should match [super::MongoDb] as closely as possible.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceDb {
+    pub audit_log: Collection<String, AuditLogEntry>,
+    pub automod_rules: Collection<String, AutomodRule>,
+    pub bots: Collection<String, Bot>,
+    pub bot_commands: Collection<String, BotCommand>,
+    pub channels: Collection<String, Channel>,
+    pub channel_invites: Collection<String, Invite>,
+    pub channel_unreads: Collection<CompositeKey, ChannelUnread>,
+    pub deleted_messages: Collection<String, DeletedMessage>,
+    pub messages: Collection<String, Message>,
+    pub message_revisions: Collection<String, MessageRevision>,
+    pub attachments: Collection<String, File>,
+    pub emojis: Collection<String, Emoji>,
+    pub jobs: Collection<String, Job>,
+    pub servers: Collection<String, Server>,
+    pub server_bans: Collection<CompositeKey, ServerBan>,
+    pub server_members: Collection<CompositeKey, Member>,
+    pub users: Collection<String, User>,
+    pub user_settings: Collection<String, UserSettings>,
+    pub webhooks: Collection<String, Webhook>,
+    /// Session token to user id mapping
+    ///
+    /// Normally owned by rAuth, populate this through [`ReferenceDb::insert_session`]
+    /// so that `fetch_user_by_token` (and in turn `User::from_token`) can authenticate.
+    pub sessions: Collection<String, String>,
+}
+
+impl ReferenceDb {
+    /// Register a session token for a given user
+    pub async fn insert_session(&self, token: &str, user_id: &str) {
+        self.sessions
+            .lock()
+            .await
+            .insert(token.to_string(), user_id.to_string());
+    }
+}
+
+impl AbstractDatabase for ReferenceDb {}
@@ -0,0 +1,44 @@
+use crate::models::audit_log::AuditLogAction;
+use crate::models::AuditLogEntry;
+use crate::{AbstractAuditLog, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractAuditLog for ReferenceDb {
+    async fn insert_audit_log_entry(&self, entry: &AuditLogEntry) -> Result<()> {
+        let mut audit_log = self.audit_log.lock().await;
+        if audit_log.contains_key(&entry.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "audit_log",
+            })
+        } else {
+            audit_log.insert(entry.id.to_string(), entry.clone());
+            Ok(())
+        }
+    }
+
+    async fn fetch_audit_log(
+        &self,
+        server: &str,
+        limit: i64,
+        before: Option<String>,
+        actor: Option<String>,
+        action: Option<AuditLogAction>,
+    ) -> Result<Vec<AuditLogEntry>> {
+        let audit_log = self.audit_log.lock().await;
+        let mut entries = audit_log
+            .values()
+            .filter(|entry| entry.server == server)
+            .filter(|entry| before.as_ref().map_or(true, |before| &entry.id < before))
+            .filter(|entry| actor.as_ref().map_or(true, |actor| &entry.actor == actor))
+            .filter(|entry| action.map_or(true, |action| entry.action == action))
+            .cloned()
+            .collect::<Vec<AuditLogEntry>>();
+
+        entries.sort_by(|a, b| b.id.cmp(&a.id));
+        entries.truncate(limit.max(0) as usize);
+        Ok(entries)
+    }
+}
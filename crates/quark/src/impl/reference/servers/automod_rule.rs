@@ -0,0 +1,48 @@
+use crate::models::automod_rule::{AutomodRule, PartialAutomodRule};
+use crate::{AbstractAutomodRule, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractAutomodRule for ReferenceDb {
+    async fn insert_automod_rule(&self, rule: &AutomodRule) -> Result<()> {
+        let mut automod_rules = self.automod_rules.lock().await;
+        if automod_rules.contains_key(&rule.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "automod_rule",
+            })
+        } else {
+            automod_rules.insert(rule.id.to_string(), rule.clone());
+            Ok(())
+        }
+    }
+
+    async fn fetch_automod_rule(&self, id: &str) -> Result<AutomodRule> {
+        let automod_rules = self.automod_rules.lock().await;
+        automod_rules.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn fetch_automod_rules(&self, server: &str) -> Result<Vec<AutomodRule>> {
+        let automod_rules = self.automod_rules.lock().await;
+        Ok(automod_rules
+            .values()
+            .filter(|rule| rule.server == server)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_automod_rule(&self, id: &str, partial: &PartialAutomodRule) -> Result<()> {
+        let mut automod_rules = self.automod_rules.lock().await;
+        if let Some(rule) = automod_rules.get_mut(id) {
+            rule.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_automod_rule(&self, id: &str) -> Result<()> {
+        self.automod_rules.lock().await.remove(id);
+        Ok(())
+    }
+}
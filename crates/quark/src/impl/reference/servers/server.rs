@@ -0,0 +1,200 @@
+use crate::models::emoji::EmojiParent;
+use crate::models::server::{FieldsRole, FieldsServer, PartialRole, PartialServer, Role, Server};
+use crate::models::{Channel, Invite};
+use crate::{AbstractChannel, AbstractServer, Error, Result};
+
+use super::super::ReferenceDb;
+
+impl ReferenceDb {
+    /// Delete all objects that belong to a server
+    pub async fn delete_associated_server_objects(&self, server: &Server) {
+        // Delete all messages.
+        self.delete_bulk_messages(|message| server.channels.contains(&message.channel))
+            .await;
+
+        // Delete all emoji.
+        self.emojis.lock().await.retain(
+            |_, emoji| !matches!(&emoji.parent, EmojiParent::Server { id } if id == &server.id),
+        );
+
+        // Delete all channels.
+        self.channels
+            .lock()
+            .await
+            .retain(|_, channel| match channel {
+                Channel::TextChannel { server: id, .. }
+                | Channel::VoiceChannel { server: id, .. } => id != &server.id,
+                _ => true,
+            });
+
+        // Delete any associated objects, e.g. unreads and invites.
+        self.channel_invites
+            .lock()
+            .await
+            .retain(|_, invite| match invite {
+                Invite::Server { channel, .. } | Invite::Group { channel, .. } => {
+                    !server.channels.contains(channel)
+                }
+            });
+
+        self.channel_unreads
+            .lock()
+            .await
+            .retain(|(channel, _), _| !server.channels.contains(channel));
+
+        self.webhooks
+            .lock()
+            .await
+            .retain(|_, webhook| !server.channels.contains(&webhook.channel_id));
+
+        // Delete members and bans.
+        self.server_members
+            .lock()
+            .await
+            .retain(|(id, _), _| id != &server.id);
+
+        self.server_bans
+            .lock()
+            .await
+            .retain(|(id, _), _| id != &server.id);
+
+        // Delete audit log.
+        self.audit_log
+            .lock()
+            .await
+            .retain(|_, entry| entry.server != server.id);
+
+        // Delete automod rules.
+        self.automod_rules
+            .lock()
+            .await
+            .retain(|_, rule| rule.server != server.id);
+
+        // Update many attachments with parent id.
+        for file in self.attachments.lock().await.values_mut() {
+            if file.object_id.as_ref() == Some(&server.id) {
+                file.deleted = Some(true);
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl AbstractServer for ReferenceDb {
+    async fn fetch_server(&self, id: &str) -> Result<Server> {
+        let servers = self.servers.lock().await;
+        servers.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn fetch_servers<'a>(&self, ids: &'a [String]) -> Result<Vec<Server>> {
+        let servers = self.servers.lock().await;
+        Ok(ids
+            .iter()
+            .filter_map(|id| servers.get(id).cloned())
+            .collect())
+    }
+
+    async fn insert_server(&self, server: &Server) -> Result<()> {
+        let mut servers = self.servers.lock().await;
+        if servers.contains_key(&server.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "server",
+            })
+        } else {
+            servers.insert(server.id.to_string(), server.clone());
+            Ok(())
+        }
+    }
+
+    async fn update_server(
+        &self,
+        id: &str,
+        partial: &PartialServer,
+        remove: Vec<FieldsServer>,
+    ) -> Result<()> {
+        let mut servers = self.servers.lock().await;
+        if let Some(server) = servers.get_mut(id) {
+            for field in &remove {
+                server.remove(field);
+            }
+
+            server.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_server(&self, server: &Server) -> Result<()> {
+        for thread in self.fetch_threads(&server.channels).await? {
+            self.delete_channel(&thread).await?;
+        }
+
+        self.delete_associated_server_objects(server).await;
+        self.servers.lock().await.remove(&server.id);
+        Ok(())
+    }
+
+    async fn insert_role(&self, server_id: &str, role_id: &str, role: &Role) -> Result<()> {
+        let mut servers = self.servers.lock().await;
+        if let Some(server) = servers.get_mut(server_id) {
+            server.roles.insert(role_id.to_string(), role.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn update_role(
+        &self,
+        server_id: &str,
+        role_id: &str,
+        partial: &PartialRole,
+        remove: Vec<FieldsRole>,
+    ) -> Result<()> {
+        let mut servers = self.servers.lock().await;
+        if let Some(role) = servers
+            .get_mut(server_id)
+            .and_then(|server| server.roles.get_mut(role_id))
+        {
+            for field in &remove {
+                role.remove(field);
+            }
+
+            role.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_role(&self, server_id: &str, role_id: &str) -> Result<()> {
+        for member in self.server_members.lock().await.values_mut() {
+            if member.id.server == server_id {
+                member.roles.retain(|id| id != role_id);
+            }
+        }
+
+        for channel in self.channels.lock().await.values_mut() {
+            if let Channel::TextChannel {
+                server,
+                role_permissions,
+                ..
+            }
+            | Channel::VoiceChannel {
+                server,
+                role_permissions,
+                ..
+            } = channel
+            {
+                if server == server_id {
+                    role_permissions.remove(role_id);
+                }
+            }
+        }
+
+        if let Some(server) = self.servers.lock().await.get_mut(server_id) {
+            server.roles.remove(role_id);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+use crate::models::server_member::MemberCompositeKey;
+use crate::models::ServerBan;
+use crate::{AbstractServerBan, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractServerBan for ReferenceDb {
+    async fn fetch_ban(&self, server: &str, user: &str) -> Result<ServerBan> {
+        let bans = self.server_bans.lock().await;
+        bans.get(&(server.to_string(), user.to_string()))
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    async fn fetch_bans(&self, server: &str) -> Result<Vec<ServerBan>> {
+        let bans = self.server_bans.lock().await;
+        Ok(bans
+            .values()
+            .filter(|ban| ban.id.server == server)
+            .cloned()
+            .collect())
+    }
+
+    async fn insert_ban(&self, ban: &ServerBan) -> Result<()> {
+        let key = (ban.id.server.to_string(), ban.id.user.to_string());
+        let mut bans = self.server_bans.lock().await;
+        if bans.contains_key(&key) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "server_ban",
+            })
+        } else {
+            bans.insert(key, ban.clone());
+            Ok(())
+        }
+    }
+
+    async fn delete_ban(&self, id: &MemberCompositeKey) -> Result<()> {
+        self.server_bans
+            .lock()
+            .await
+            .remove(&(id.server.to_string(), id.user.to_string()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use crate::models::server_member::{FieldsMember, Member, MemberCompositeKey, PartialMember};
+use crate::{AbstractServerMember, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractServerMember for ReferenceDb {
+    async fn fetch_member(&self, server: &str, user: &str) -> Result<Member> {
+        let members = self.server_members.lock().await;
+        members
+            .get(&(server.to_string(), user.to_string()))
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    async fn insert_member(&self, member: &Member) -> Result<()> {
+        let key = (member.id.server.to_string(), member.id.user.to_string());
+        let mut members = self.server_members.lock().await;
+        if members.contains_key(&key) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "server_member",
+            })
+        } else {
+            members.insert(key, member.clone());
+            Ok(())
+        }
+    }
+
+    async fn update_member(
+        &self,
+        id: &MemberCompositeKey,
+        partial: &PartialMember,
+        remove: Vec<FieldsMember>,
+    ) -> Result<()> {
+        let mut members = self.server_members.lock().await;
+        if let Some(member) = members.get_mut(&(id.server.to_string(), id.user.to_string())) {
+            for field in &remove {
+                member.remove(field);
+            }
+
+            member.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_member(&self, id: &MemberCompositeKey) -> Result<()> {
+        self.server_members
+            .lock()
+            .await
+            .remove(&(id.server.to_string(), id.user.to_string()));
+
+        Ok(())
+    }
+
+    async fn fetch_all_members<'a>(&self, server: &str) -> Result<Vec<Member>> {
+        let members = self.server_members.lock().await;
+        Ok(members
+            .values()
+            .filter(|member| member.id.server == server)
+            .cloned()
+            .collect())
+    }
+
+    async fn fetch_all_memberships<'a>(&self, user: &str) -> Result<Vec<Member>> {
+        let members = self.server_members.lock().await;
+        Ok(members
+            .values()
+            .filter(|member| member.id.user == user)
+            .cloned()
+            .collect())
+    }
+
+    async fn fetch_members<'a>(&self, server: &str, ids: &'a [String]) -> Result<Vec<Member>> {
+        let members = self.server_members.lock().await;
+        Ok(ids
+            .iter()
+            .filter_map(|id| members.get(&(server.to_string(), id.to_string())).cloned())
+            .collect())
+    }
+
+    async fn fetch_member_count(&self, server: &str) -> Result<usize> {
+        let members = self.server_members.lock().await;
+        Ok(members
+            .values()
+            .filter(|member| member.id.server == server)
+            .count())
+    }
+
+    async fn fetch_server_count(&self, user: &str) -> Result<usize> {
+        let members = self.server_members.lock().await;
+        Ok(members
+            .values()
+            .filter(|member| member.id.user == user)
+            .count())
+    }
+}
@@ -0,0 +1,72 @@
+use crate::models::bot::{Bot, FieldsBot, PartialBot};
+use crate::{AbstractBot, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractBot for ReferenceDb {
+    async fn fetch_bot(&self, id: &str) -> Result<Bot> {
+        let bots = self.bots.lock().await;
+        bots.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn fetch_bot_by_token(&self, token: &str) -> Result<Bot> {
+        let bots = self.bots.lock().await;
+        bots.values()
+            .find(|bot| bot.token == token)
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    async fn insert_bot(&self, bot: &Bot) -> Result<()> {
+        let mut bots = self.bots.lock().await;
+        if bots.contains_key(&bot.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "bot",
+            })
+        } else {
+            bots.insert(bot.id.to_string(), bot.clone());
+            Ok(())
+        }
+    }
+
+    async fn update_bot(
+        &self,
+        id: &str,
+        partial: &PartialBot,
+        remove: Vec<FieldsBot>,
+    ) -> Result<()> {
+        let mut bots = self.bots.lock().await;
+        if let Some(bot) = bots.get_mut(id) {
+            for field in &remove {
+                // New tokens are provided through the partial object.
+                if let FieldsBot::InteractionsURL = field {
+                    bot.remove(field);
+                }
+            }
+
+            bot.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_bot(&self, id: &str) -> Result<()> {
+        self.bots.lock().await.remove(id);
+        Ok(())
+    }
+
+    async fn fetch_bots_by_user(&self, user_id: &str) -> Result<Vec<Bot>> {
+        let bots = self.bots.lock().await;
+        Ok(bots
+            .values()
+            .filter(|bot| bot.owner == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn get_number_of_bots_by_user(&self, user_id: &str) -> Result<usize> {
+        self.fetch_bots_by_user(user_id).await.map(|x| x.len())
+    }
+}
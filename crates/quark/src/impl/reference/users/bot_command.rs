@@ -0,0 +1,46 @@
+use crate::models::BotCommand;
+use crate::{AbstractBotCommand, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractBotCommand for ReferenceDb {
+    async fn insert_command(&self, command: &BotCommand) -> Result<()> {
+        let mut commands = self.bot_commands.lock().await;
+        if commands.contains_key(&command.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "bot_command",
+            })
+        } else {
+            commands.insert(command.id.to_string(), command.clone());
+            Ok(())
+        }
+    }
+
+    async fn fetch_command(&self, id: &str) -> Result<BotCommand> {
+        let commands = self.bot_commands.lock().await;
+        commands.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn fetch_commands(&self, bot: &str) -> Result<Vec<BotCommand>> {
+        let commands = self.bot_commands.lock().await;
+        Ok(commands
+            .values()
+            .filter(|command| command.bot_id == bot)
+            .cloned()
+            .collect())
+    }
+
+    async fn delete_command(&self, id: &str) -> Result<()> {
+        let mut commands = self.bot_commands.lock().await;
+        commands.remove(id);
+        Ok(())
+    }
+
+    async fn delete_commands_for_bot(&self, bot: &str) -> Result<()> {
+        let mut commands = self.bot_commands.lock().await;
+        commands.retain(|_, command| command.bot_id != bot);
+        Ok(())
+    }
+}
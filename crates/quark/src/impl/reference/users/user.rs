@@ -0,0 +1,168 @@
+use crate::models::user::{FieldsUser, PartialUser, Relationship, RelationshipStatus, User};
+use crate::models::Channel;
+use crate::{AbstractUser, Error, Result};
+
+use super::super::ReferenceDb;
+
+/// Check whether a user has a friend relationship with the given user
+fn is_friends_with(user: &User, target: &str) -> bool {
+    user.relations.as_ref().map_or(false, |relations| {
+        relations
+            .iter()
+            .any(|entry| entry.id == target && entry.status == RelationshipStatus::Friend)
+    })
+}
+
+#[async_trait]
+impl AbstractUser for ReferenceDb {
+    async fn fetch_user(&self, id: &str) -> Result<User> {
+        let users = self.users.lock().await;
+        users.get(id).cloned().ok_or(Error::NotFound)
+    }
+
+    async fn fetch_user_by_username(&self, username: &str) -> Result<User> {
+        let username = username.to_lowercase();
+        let users = self.users.lock().await;
+        users
+            .values()
+            .find(|user| user.username.to_lowercase() == username)
+            .cloned()
+            .ok_or(Error::NotFound)
+    }
+
+    async fn fetch_user_by_token(&self, token: &str) -> Result<User> {
+        let user_id = self
+            .sessions
+            .lock()
+            .await
+            .get(token)
+            .cloned()
+            .ok_or(Error::InvalidSession)?;
+
+        self.fetch_user(&user_id).await
+    }
+
+    async fn insert_user(&self, user: &User) -> Result<()> {
+        let mut users = self.users.lock().await;
+        if users.contains_key(&user.id) {
+            Err(Error::DatabaseError {
+                operation: "insert_one",
+                with: "user",
+            })
+        } else {
+            users.insert(user.id.to_string(), user.clone());
+            Ok(())
+        }
+    }
+
+    async fn update_user(
+        &self,
+        id: &str,
+        partial: &PartialUser,
+        remove: Vec<FieldsUser>,
+    ) -> Result<()> {
+        let mut users = self.users.lock().await;
+        if let Some(user) = users.get_mut(id) {
+            for field in &remove {
+                user.remove(field);
+            }
+
+            user.apply_options(partial.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_user(&self, id: &str) -> Result<()> {
+        self.users.lock().await.remove(id);
+        Ok(())
+    }
+
+    async fn fetch_users<'a>(&self, ids: &'a [String]) -> Result<Vec<User>> {
+        let users = self.users.lock().await;
+        Ok(ids.iter().filter_map(|id| users.get(id).cloned()).collect())
+    }
+
+    async fn is_username_taken(&self, username: &str) -> Result<bool> {
+        match self.fetch_user_by_username(username).await {
+            Ok(_) => Ok(true),
+            Err(Error::NotFound) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    async fn fetch_mutual_user_ids(&self, user_a: &str, user_b: &str) -> Result<Vec<String>> {
+        let users = self.users.lock().await;
+        Ok(users
+            .values()
+            .filter(|user| is_friends_with(user, user_a) && is_friends_with(user, user_b))
+            .map(|user| user.id.to_string())
+            .collect())
+    }
+
+    async fn fetch_mutual_channel_ids(&self, user_a: &str, user_b: &str) -> Result<Vec<String>> {
+        let channels = self.channels.lock().await;
+        Ok(channels
+            .values()
+            .filter_map(|channel| match channel {
+                Channel::DirectMessage { id, recipients, .. }
+                | Channel::Group { id, recipients, .. } => {
+                    if recipients.iter().any(|user| user == user_a)
+                        && recipients.iter().any(|user| user == user_b)
+                    {
+                        Some(id.to_string())
+                    } else {
+                        None
+                    }
+                }
+                _ => None,
+            })
+            .collect())
+    }
+
+    async fn fetch_mutual_server_ids(&self, user_a: &str, user_b: &str) -> Result<Vec<String>> {
+        let members = self.server_members.lock().await;
+        Ok(members
+            .keys()
+            .filter(|(server, user)| {
+                user == user_a && members.contains_key(&(server.to_string(), user_b.to_string()))
+            })
+            .map(|(server, _)| server.to_string())
+            .collect())
+    }
+
+    async fn set_relationship(
+        &self,
+        user_id: &str,
+        target_id: &str,
+        relationship: &RelationshipStatus,
+    ) -> Result<()> {
+        if let RelationshipStatus::None = relationship {
+            return self.pull_relationship(user_id, target_id).await;
+        }
+
+        let mut users = self.users.lock().await;
+        if let Some(user) = users.get_mut(user_id) {
+            let relations = user.relations.get_or_insert_with(Vec::new);
+            relations.retain(|entry| entry.id != target_id);
+            relations.push(Relationship {
+                id: target_id.to_string(),
+                status: relationship.clone(),
+            });
+        }
+
+        Ok(())
+    }
+
+    async fn pull_relationship(&self, user_id: &str, target_id: &str) -> Result<()> {
+        let mut users = self.users.lock().await;
+        if let Some(relations) = users
+            .get_mut(user_id)
+            .and_then(|user| user.relations.as_mut())
+        {
+            relations.retain(|entry| entry.id != target_id);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,36 @@
+use crate::models::UserSettings;
+use crate::{AbstractUserSettings, Error, Result};
+
+use super::super::ReferenceDb;
+
+#[async_trait]
+impl AbstractUserSettings for ReferenceDb {
+    async fn fetch_user_settings(&'_ self, id: &str, filter: &'_ [String]) -> Result<UserSettings> {
+        let user_settings = self.user_settings.lock().await;
+        let settings = user_settings.get(id).ok_or(Error::NotFound)?;
+
+        Ok(filter
+            .iter()
+            .filter_map(|key| {
+                settings
+                    .get(key)
+                    .map(|value| (key.to_string(), value.clone()))
+            })
+            .collect())
+    }
+
+    async fn set_user_settings(&self, id: &str, settings: &UserSettings) -> Result<()> {
+        let mut user_settings = self.user_settings.lock().await;
+        let entry = user_settings.entry(id.to_string()).or_default();
+        for (key, value) in settings {
+            entry.insert(key.to_string(), value.clone());
+        }
+
+        Ok(())
+    }
+
+    async fn delete_user_settings(&self, id: &str) -> Result<()> {
+        self.user_settings.lock().await.remove(id);
+        Ok(())
+    }
+}
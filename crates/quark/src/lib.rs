@@ -23,6 +23,7 @@ pub use redis_kiss;
 
 pub mod events;
 pub mod r#impl;
+pub mod metrics;
 pub mod models;
 pub mod presence;
 pub mod tasks;
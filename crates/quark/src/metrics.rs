@@ -0,0 +1,94 @@
+//! Prometheus metrics shared between services
+//!
+//! Metrics are registered with the default registry the first time
+//! they are used, [render] exports everything registered so far.
+
+use prometheus::{
+    register_histogram_vec, register_int_counter_vec, register_int_gauge_vec, Encoder,
+    HistogramVec, IntCounterVec, IntGaugeVec, TextEncoder,
+};
+
+use crate::Error;
+
+lazy_static! {
+    /// HTTP requests handled, by method, route and response status
+    pub static ref HTTP_REQUESTS: IntCounterVec = register_int_counter_vec!(
+        "revolt_http_requests_total",
+        "Number of HTTP requests handled",
+        &["method", "route", "status"]
+    )
+    .unwrap();
+
+    /// Time taken to handle HTTP requests, by method and route
+    pub static ref HTTP_REQUEST_DURATION: HistogramVec = register_histogram_vec!(
+        "revolt_http_request_duration_seconds",
+        "Time taken to handle HTTP requests",
+        &["method", "route"]
+    )
+    .unwrap();
+
+    /// Requests rejected by the rate limiter, by bucket
+    pub static ref RATELIMIT_REJECTIONS: IntCounterVec = register_int_counter_vec!(
+        "revolt_ratelimit_rejections_total",
+        "Number of requests rejected by the rate limiter",
+        &["bucket"]
+    )
+    .unwrap();
+
+    /// Jobs waiting in each background task queue
+    pub static ref TASK_QUEUE_DEPTH: IntGaugeVec = register_int_gauge_vec!(
+        "revolt_task_queue_depth",
+        "Number of jobs waiting in a background task queue",
+        &["queue"]
+    )
+    .unwrap();
+
+    /// Clients currently connected for realtime events, by transport
+    pub static ref EVENT_CONNECTIONS: IntGaugeVec = register_int_gauge_vec!(
+        "revolt_event_connections",
+        "Number of clients connected for realtime events",
+        &["transport"]
+    )
+    .unwrap();
+
+    /// Events sent to clients, by transport
+    pub static ref EVENTS_DELIVERED: IntCounterVec = register_int_counter_vec!(
+        "revolt_events_delivered_total",
+        "Number of events sent to clients",
+        &["transport"]
+    )
+    .unwrap();
+
+    /// Failed database operations, by operation and collection
+    pub static ref DATABASE_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "revolt_database_errors_total",
+        "Number of failed database operations",
+        &["operation", "with"]
+    )
+    .unwrap();
+
+    /// Failed Redis operations, by operation
+    pub static ref REDIS_ERRORS: IntCounterVec = register_int_counter_vec!(
+        "revolt_redis_errors_total",
+        "Number of failed Redis operations",
+        &["operation"]
+    )
+    .unwrap();
+}
+
+/// Count an error if it was caused by a failed database operation
+pub fn record_error(error: &Error) {
+    if let Error::DatabaseError { operation, with } = error {
+        DATABASE_ERRORS.with_label_values(&[operation, with]).inc();
+    }
+}
+
+/// Render all registered metrics in the Prometheus text format
+pub fn render() -> String {
+    let mut buffer = vec![];
+    TextEncoder::new()
+        .encode(&prometheus::gather(), &mut buffer)
+        .expect("Failed to encode metrics.");
+
+    String::from_utf8(buffer).expect("Metrics should be valid UTF-8.")
+}
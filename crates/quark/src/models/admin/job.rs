@@ -0,0 +1,26 @@
+use serde::{Deserialize, Serialize};
+
+/// Background job held in a persistent queue
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Job {
+    /// Unique Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// Name of the queue this job belongs to
+    pub queue: String,
+    /// Serialised task information
+    pub payload: String,
+    /// Number of times this job has been claimed by a worker
+    pub attempts: u32,
+    /// Time (in milliseconds since Unix epoch) from which this job may be claimed
+    ///
+    /// Claiming a job pushes this back by the visibility timeout,
+    /// so jobs held by a worker which went away are picked up again.
+    pub available_at: i64,
+    /// Error encountered on the most recent attempt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+    /// Whether this job ran out of attempts and was moved to the dead letter queue
+    #[serde(default)]
+    pub dead: bool,
+}
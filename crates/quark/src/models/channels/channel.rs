@@ -133,6 +133,34 @@ pub enum Channel {
         #[serde(skip_serializing_if = "if_false", default)]
         nsfw: bool,
     },
+    /// Thread branching off a message in a text channel
+    ///
+    /// Permissions are inherited from the parent channel.
+    Thread {
+        /// Unique Id
+        #[serde(rename = "_id")]
+        id: String,
+        /// Id of the server this thread belongs to
+        server: String,
+        /// Id of the channel this thread was started in
+        parent: String,
+        /// Id of the message this thread was started from
+        message: String,
+        /// User id of the user who started this thread
+        owner: String,
+
+        /// Display name of the thread
+        name: String,
+        /// Id of the last message sent in this thread
+        #[serde(skip_serializing_if = "Option::is_none")]
+        last_message_id: Option<String>,
+
+        /// Whether this thread has been archived
+        ///
+        /// Archived threads are read-only.
+        #[serde(skip_serializing_if = "if_false", default)]
+        archived: bool,
+    },
 }
 
 /// Partial values of [Channel]
@@ -158,6 +186,8 @@ pub struct PartialChannel {
     pub default_permissions: Option<OverrideField>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_message_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived: Option<bool>,
 }
 
 /// Optional fields on channel object
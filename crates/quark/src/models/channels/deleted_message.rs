@@ -0,0 +1,20 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::Message;
+
+/// Message which was deleted but may still be restored
+///
+/// Kept around until the recovery window passes, after which
+/// it is purged along with its attachments and revisions.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct DeletedMessage {
+    /// Id of the deleted message
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// Id of the channel the message was sent in
+    pub channel: String,
+    /// Time at which the message was deleted, in milliseconds since Unix epoch
+    pub deleted_at: i64,
+    /// Message as it was when deleted
+    pub message: Message,
+}
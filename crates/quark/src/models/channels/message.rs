@@ -70,6 +70,14 @@ pub enum SystemMessage {
     ChannelIconChanged { by: String },
     #[serde(rename = "channel_ownership_changed")]
     ChannelOwnershipChanged { from: String, to: String },
+    #[serde(rename = "message_pinned")]
+    MessagePinned { id: String, by: String },
+    #[serde(rename = "automod_triggered")]
+    AutomodTriggered {
+        rule: String,
+        user: String,
+        channel: String,
+    },
 }
 
 /// Name and / or avatar override information
@@ -150,6 +158,9 @@ pub struct Message {
     /// Name and / or avatar overrides for this message
     #[serde(skip_serializing_if = "Option::is_none")]
     pub masquerade: Option<Masquerade>,
+    /// Whether this message is pinned in its channel
+    #[serde(skip_serializing_if = "if_false", default)]
+    pub pinned: bool,
 }
 
 /// # Message Sort
@@ -172,6 +183,51 @@ impl Default for MessageSort {
     }
 }
 
+/// # Message Filter
+///
+/// Criteria which searched messages must match
+#[derive(Validate, Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+pub struct MessageFilter {
+    /// Full-text search query
+    ///
+    /// See [MongoDB documentation](https://docs.mongodb.com/manual/text-search/#-text-operator) for more information.
+    #[validate(length(min = 1, max = 64))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Only include messages sent by this user
+    #[validate(length(min = 26, max = 26))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Only include messages which mention this user
+    #[validate(length(min = 26, max = 26))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mention: Option<String>,
+    /// Only include messages with attachments
+    #[serde(skip_serializing_if = "if_false", default)]
+    pub has_attachment: bool,
+    /// Only include messages with embeds
+    #[serde(skip_serializing_if = "if_false", default)]
+    pub has_embed: bool,
+    /// Only include messages which contain a link
+    #[serde(skip_serializing_if = "if_false", default)]
+    pub has_link: bool,
+    /// Only include pinned messages
+    #[serde(skip_serializing_if = "if_false", default)]
+    pub pinned: bool,
+    /// Message id before which messages should be fetched
+    ///
+    /// Any ULID may be used here, one generated for a given time limits results to messages sent before it.
+    #[validate(length(min = 26, max = 26))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<String>,
+    /// Message id after which messages should be fetched
+    ///
+    /// Any ULID may be used here, one generated for a given time limits results to messages sent after it.
+    #[validate(length(min = 26, max = 26))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
 /// # Bulk Message Response
 ///
 /// Response used when multiple messages are fetched
@@ -193,6 +249,23 @@ pub enum BulkMessageResponse {
     },
 }
 
+/// # Server Search Response
+///
+/// Messages found across a server, grouped by channel
+#[derive(Serialize, JsonSchema)]
+pub struct ServerSearchResponse {
+    /// Map of channel ids to messages found in them
+    ///
+    /// Channels are ordered by their best matching message.
+    pub channels: IndexMap<String, Vec<Message>>,
+    /// List of users
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub users: Option<Vec<User>>,
+    /// List of members
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<Member>>,
+}
+
 /// # Appended Information
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct AppendMessage {
@@ -0,0 +1,30 @@
+use iso8601_timestamp::Timestamp;
+use serde::{Deserialize, Serialize};
+
+use crate::types::january::Embed;
+
+/// Previous version of a message, recorded when it is edited
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct MessageRevision {
+    /// Unique Id
+    ///
+    /// Generated when the revision was replaced by an edit.
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// Id of the message this revision belongs to
+    pub message: String,
+    /// Id of the channel the message was sent in
+    pub channel: String,
+
+    /// Message content at the time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+    /// Embeds attached to the message at the time
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embeds: Option<Vec<Embed>>,
+    /// Time at which this revision was created by an earlier edit
+    ///
+    /// Not present if this was the original version of the message.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub edited: Option<Timestamp>,
+}
@@ -0,0 +1,35 @@
+use serde::{Deserialize, Serialize};
+
+use crate::models::attachment::File;
+
+/// Representation of a webhook on Revolt
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, OptionalStruct, Default)]
+#[optional_derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
+#[optional_name = "PartialWebhook"]
+#[opt_skip_serializing_none]
+#[opt_some_priority]
+pub struct Webhook {
+    /// Webhook Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// Name used for messages sent by this webhook
+    pub name: String,
+    /// Avatar used for messages sent by this webhook
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub avatar: Option<File>,
+    /// Id of the channel this webhook belongs to
+    pub channel_id: String,
+    /// Id of the user who created this webhook
+    pub creator_id: String,
+    /// Secret token used to execute this webhook
+    ///
+    /// Only present when fetched by a user who can manage webhooks.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+}
+
+/// Optional fields on webhook object
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq, Eq, Clone)]
+pub enum FieldsWebhook {
+    Avatar,
+}
@@ -1,4 +1,5 @@
 mod admin {
+    pub mod job;
     pub mod migrations;
     pub mod simple;
 }
@@ -12,10 +13,15 @@ mod channels {
     pub mod channel;
     pub mod channel_invite;
     pub mod channel_unread;
+    pub mod deleted_message;
     pub mod message;
+    pub mod message_revision;
+    pub mod webhook;
 }
 
 mod servers {
+    pub mod audit_log;
+    pub mod automod_rule;
     pub mod server;
     pub mod server_ban;
     pub mod server_member;
@@ -23,6 +29,7 @@ mod servers {
 
 mod users {
     pub mod bot;
+    pub mod bot_command;
     pub mod user;
     pub mod user_settings;
 }
@@ -34,12 +41,18 @@ pub use servers::*;
 pub use users::*;
 
 pub use attachment::File;
+pub use audit_log::AuditLogEntry;
+pub use automod_rule::AutomodRule;
 pub use bot::Bot;
+pub use bot_command::BotCommand;
 pub use channel::Channel;
 pub use channel_invite::Invite;
 pub use channel_unread::ChannelUnread;
+pub use deleted_message::DeletedMessage;
 pub use emoji::Emoji;
+pub use job::Job;
 pub use message::Message;
+pub use message_revision::MessageRevision;
 pub use migrations::MigrationInfo;
 pub use server::Server;
 pub use server_ban::ServerBan;
@@ -47,3 +60,4 @@ pub use server_member::Member;
 pub use simple::SimpleModel;
 pub use user::User;
 pub use user_settings::UserSettings;
+pub use webhook::Webhook;
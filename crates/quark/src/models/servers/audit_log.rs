@@ -0,0 +1,100 @@
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "rocket_impl")]
+use rocket::FromFormField;
+
+use crate::models::{
+    channel::{FieldsChannel, PartialChannel},
+    server::{FieldsRole, FieldsServer, PartialRole, PartialServer},
+    server_member::{FieldsMember, PartialMember},
+};
+
+/// Type of action which was recorded in the audit log
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq, Eq, Clone, Copy)]
+#[cfg_attr(feature = "rocket_impl", derive(FromFormField))]
+pub enum AuditLogAction {
+    /// Server information or default permissions were changed
+    ServerUpdate,
+    /// Channel was created
+    ChannelCreate,
+    /// Channel information or permissions were changed
+    ChannelUpdate,
+    /// Channel was deleted
+    ChannelDelete,
+    /// Role was created
+    RoleCreate,
+    /// Role information or permissions were changed
+    RoleUpdate,
+    /// Role was deleted
+    RoleDelete,
+    /// Member was edited by someone else
+    MemberUpdate,
+    /// Member was kicked
+    MemberKick,
+    /// User was banned
+    MemberBan,
+    /// User was unbanned
+    MemberUnban,
+    /// Automod rule was created
+    AutomodRuleCreate,
+    /// Automod rule was changed
+    AutomodRuleUpdate,
+    /// Automod rule was deleted
+    AutomodRuleDelete,
+    /// Deleted message was restored
+    MessageRestore,
+}
+
+/// Changes made to an object by an audited action
+///
+/// `before` and `after` only contain the fields which changed.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum AuditLogChanges {
+    Server {
+        before: PartialServer,
+        after: PartialServer,
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        remove: Vec<FieldsServer>,
+    },
+    Channel {
+        before: PartialChannel,
+        after: PartialChannel,
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        remove: Vec<FieldsChannel>,
+    },
+    Role {
+        before: PartialRole,
+        after: PartialRole,
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        remove: Vec<FieldsRole>,
+    },
+    Member {
+        before: PartialMember,
+        after: PartialMember,
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        remove: Vec<FieldsMember>,
+    },
+}
+
+/// Representation of an audit log entry on Revolt
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct AuditLogEntry {
+    /// Unique Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// Id of the server this action was taken in
+    pub server: String,
+    /// Id of the user who took this action
+    pub actor: String,
+    /// Action which was taken
+    pub action: AuditLogAction,
+    /// Id of the object this action was taken on
+    pub target: String,
+    /// Changes made to the target
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub changes: Option<AuditLogChanges>,
+    /// Reason given for this action
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
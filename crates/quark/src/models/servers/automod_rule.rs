@@ -0,0 +1,69 @@
+use serde::{Deserialize, Serialize};
+
+/// Condition which causes an automod rule to act on a message
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum AutomodTrigger {
+    /// Message contains any of the given keywords (case insensitive)
+    Keyword { keywords: Vec<String> },
+    /// Message matches any of the given regular expressions
+    Regex { patterns: Vec<String> },
+    /// Message links to a denied domain or to a domain not on the allow list
+    ///
+    /// An empty allow list permits any domain which isn't denied.
+    Link {
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        allow: Vec<String>,
+        #[serde(skip_serializing_if = "Vec::is_empty", default)]
+        deny: Vec<String>,
+    },
+    /// Message mentions more than the given number of users
+    MentionSpam { limit: usize },
+    /// Author already sent the same content the given number of times within the window (in seconds)
+    Duplicate { limit: usize, window: u64 },
+}
+
+/// Action taken when an automod rule is triggered
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+#[serde(tag = "type")]
+pub enum AutomodAction {
+    /// Prevent the message from being sent
+    Block,
+    /// Delete the message after it has been sent
+    Delete,
+    /// Time out the author for the given duration (in seconds)
+    Timeout { duration: u64 },
+    /// Send an alert to the given moderator channel
+    Alert { channel: String },
+}
+
+/// Representation of an automatic moderation rule on Revolt
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, OptionalStruct)]
+#[optional_derive(Serialize, Deserialize, JsonSchema, Debug, Default, Clone)]
+#[optional_name = "PartialAutomodRule"]
+#[opt_skip_serializing_none]
+#[opt_some_priority]
+pub struct AutomodRule {
+    /// Rule Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// Id of the server this rule belongs to
+    pub server: String,
+    /// Rule name
+    pub name: String,
+    /// Whether this rule is currently in effect
+    pub enabled: bool,
+    /// Condition which triggers this rule
+    pub trigger: AutomodTrigger,
+    /// Action taken when this rule is triggered
+    pub action: AutomodAction,
+    /// Roles which this rule doesn't apply to
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub exempt_roles: Vec<String>,
+    /// Channels which this rule doesn't apply to
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub exempt_channels: Vec<String>,
+    /// Number of times this rule's trigger has been changed
+    #[serde(default)]
+    pub revision: u64,
+}
@@ -131,6 +131,12 @@ pub struct Server {
     /// Whether this server should be publicly discoverable
     #[serde(skip_serializing_if = "if_false", default)]
     pub discoverable: bool,
+
+    /// Number of days to keep message edit history for
+    ///
+    /// Uses the instance default if not set, history is not kept if set to 0.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_history_retention: Option<u32>,
 }
 
 /// Optional fields on server object
@@ -141,6 +147,7 @@ pub enum FieldsServer {
     SystemMessages,
     Icon,
     Banner,
+    MessageHistoryRetention,
 }
 
 /// Optional fields on server object
@@ -40,7 +40,7 @@ pub struct Bot {
     /// Whether this bot should be publicly discoverable
     #[serde(skip_serializing_if = "if_false", default)]
     pub discoverable: bool,
-    /// Reserved; URL for handling interactions
+    /// URL which command interactions are sent to
     #[serde(skip_serializing_if = "Option::is_none")]
     pub interactions_url: Option<String>,
     /// URL for terms of service
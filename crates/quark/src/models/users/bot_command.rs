@@ -0,0 +1,94 @@
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::models::message::SendableEmbed;
+
+/// Type of value accepted by a command option
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq, Eq, Clone)]
+pub enum CommandOptionType {
+    String,
+    Integer,
+    Boolean,
+    User,
+    Channel,
+}
+
+/// Option which can be passed to a command
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Validate)]
+pub struct CommandOption {
+    /// Option name
+    #[validate(length(min = 1, max = 32))]
+    pub name: String,
+    /// Option description
+    #[validate(length(min = 1, max = 100))]
+    pub description: String,
+    /// Type of value this option accepts
+    #[serde(rename = "type")]
+    pub option_type: CommandOptionType,
+    /// Whether this option must be provided
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// Representation of a bot command on Revolt
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Default)]
+pub struct BotCommand {
+    /// Command Id
+    #[serde(rename = "_id")]
+    pub id: String,
+    /// Id of the bot which owns this command
+    pub bot_id: String,
+    /// Id of the server this command is registered in
+    ///
+    /// Global commands may be used anywhere the bot is present.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_id: Option<String>,
+    /// Command name
+    pub name: String,
+    /// Command description
+    pub description: String,
+    /// Options accepted by this command
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    pub options: Vec<CommandOption>,
+}
+
+/// Value given for a command option
+#[derive(Serialize, Deserialize, JsonSchema, Debug, PartialEq, Clone)]
+#[serde(untagged)]
+pub enum CommandOptionValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+/// Interaction payload sent to a bot's interactions URL
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone)]
+pub struct CommandInteraction {
+    /// Interaction Id
+    pub id: String,
+    /// Id of the command which was invoked
+    pub command_id: String,
+    /// Name of the command which was invoked
+    pub command_name: String,
+    /// Id of the user who invoked the command
+    pub user_id: String,
+    /// Id of the channel the command was invoked in
+    pub channel_id: String,
+    /// Id of the server the command was invoked in
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_id: Option<String>,
+    /// Values given for the command's options
+    pub options: IndexMap<String, CommandOptionValue>,
+}
+
+/// Response returned by a bot after handling an interaction
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, Validate)]
+pub struct CommandResponse {
+    /// Message content to send
+    #[validate(length(min = 0, max = 2000))]
+    pub content: Option<String>,
+    /// Embeds to include in message
+    #[validate(length(min = 1, max = 10))]
+    pub embeds: Option<Vec<SendableEmbed>>,
+}
@@ -23,8 +23,8 @@ pub enum Permission {
     ManageRole = 1 << 3,
     /// Manage server customisation (includes emoji)
     ManageCustomisation = 1 << 4,
-
-    // % 1 bits reserved
+    /// View the server's audit log
+    ViewAuditLog = 1 << 5,
 
     // * Member permissions
     /// Kick other members below their ranking
@@ -99,6 +99,9 @@ impl_op_ex_commutative!(+ |a: &u64, b: &Permission| -> u64 { *a | *b as u64 });
 
 lazy_static! {
     pub static ref ALLOW_IN_TIMEOUT: u64 = Permission::ViewChannel + Permission::ReadMessageHistory;
+    pub static ref ALLOW_IN_ARCHIVED_THREAD: u64 = *ALLOW_IN_TIMEOUT
+        + Permission::ManageChannel
+        + Permission::ManageMessages;
     pub static ref DEFAULT_PERMISSION_VIEW_ONLY: u64 =
         Permission::ViewChannel + Permission::ReadMessageHistory;
     pub static ref DEFAULT_PERMISSION: u64 = *DEFAULT_PERMISSION_VIEW_ONLY
@@ -126,6 +129,7 @@ bitfield! {
     pub can_manage_permissions, _: 61;
     pub can_manage_roles, _: 60;
     pub can_manage_customisation, _: 59;
+    pub can_view_audit_log, _: 58;
 
     // * Member permissions
     pub can_kick_members, _: 57;
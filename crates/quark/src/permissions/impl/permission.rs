@@ -1,9 +1,10 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
 use crate::{
-    models::Channel, permissions::PermissionCalculator, Override, Permission, PermissionValue,
-    Permissions, Perms, Result, ALLOW_IN_TIMEOUT, DEFAULT_PERMISSION_DIRECT_MESSAGE,
-    DEFAULT_PERMISSION_SAVED_MESSAGES, DEFAULT_PERMISSION_VIEW_ONLY,
+    models::Channel, permissions::PermissionCalculator, Override, OverrideField, Permission,
+    PermissionValue, Permissions, Perms, Result, ALLOW_IN_ARCHIVED_THREAD, ALLOW_IN_TIMEOUT,
+    DEFAULT_PERMISSION_DIRECT_MESSAGE, DEFAULT_PERMISSION_SAVED_MESSAGES,
+    DEFAULT_PERMISSION_VIEW_ONLY,
 };
 
 use super::super::Permission::GrantAllSafe;
@@ -92,11 +93,13 @@ async fn calculate_channel_permission(
     // Pre-calculate server permissions if applicable.
     // We do this to satisfy the borrow checker.
     let server_id = match data.channel.get().unwrap() {
-        Channel::TextChannel { server, .. } | Channel::VoiceChannel { server, .. } => Some(server),
+        Channel::TextChannel { server, .. }
+        | Channel::VoiceChannel { server, .. }
+        | Channel::Thread { server, .. } => Some(server),
         _ => None,
     };
 
-    let mut permissions = if let Some(server) = server_id {
+    let permissions = if let Some(server) = server_id {
         if !data.server.has() {
             data.server.set(db.fetch_server(server).await?);
         }
@@ -174,59 +177,92 @@ async fn calculate_channel_permission(
             default_permissions,
             role_permissions,
             ..
+        } => apply_channel_overrides(data, permissions, default_permissions, role_permissions),
+        Channel::Thread {
+            parent, archived, ..
         } => {
-            // 2. If server owner, just grant all permissions.
-            //
-            // Member may be present and we need to check or
-            // we can just grant all if member is not present.
-            //
-            // In the case member isn't present, the previous
-            // step did not fetch member as we are the server owner.
-            if let Some(member) = data.member.get() {
-                let server = data.server.get().unwrap();
-                if server.owner == member.id.user {
-                    return Ok((Permission::GrantAllSafe as u64).into());
-                }
+            // 2. Inherit overrides from the parent channel.
+            let mut value = match db.fetch_channel(parent).await? {
+                Channel::TextChannel {
+                    default_permissions,
+                    role_permissions,
+                    ..
+                } => apply_channel_overrides(
+                    data,
+                    permissions,
+                    &default_permissions,
+                    &role_permissions,
+                ),
+                _ => 0_u64.into(),
+            };
+
+            // 3. Archived threads are read-only.
+            if *archived {
+                value.restrict(*ALLOW_IN_ARCHIVED_THREAD);
+            }
 
-                // 3. Apply default allows and denies for channel.
-                if let Some(default) = default_permissions {
-                    permissions.apply((*default).into());
-                }
+            value
+        }
+    };
 
-                // 4. Resolve each role in order.
-                let member_roles: HashSet<&String> = member.roles.iter().collect();
-
-                if !member_roles.is_empty() {
-                    let mut roles = role_permissions
-                        .iter()
-                        .filter(|(id, _)| member_roles.contains(id))
-                        .filter_map(|(id, permission)| {
-                            server.roles.get(id).map(|role| {
-                                let v: Override = (*permission).into();
-                                (role.rank, v)
-                            })
-                        })
-                        .collect::<Vec<(i64, Override)>>();
-
-                    roles.sort_by(|a, b| b.0.cmp(&a.0));
-
-                    // 5. Apply allows and denies from roles.
-                    for (_, v) in roles {
-                        permissions.apply(v);
-                    }
-                }
+    Ok(value)
+}
 
-                // 5. Revoke permissions if member is timed out.
-                if member.in_timeout() {
-                    permissions.restrict(*ALLOW_IN_TIMEOUT);
-                }
+/// Internal helper function for applying server channel overrides
+fn apply_channel_overrides(
+    data: &PermissionCalculator<'_>,
+    mut permissions: PermissionValue,
+    default_permissions: &Option<OverrideField>,
+    role_permissions: &HashMap<String, OverrideField>,
+) -> PermissionValue {
+    // 1. If server owner, just grant all permissions.
+    //
+    // Member may be present and we need to check or
+    // we can just grant all if member is not present.
+    //
+    // In the case member isn't present, the previous
+    // step did not fetch member as we are the server owner.
+    if let Some(member) = data.member.get() {
+        let server = data.server.get().unwrap();
+        if server.owner == member.id.user {
+            return (Permission::GrantAllSafe as u64).into();
+        }
 
-                permissions
-            } else {
-                (Permission::GrantAllSafe as u64).into()
+        // 2. Apply default allows and denies for channel.
+        if let Some(default) = default_permissions {
+            permissions.apply((*default).into());
+        }
+
+        // 3. Resolve each role in order.
+        let member_roles: HashSet<&String> = member.roles.iter().collect();
+
+        if !member_roles.is_empty() {
+            let mut roles = role_permissions
+                .iter()
+                .filter(|(id, _)| member_roles.contains(id))
+                .filter_map(|(id, permission)| {
+                    server.roles.get(id).map(|role| {
+                        let v: Override = (*permission).into();
+                        (role.rank, v)
+                    })
+                })
+                .collect::<Vec<(i64, Override)>>();
+
+            roles.sort_by(|a, b| b.0.cmp(&a.0));
+
+            // 4. Apply allows and denies from roles.
+            for (_, v) in roles {
+                permissions.apply(v);
             }
         }
-    };
 
-    Ok(value)
+        // 5. Revoke permissions if member is timed out.
+        if member.in_timeout() {
+            permissions.restrict(*ALLOW_IN_TIMEOUT);
+        }
+
+        permissions
+    } else {
+        (Permission::GrantAllSafe as u64).into()
+    }
 }
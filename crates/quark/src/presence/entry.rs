@@ -2,6 +2,8 @@ use std::env;
 
 use serde::{Deserialize, Serialize};
 
+use crate::models::user::Presence;
+
 lazy_static! {
     pub static ref REGION_ID: u16 = env::var("REGION_ID")
         .unwrap_or_else(|_| "0".to_string())
@@ -11,7 +13,7 @@ lazy_static! {
 }
 
 /// Compact presence information for a user
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct PresenceEntry {
     /// Region this session exists in
     ///
@@ -36,6 +38,73 @@ impl PresenceEntry {
     }
 }
 
+/// Flag set on sessions where the client detected that the user is away
+const FLAG_IDLE: u8 = 1 << 3;
+
+/// Bits used to store the presence chosen by a session
+const PRESENCE_MASK: u8 = 0b111;
+
+/// Presence chosen by a single session
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct SessionPresence {
+    /// Presence picked for this session, overrides the user's status
+    pub presence: Option<Presence>,
+    /// Whether the user is away from this session
+    pub idle: bool,
+}
+
+impl SessionPresence {
+    /// Pack into presence entry flags
+    pub fn to_flags(&self) -> u8 {
+        let presence = match self.presence {
+            None => 0,
+            Some(Presence::Online) => 1,
+            Some(Presence::Idle) => 2,
+            Some(Presence::Focus) => 3,
+            Some(Presence::Busy) => 4,
+            Some(Presence::Invisible) => 5,
+        };
+
+        if self.idle {
+            presence | FLAG_IDLE
+        } else {
+            presence
+        }
+    }
+
+    /// Unpack from presence entry flags
+    pub fn from_flags(flags: u8) -> Self {
+        Self {
+            presence: match flags & PRESENCE_MASK {
+                1 => Some(Presence::Online),
+                2 => Some(Presence::Idle),
+                3 => Some(Presence::Focus),
+                4 => Some(Presence::Busy),
+                5 => Some(Presence::Invisible),
+                _ => None,
+            },
+            idle: flags & FLAG_IDLE == FLAG_IDLE,
+        }
+    }
+
+    /// Presence this session appears with given the user's own status
+    ///
+    /// Idle sessions only affect users who would otherwise appear online.
+    pub fn resolve(&self, status: Option<&Presence>) -> Presence {
+        let presence = self
+            .presence
+            .clone()
+            .or_else(|| status.cloned())
+            .unwrap_or(Presence::Online);
+
+        if self.idle && presence == Presence::Online {
+            Presence::Idle
+        } else {
+            presence
+        }
+    }
+}
+
 pub trait PresenceOp {
     /// Find next available session ID
     fn find_next_id(&self) -> u8;
@@ -62,3 +131,70 @@ impl PresenceOp for Vec<PresenceEntry> {
         255
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flags_round_trip() {
+        for presence in [
+            None,
+            Some(Presence::Online),
+            Some(Presence::Idle),
+            Some(Presence::Focus),
+            Some(Presence::Busy),
+            Some(Presence::Invisible),
+        ] {
+            for idle in [false, true] {
+                let session = SessionPresence {
+                    presence: presence.clone(),
+                    idle,
+                };
+
+                assert_eq!(SessionPresence::from_flags(session.to_flags()), session);
+            }
+        }
+
+        assert_eq!(SessionPresence::default().to_flags(), 0);
+        assert_eq!(
+            SessionPresence::from_flags(0b1111),
+            SessionPresence {
+                presence: None,
+                idle: true
+            }
+        );
+    }
+
+    #[test]
+    fn resolve() {
+        let idle = SessionPresence {
+            presence: None,
+            idle: true,
+        };
+
+        // Sessions fall back to the user's status, then to online.
+        assert_eq!(SessionPresence::default().resolve(None), Presence::Online);
+        assert_eq!(
+            SessionPresence::default().resolve(Some(&Presence::Busy)),
+            Presence::Busy
+        );
+
+        // Presence picked by the session overrides the user's status.
+        let focus = SessionPresence {
+            presence: Some(Presence::Focus),
+            idle: true,
+        };
+
+        assert_eq!(focus.resolve(Some(&Presence::Busy)), Presence::Focus);
+
+        // Going idle only affects users who would otherwise appear online.
+        assert_eq!(idle.resolve(None), Presence::Idle);
+        assert_eq!(idle.resolve(Some(&Presence::Online)), Presence::Idle);
+        assert_eq!(idle.resolve(Some(&Presence::Busy)), Presence::Busy);
+        assert_eq!(
+            idle.resolve(Some(&Presence::Invisible)),
+            Presence::Invisible
+        );
+    }
+}
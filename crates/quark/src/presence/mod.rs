@@ -1,17 +1,20 @@
 use std::collections::HashSet;
 
-use redis_kiss::{get_connection, AsyncCommands};
-
 mod entry;
-mod operations;
+mod store;
 
-use entry::{PresenceEntry, PresenceOp};
-use operations::{
-    __add_to_set_sessions, __delete_key_presence_entry, __get_key_presence_entry,
-    __get_set_sessions, __remove_from_set_sessions, __set_key_presence_entry,
+pub use entry::{PresenceEntry, SessionPresence};
+pub use store::{
+    configure_presence_store, presence_store, set_presence_store, MemoryPresenceStore,
+    PresenceStore, RedisPresenceStore,
 };
 
-use crate::presence::operations::__delete_set_sessions;
+use entry::PresenceOp;
+
+use crate::{
+    models::{user::Presence, User},
+    Result,
+};
 
 use self::entry::REGION_KEY;
 
@@ -19,11 +22,21 @@ use self::entry::REGION_KEY;
 pub async fn presence_create_session(user_id: &str, flags: u8) -> (bool, u8) {
     info!("Creating a presence session for {user_id} with flags {flags}");
 
+    match presence_create_session_internal(user_id, flags).await {
+        Ok(result) => result,
+        Err(err) => {
+            error!("Failed to create presence session for {user_id}: {err:?}");
+            (false, 0)
+        }
+    }
+}
+
+/// Create a new presence session
+async fn presence_create_session_internal(user_id: &str, flags: u8) -> Result<(bool, u8)> {
+    let store = presence_store();
+
     // Try to find the presence entry for this user.
-    let mut conn = get_connection().await.unwrap();
-    let mut entry: Vec<PresenceEntry> = __get_key_presence_entry(&mut conn, user_id)
-        .await
-        .unwrap_or_default();
+    let mut entry: Vec<PresenceEntry> = store.get_entry(user_id).await?.unwrap_or_default();
 
     // Return whether this was the first session.
     let was_empty = entry.is_empty();
@@ -32,16 +45,27 @@ pub async fn presence_create_session(user_id: &str, flags: u8) -> (bool, u8) {
     // Generate session ID and push new entry.
     let session_id = entry.find_next_id();
     entry.push(PresenceEntry::from(session_id, flags));
-    __set_key_presence_entry(&mut conn, user_id, entry).await;
+    store.set_entry(user_id, entry).await?;
 
     // Add to region set in case of failure.
-    __add_to_set_sessions(&mut conn, &*REGION_KEY, user_id, session_id).await;
-    (was_empty, session_id)
+    store
+        .add_region_session(&REGION_KEY, user_id, session_id)
+        .await?;
+
+    Ok((was_empty, session_id))
 }
 
 /// Delete existing presence session
 pub async fn presence_delete_session(user_id: &str, session_id: u8) -> bool {
-    presence_delete_session_internal(user_id, session_id, false).await
+    info!("Deleting presence session for {user_id} with id {session_id}");
+
+    match presence_delete_session_internal(user_id, session_id, false).await {
+        Ok(is_empty) => is_empty,
+        Err(err) => {
+            error!("Failed to delete presence session for {user_id}: {err:?}");
+            false
+        }
+    }
 }
 
 /// Delete existing presence session (but also choose whether to skip region)
@@ -49,16 +73,14 @@ async fn presence_delete_session_internal(
     user_id: &str,
     session_id: u8,
     skip_region: bool,
-) -> bool {
-    info!("Deleting presence session for {user_id} with id {session_id}");
+) -> Result<bool> {
+    let store = presence_store();
 
     // Return whether this was the last session.
     let mut is_empty = false;
 
     // Only continue if we can actually find one.
-    let mut conn = get_connection().await.unwrap();
-    let entry: Option<Vec<PresenceEntry>> = __get_key_presence_entry(&mut conn, user_id).await;
-    if let Some(entry) = entry {
+    if let Some(entry) = store.get_entry(user_id).await? {
         let entries = entry
             .into_iter()
             .filter(|x| x.session_id != session_id)
@@ -66,15 +88,17 @@ async fn presence_delete_session_internal(
 
         // If entry is empty, then just delete it.
         if entries.is_empty() {
-            __delete_key_presence_entry(&mut conn, user_id).await;
+            store.delete_entry(user_id).await?;
             is_empty = true;
         } else {
-            __set_key_presence_entry(&mut conn, user_id, entries).await;
+            store.set_entry(user_id, entries).await?;
         }
 
         // Remove from region set.
         if !skip_region {
-            __remove_from_set_sessions(&mut conn, &*REGION_KEY, user_id, session_id).await;
+            store
+                .remove_region_session(&REGION_KEY, user_id, session_id)
+                .await?;
         }
     }
 
@@ -82,61 +106,130 @@ async fn presence_delete_session_internal(
         info!("User ID {} just went offline.", &user_id);
     }
 
-    is_empty
+    Ok(is_empty)
+}
+
+/// Update the presence chosen by an existing session
+pub async fn presence_update_session(user_id: &str, session_id: u8, presence: &SessionPresence) {
+    let store = presence_store();
+    let result = async {
+        if let Some(mut entry) = store.get_entry(user_id).await? {
+            for session in &mut entry {
+                if session.session_id == session_id {
+                    session.flags = presence.to_flags();
+                }
+            }
+
+            store.set_entry(user_id, entry).await?;
+        }
+
+        Ok::<_, crate::Error>(())
+    }
+    .await;
+
+    if let Err(err) = result {
+        error!("Failed to update presence session for {user_id}: {err:?}");
+    }
+}
+
+/// How active a presence is, higher wins when combining sessions
+fn activity(presence: &Presence) -> u8 {
+    match presence {
+        Presence::Online => 4,
+        Presence::Focus => 3,
+        Presence::Busy => 2,
+        Presence::Idle => 1,
+        Presence::Invisible => 0,
+    }
+}
+
+/// Combine the presence of each session given the user's own status
+///
+/// Appearing invisible on the account hides every session,
+/// otherwise the most active session wins.
+fn combine(sessions: &[SessionPresence], status: Option<&Presence>) -> Option<Presence> {
+    if sessions.is_empty() {
+        return None;
+    }
+
+    if status == Some(&Presence::Invisible) {
+        return Some(Presence::Invisible);
+    }
+
+    sessions
+        .iter()
+        .map(|session| session.resolve(status))
+        .max_by_key(activity)
+}
+
+/// Work out the presence a user appears with across all of their sessions
+///
+/// Returns None if the user has no sessions.
+pub async fn presence_effective(user_id: &str, status: Option<&Presence>) -> Option<Presence> {
+    let sessions: Vec<SessionPresence> = presence_store()
+        .get_entry(user_id)
+        .await
+        .ok()
+        .flatten()?
+        .iter()
+        .map(|entry| SessionPresence::from_flags(entry.flags))
+        .collect();
+
+    combine(&sessions, status)
+}
+
+/// Fill in whether a user is online along with the presence they appear with
+///
+/// Users appearing invisible are hidden once made foreign.
+pub async fn presence_populate(user: &mut User) {
+    let status = user
+        .status
+        .as_ref()
+        .and_then(|status| status.presence.as_ref());
+    let presence = presence_effective(&user.id, status).await;
+
+    user.online = Some(presence.is_some());
+    if let Some(presence) = presence {
+        user.status.get_or_insert_with(Default::default).presence = Some(presence);
+    }
 }
 
 /// Check whether a given user ID is online
 pub async fn presence_is_online(user_id: &str) -> bool {
-    if let Ok(mut conn) = get_connection().await {
-        conn.exists(user_id).await.unwrap_or(false)
-    } else {
-        false
-    }
+    presence_filter_online(&[user_id.to_string()])
+        .await
+        .contains(user_id)
 }
 
 /// Check whether a set of users is online, returns a set of the online user IDs
 pub async fn presence_filter_online(user_ids: &'_ [String]) -> HashSet<String> {
     // Ignore empty list immediately, to save time.
-    let mut set = HashSet::new();
     if user_ids.is_empty() {
-        return set;
+        return HashSet::new();
     }
 
-    // We need to handle a special case where only one is present
-    // as for some reason or another, Redis does not like us sending
-    // a list of just one ID to the server.
-    if user_ids.len() == 1 {
-        if presence_is_online(&user_ids[0]).await {
-            set.insert(user_ids[0].to_string());
+    match presence_store().filter_online(user_ids).await {
+        Ok(set) => set,
+        Err(err) => {
+            error!("Failed to check which users are online: {err:?}");
+            HashSet::new()
         }
-
-        return set;
     }
-
-    // Otherwise, go ahead as normal.
-    if let Ok(mut conn) = get_connection().await {
-        let data: Vec<Option<Vec<u8>>> = conn.get(user_ids).await.unwrap_or_default();
-        if data.is_empty() {
-            return set;
-        }
-
-        // We filter known values to figure out who is online.
-        for i in 0..user_ids.len() {
-            if data[i].is_some() {
-                set.insert(user_ids[i].to_string());
-            }
-        }
-    }
-
-    set
 }
 
 /// Reset any stale presence data
 pub async fn presence_clear_region(region_id: Option<&str>) {
     let region_id = region_id.unwrap_or(&*REGION_KEY);
-    let mut conn = get_connection().await.expect("Redis connection");
+    let store = presence_store();
+
+    let sessions = match store.get_region_sessions(region_id).await {
+        Ok(sessions) => sessions,
+        Err(err) => {
+            error!("Failed to fetch sessions in {region_id}, skipping clean up: {err:?}");
+            return;
+        }
+    };
 
-    let sessions = __get_set_sessions(&mut conn, region_id).await;
     if !sessions.is_empty() {
         info!(
             "Cleaning up {} sessions, this may take a while...",
@@ -149,14 +242,87 @@ pub async fn presence_clear_region(region_id: Option<&str>) {
             let parts = session.split(':').collect::<Vec<&str>>();
             if let (Some(user_id), Some(session_id)) = (parts.get(0), parts.get(1)) {
                 if let Ok(session_id) = session_id.parse() {
-                    presence_delete_session_internal(user_id, session_id, true).await;
+                    if let Err(err) =
+                        presence_delete_session_internal(user_id, session_id, true).await
+                    {
+                        error!("Failed to clean up session {session}: {err:?}");
+                    }
                 }
             }
         }
 
-        // Then clear the set in Redis.
-        __delete_set_sessions(&mut conn, region_id).await;
+        // Then clear the set.
+        if let Err(err) = store.delete_region(region_id).await {
+            error!("Failed to clear sessions in {region_id}: {err:?}");
+        }
 
         info!("Clean up complete.");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(presence: Option<Presence>, idle: bool) -> SessionPresence {
+        SessionPresence { presence, idle }
+    }
+
+    #[test]
+    fn activity_order() {
+        let mut presences = vec![
+            Presence::Idle,
+            Presence::Invisible,
+            Presence::Online,
+            Presence::Busy,
+            Presence::Focus,
+        ];
+
+        presences.sort_by_key(activity);
+        assert_eq!(
+            presences,
+            vec![
+                Presence::Invisible,
+                Presence::Idle,
+                Presence::Busy,
+                Presence::Focus,
+                Presence::Online,
+            ]
+        );
+    }
+
+    #[test]
+    fn most_active_session_wins() {
+        assert_eq!(combine(&[], Some(&Presence::Online)), None);
+
+        let sessions = [
+            session(Some(Presence::Busy), false),
+            session(None, true),
+            session(Some(Presence::Invisible), false),
+        ];
+
+        assert_eq!(combine(&sessions, None), Some(Presence::Busy));
+        assert_eq!(
+            combine(&sessions, Some(&Presence::Focus)),
+            Some(Presence::Focus)
+        );
+
+        assert_eq!(
+            combine(&[session(None, false), session(None, true)], None),
+            Some(Presence::Online)
+        );
+    }
+
+    #[test]
+    fn account_invisible_wins() {
+        let sessions = [
+            session(Some(Presence::Online), false),
+            session(Some(Presence::Focus), false),
+        ];
+
+        assert_eq!(
+            combine(&sessions, Some(&Presence::Invisible)),
+            Some(Presence::Invisible)
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+};
+
+use super::PresenceStore;
+use crate::{presence::PresenceEntry, Result};
+
+/// Presence store which keeps everything in the current process
+///
+/// Useful for single node deployments and tests.
+#[derive(Default)]
+pub struct MemoryPresenceStore {
+    entries: Mutex<HashMap<String, Vec<PresenceEntry>>>,
+    regions: Mutex<HashMap<String, HashSet<String>>>,
+}
+
+#[async_trait]
+impl PresenceStore for MemoryPresenceStore {
+    async fn get_entry(&self, user_id: &str) -> Result<Option<Vec<PresenceEntry>>> {
+        Ok(self.entries.lock().unwrap().get(user_id).cloned())
+    }
+
+    async fn set_entry(&self, user_id: &str, entry: Vec<PresenceEntry>) -> Result<()> {
+        self.entries
+            .lock()
+            .unwrap()
+            .insert(user_id.to_string(), entry);
+        Ok(())
+    }
+
+    async fn delete_entry(&self, user_id: &str) -> Result<()> {
+        self.entries.lock().unwrap().remove(user_id);
+        Ok(())
+    }
+
+    async fn filter_online(&self, user_ids: &[String]) -> Result<HashSet<String>> {
+        let entries = self.entries.lock().unwrap();
+        Ok(user_ids
+            .iter()
+            .filter(|id| entries.contains_key(*id))
+            .cloned()
+            .collect())
+    }
+
+    async fn add_region_session(
+        &self,
+        region_id: &str,
+        user_id: &str,
+        session_id: u8,
+    ) -> Result<()> {
+        self.regions
+            .lock()
+            .unwrap()
+            .entry(region_id.to_string())
+            .or_default()
+            .insert(format!("{user_id}:{session_id}"));
+        Ok(())
+    }
+
+    async fn remove_region_session(
+        &self,
+        region_id: &str,
+        user_id: &str,
+        session_id: u8,
+    ) -> Result<()> {
+        if let Some(sessions) = self.regions.lock().unwrap().get_mut(region_id) {
+            sessions.remove(&format!("{user_id}:{session_id}"));
+        }
+
+        Ok(())
+    }
+
+    async fn get_region_sessions(&self, region_id: &str) -> Result<Vec<String>> {
+        Ok(self
+            .regions
+            .lock()
+            .unwrap()
+            .get(region_id)
+            .map(|sessions| sessions.iter().cloned().collect())
+            .unwrap_or_default())
+    }
+
+    async fn delete_region(&self, region_id: &str) -> Result<()> {
+        self.regions.lock().unwrap().remove(region_id);
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::collections::HashSet;
+
+use once_cell::sync::OnceCell;
+
+use super::PresenceEntry;
+use crate::{variables::delta::USE_LOCAL_EVENTS, Result};
+
+mod memory;
+mod redis;
+
+pub use self::memory::MemoryPresenceStore;
+pub use self::redis::RedisPresenceStore;
+
+/// Storage for presence sessions and the regions they belong to
+#[async_trait]
+pub trait PresenceStore: Sync + Send {
+    /// Fetch all sessions of a user
+    async fn get_entry(&self, user_id: &str) -> Result<Option<Vec<PresenceEntry>>>;
+
+    /// Replace all sessions of a user
+    async fn set_entry(&self, user_id: &str, entry: Vec<PresenceEntry>) -> Result<()>;
+
+    /// Remove all sessions of a user
+    async fn delete_entry(&self, user_id: &str) -> Result<()>;
+
+    /// Find which of the given users have any sessions
+    async fn filter_online(&self, user_ids: &[String]) -> Result<HashSet<String>>;
+
+    /// Record a session as belonging to a region
+    async fn add_region_session(
+        &self,
+        region_id: &str,
+        user_id: &str,
+        session_id: u8,
+    ) -> Result<()>;
+
+    /// Remove a session from a region
+    async fn remove_region_session(
+        &self,
+        region_id: &str,
+        user_id: &str,
+        session_id: u8,
+    ) -> Result<()>;
+
+    /// List the sessions in a region as `user_id:session_id`
+    async fn get_region_sessions(&self, region_id: &str) -> Result<Vec<String>>;
+
+    /// Forget every session in a region
+    async fn delete_region(&self, region_id: &str) -> Result<()>;
+}
+
+static PRESENCE_STORE: OnceCell<Box<dyn PresenceStore>> = OnceCell::new();
+
+/// Use a different presence store for this process
+///
+/// Must be called before any presence information is read or written.
+pub fn set_presence_store<S: PresenceStore + 'static>(store: S) {
+    if PRESENCE_STORE.set(Box::new(store)).is_err() {
+        panic!("Presence store was already initialised.");
+    }
+}
+
+/// Use the presence store selected through `REVOLT_EVENT_BUS`
+///
+/// Presence is kept in memory alongside the in-process event bus.
+/// Should be called on startup, before any presence information is read or written.
+pub fn configure_presence_store() {
+    if *USE_LOCAL_EVENTS {
+        info!("Using the in-memory presence store");
+        set_presence_store(MemoryPresenceStore::default());
+    }
+}
+
+/// Get the presence store for this process, Redis unless configured otherwise
+pub fn presence_store() -> &'static dyn PresenceStore {
+    PRESENCE_STORE
+        .get_or_init(|| Box::new(RedisPresenceStore))
+        .as_ref()
+}
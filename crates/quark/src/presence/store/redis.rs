@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use redis_kiss::{get_connection, AsyncCommands, Conn};
+
+use super::PresenceStore;
+use crate::{metrics::REDIS_ERRORS, presence::PresenceEntry, Error, Result};
+
+/// Presence store backed by Redis, shared between all nodes
+pub struct RedisPresenceStore;
+
+/// Error for a failed presence operation
+fn error(operation: &'static str) -> Error {
+    REDIS_ERRORS.with_label_values(&[operation]).inc();
+    Error::DatabaseError {
+        operation,
+        with: "presence",
+    }
+}
+
+/// Get a connection to Redis
+async fn connect() -> Result<Conn> {
+    get_connection().await.map_err(|_| error("connect"))
+}
+
+#[async_trait]
+impl PresenceStore for RedisPresenceStore {
+    async fn get_entry(&self, user_id: &str) -> Result<Option<Vec<PresenceEntry>>> {
+        connect()
+            .await?
+            .get::<_, Option<Vec<u8>>>(user_id)
+            .await
+            .map_err(|_| error("get"))?
+            .map(|entry| bincode::deserialize(&entry[..]).map_err(|_| error("deserialise")))
+            .transpose()
+    }
+
+    async fn set_entry(&self, user_id: &str, entry: Vec<PresenceEntry>) -> Result<()> {
+        let data = bincode::serialize(&entry).map_err(|_| error("serialise"))?;
+        connect()
+            .await?
+            .set(user_id, data)
+            .await
+            .map_err(|_| error("set"))
+    }
+
+    async fn delete_entry(&self, user_id: &str) -> Result<()> {
+        connect()
+            .await?
+            .del(user_id)
+            .await
+            .map_err(|_| error("del"))
+    }
+
+    async fn filter_online(&self, user_ids: &[String]) -> Result<HashSet<String>> {
+        let mut conn = connect().await?;
+        let mut set = HashSet::new();
+
+        // We need to handle a special case where only one is present
+        // as for some reason or another, Redis does not like us sending
+        // a list of just one ID to the server.
+        if user_ids.len() == 1 {
+            if conn
+                .exists(&user_ids[0])
+                .await
+                .map_err(|_| error("exists"))?
+            {
+                set.insert(user_ids[0].to_string());
+            }
+
+            return Ok(set);
+        }
+
+        // Otherwise, go ahead as normal.
+        let data: Vec<Option<Vec<u8>>> = conn.get(user_ids).await.map_err(|_| error("get"))?;
+
+        // We filter known values to figure out who is online.
+        for (user_id, entry) in user_ids.iter().zip(data) {
+            if entry.is_some() {
+                set.insert(user_id.to_string());
+            }
+        }
+
+        Ok(set)
+    }
+
+    async fn add_region_session(
+        &self,
+        region_id: &str,
+        user_id: &str,
+        session_id: u8,
+    ) -> Result<()> {
+        connect()
+            .await?
+            .sadd(region_id, format!("{user_id}:{session_id}"))
+            .await
+            .map_err(|_| error("sadd"))
+    }
+
+    async fn remove_region_session(
+        &self,
+        region_id: &str,
+        user_id: &str,
+        session_id: u8,
+    ) -> Result<()> {
+        connect()
+            .await?
+            .srem(region_id, format!("{user_id}:{session_id}"))
+            .await
+            .map_err(|_| error("srem"))
+    }
+
+    async fn get_region_sessions(&self, region_id: &str) -> Result<Vec<String>> {
+        connect()
+            .await?
+            .smembers(region_id)
+            .await
+            .map_err(|_| error("smembers"))
+    }
+
+    async fn delete_region(&self, region_id: &str) -> Result<()> {
+        connect()
+            .await?
+            .del(region_id)
+            .await
+            .map_err(|_| error("del"))
+    }
+}
@@ -1,14 +1,16 @@
 // Queue Type: Debounced
 use crate::Database;
 
-use deadqueue::limited::Queue;
-use mongodb::bson::doc;
-use std::{collections::HashMap, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use super::DelayedTask;
+use super::{claim, enqueue, DelayedTask, POLL_INTERVAL};
+
+/// Name of the job queue
+pub(super) static QUEUE: &str = "ack";
 
 /// Enumeration of possible events
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, Eq, PartialEq)]
 pub enum AckEvent {
     /// Add mentions for a user in a channel
     AddMention {
@@ -24,6 +26,7 @@ pub enum AckEvent {
 }
 
 /// Task information
+#[derive(Serialize, Deserialize)]
 struct Data {
     /// Channel to ack
     channel: String,
@@ -38,23 +41,23 @@ struct Task {
     event: AckEvent,
 }
 
-lazy_static! {
-    static ref Q: Queue<Data> = Queue::new(10_000);
-}
-
 /// Queue a new task for a worker
-pub async fn queue(channel: String, user: String, event: AckEvent) {
-    Q.try_push(Data {
-        channel,
-        user,
-        event,
-    })
-    .ok();
-
-    info!("Queue is using {} slots from {}.", Q.len(), Q.capacity());
+pub async fn queue(db: &Database, channel: String, user: String, event: AckEvent) {
+    enqueue(
+        db,
+        QUEUE,
+        &Data {
+            channel,
+            user,
+            event,
+        },
+    )
+    .await;
 }
 
 /// Start a new worker
+///
+/// Only one worker should run per node, as each keeps its own debounce state.
 pub async fn worker(db: Database) {
     let mut tasks = HashMap::<(String, String), DelayedTask<Task>>::new();
     let mut keys = vec![];
@@ -70,19 +73,23 @@ pub async fn worker(db: Database) {
         // Commit any due tasks to the database.
         for key in &keys {
             if let Some(task) = tasks.remove(key) {
-                let Task { event } = task.data;
                 let (user, channel) = key;
+                let event = &task.data.event;
 
-                if let Err(err) = match &event {
+                let result = match event {
                     AckEvent::AckMessage { id } => db.acknowledge_message(channel, user, id).await,
                     AckEvent::AddMention { ids } => {
                         db.add_mention_to_unread(channel, user, ids).await
                     }
-                } {
+                };
+
+                if let Err(err) = &result {
                     error!("{err:?} for {event:?}. ({user}, {channel})");
                 } else {
                     info!("User {user} ack in {channel} with {event:?}");
                 }
+
+                task.finish(&db, result).await;
             }
         }
 
@@ -90,15 +97,22 @@ pub async fn worker(db: Database) {
         keys.clear();
 
         // Queue incoming tasks.
-        while let Some(Data {
-            channel,
-            user,
-            mut event,
-        }) = Q.try_pop()
-        {
+        while let Some(job) = claim(&db, QUEUE).await {
+            let Data {
+                channel,
+                user,
+                mut event,
+            } = match job.payload() {
+                Ok(data) => data,
+                Err(err) => {
+                    job.finish(&db, Err(err)).await;
+                    continue;
+                }
+            };
+
             let key = (user, channel);
-            if let Some(mut task) = tasks.get_mut(&key) {
-                task.delay();
+            if let Some(task) = tasks.get_mut(&key) {
+                task.delay(job);
 
                 match &mut event {
                     AckEvent::AddMention { ids } => {
@@ -113,11 +127,11 @@ pub async fn worker(db: Database) {
                     }
                 }
             } else {
-                tasks.insert(key, DelayedTask::new(Task { event }));
+                tasks.insert(key, DelayedTask::new(Task { event }, job));
             }
         }
 
         // Sleep for an arbitrary amount of time.
-        async_std::task::sleep(Duration::from_secs(1)).await;
+        async_std::task::sleep(POLL_INTERVAL).await;
     }
 }
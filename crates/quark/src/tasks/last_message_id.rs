@@ -1,13 +1,16 @@
 // Queue Type: Debounced
-use crate::{models::channel::PartialChannel, Database};
+use crate::{models::Job, Database};
 
-use deadqueue::limited::Queue;
-use mongodb::bson::doc;
-use std::{collections::HashMap, time::Duration};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use super::DelayedTask;
+use super::{claim, enqueue, DelayedTask, POLL_INTERVAL};
+
+/// Name of the job queue
+pub(super) static QUEUE: &str = "last_message_id";
 
 /// Task information
+#[derive(Serialize, Deserialize)]
 struct Data {
     /// Channel to update
     channel: String,
@@ -26,17 +29,14 @@ struct Task {
     is_dm: bool,
 }
 
-lazy_static! {
-    static ref Q: Queue<Data> = Queue::new(10_000);
-}
-
 /// Queue a new task for a worker
-pub async fn queue(channel: String, id: String, is_dm: bool) {
-    Q.try_push(Data { channel, id, is_dm }).ok();
-    info!("Queue is using {} slots from {}.", Q.len(), Q.capacity());
+pub async fn queue(db: &Database, channel: String, id: String, is_dm: bool) {
+    enqueue(db, QUEUE, &Data { channel, id, is_dm }).await;
 }
 
 /// Start a new worker
+///
+/// Only one worker should run per node, as each keeps its own debounce state.
 pub async fn worker(db: Database) {
     let mut tasks = HashMap::<String, DelayedTask<Task>>::new();
     let mut keys = vec![];
@@ -52,21 +52,7 @@ pub async fn worker(db: Database) {
         // Commit any due tasks to the database.
         for key in &keys {
             if let Some(task) = tasks.remove(key) {
-                let Task { id, is_dm, .. } = task.data;
-
-                let mut channel = PartialChannel {
-                    last_message_id: Some(id.to_string()),
-                    ..Default::default()
-                };
-
-                if is_dm {
-                    channel.active = Some(true);
-                }
-
-                match db.update_channel(key, &channel, vec![]).await {
-                    Ok(_) => info!("Updated last_message_id for {key} to {id}."),
-                    Err(err) => error!("Failed to update last_message_id with {err:?}!"),
-                }
+                commit(&db, key, task).await;
             }
         }
 
@@ -74,16 +60,110 @@ pub async fn worker(db: Database) {
         keys.clear();
 
         // Queue incoming tasks.
-        while let Some(Data { channel, id, is_dm }) = Q.try_pop() {
-            if let Some(mut task) = tasks.get_mut(&channel) {
-                task.data.id = id;
-                task.delay();
-            } else {
-                tasks.insert(channel, DelayedTask::new(Task { id, is_dm }));
+        while let Some(job) = claim(&db, QUEUE).await {
+            match job.payload() {
+                Ok(data) => merge(&mut tasks, data, job),
+                Err(err) => job.finish(&db, Err(err)).await,
             }
         }
 
         // Sleep for an arbitrary amount of time.
-        async_std::task::sleep(Duration::from_secs(1)).await;
+        async_std::task::sleep(POLL_INTERVAL).await;
+    }
+}
+
+/// Merge a claimed job into any task already pending for its channel
+fn merge(tasks: &mut HashMap<String, DelayedTask<Task>>, data: Data, job: Job) {
+    let Data { channel, id, is_dm } = data;
+    if let Some(task) = tasks.get_mut(&channel) {
+        // Jobs may be claimed out of order, keep the newest id.
+        if id > task.data.id {
+            task.data.id = id;
+        }
+
+        task.delay(job);
+    } else {
+        tasks.insert(channel, DelayedTask::new(Task { id, is_dm }, job));
+    }
+}
+
+/// Commit a task to the database
+async fn commit(db: &Database, channel: &str, task: DelayedTask<Task>) {
+    let Task { id, is_dm } = &task.data;
+    let result = db.update_last_message_id(channel, id, *is_dm).await;
+    match &result {
+        Ok(_) => info!("Updated last_message_id for {channel} to {id}."),
+        Err(err) => error!("Failed to update last_message_id with {err:?}!"),
+    }
+
+    task.finish(db, result).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Channel;
+
+    static OLDER: &str = "01GAAAAAAAAAAAAAAAAAAAAAAA";
+    static NEWER: &str = "01GBBBBBBBBBBBBBBBBBBBBBBB";
+
+    fn job(channel: &str, id: &str) -> (Data, Job) {
+        let data = Data {
+            channel: channel.to_string(),
+            id: id.to_string(),
+            is_dm: false,
+        };
+
+        let job = Job::new(QUEUE, &data).unwrap();
+        (data, job)
+    }
+
+    async fn last_message_id(db: &Database, channel: &str) -> Option<String> {
+        match db.fetch_channel(channel).await.unwrap() {
+            Channel::TextChannel {
+                last_message_id, ..
+            } => last_message_id,
+            channel => panic!("unexpected channel {channel:?}"),
+        }
+    }
+
+    #[async_std::test]
+    async fn interleaved_jobs() {
+        let db = Database::Reference(Default::default());
+        let channel = "channel";
+        db.insert_channel(&Channel::TextChannel {
+            id: channel.to_string(),
+            server: "server".to_string(),
+            name: "general".to_string(),
+            description: None,
+            icon: None,
+            last_message_id: None,
+            default_permissions: None,
+            role_permissions: Default::default(),
+            nsfw: false,
+        })
+        .await
+        .unwrap();
+
+        // A single worker keeps the newest id whichever order jobs arrive in.
+        let mut tasks = HashMap::new();
+        for (data, job) in [job(channel, NEWER), job(channel, OLDER)] {
+            merge(&mut tasks, data, job);
+        }
+
+        assert_eq!(tasks[channel].data.id, NEWER);
+        assert_eq!(tasks[channel].jobs.len(), 2);
+
+        // Separate workers may commit out of order, which mustn't go backwards.
+        for id in [NEWER, OLDER] {
+            let mut tasks = HashMap::new();
+            let (data, job) = job(channel, id);
+            merge(&mut tasks, data, job);
+
+            let task = tasks.remove(channel).unwrap();
+            commit(&db, channel, task).await;
+        }
+
+        assert_eq!(last_message_id(&db, channel).await.as_deref(), Some(NEWER));
     }
 }
@@ -1,12 +1,34 @@
 //! Semi-important background task management
+//!
+//! Tasks are persisted as jobs in the database, so they survive restarts
+//! and are retried with backoff if they fail, see [Job].
 
-use crate::Database;
+use crate::{
+    metrics::{record_error, TASK_QUEUE_DEPTH},
+    models::{Job, Message, MessageRevision},
+    Database, Result,
+};
 
 use async_std::task;
-use std::time::Instant;
+use serde::Serialize;
+use std::time::{Duration, Instant};
 
 const WORKER_COUNT: usize = 5;
 
+/// How long a claimed job stays hidden from other workers.
+///
+/// Must outlast the debounce period of [DelayedTask].
+const VISIBILITY_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// How long to wait before checking an empty queue again.
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// How often queue depths are sampled for metrics.
+const METRICS_INTERVAL: Duration = Duration::from_secs(15);
+
+/// How often deleted messages and revisions past their retention are purged.
+const PURGE_INTERVAL: Duration = Duration::from_secs(10 * 60);
+
 pub mod ack;
 pub mod last_message_id;
 pub mod process_embeds;
@@ -14,17 +36,93 @@ pub mod web_push;
 
 /// Spawn background workers
 pub async fn start_workers(db: Database) {
+    // Debounced queues merge jobs in memory, so they get a single consumer each.
+    task::spawn(ack::worker(db.clone()));
+    task::spawn(last_message_id::worker(db.clone()));
+
     for _ in 0..WORKER_COUNT {
-        task::spawn(ack::worker(db.clone()));
-        task::spawn(last_message_id::worker(db.clone()));
         task::spawn(process_embeds::worker(db.clone()));
-        task::spawn(web_push::worker(db.clone().into()));
+        task::spawn(web_push::worker(db.clone()));
+    }
+
+    task::spawn(sample_queue_depths(db.clone()));
+    task::spawn(purge_deleted_messages(db.clone()));
+    task::spawn(purge_message_revisions(db));
+}
+
+/// Periodically record how many jobs are waiting in each queue
+async fn sample_queue_depths(db: Database) {
+    loop {
+        for queue in [
+            ack::QUEUE,
+            last_message_id::QUEUE,
+            process_embeds::QUEUE,
+            web_push::QUEUE,
+        ] {
+            match db.count_jobs(queue).await {
+                Ok(count) => TASK_QUEUE_DEPTH
+                    .with_label_values(&[queue])
+                    .set(count as i64),
+                Err(err) => {
+                    record_error(&err);
+                    error!("Failed to count jobs in {queue}: {err:?}");
+                }
+            }
+        }
+
+        task::sleep(METRICS_INTERVAL).await;
+    }
+}
+
+/// Periodically finalise deleted messages which can no longer be restored
+async fn purge_deleted_messages(db: Database) {
+    loop {
+        if let Err(err) = Message::purge_deleted(&db).await {
+            record_error(&err);
+            error!("Failed to purge deleted messages: {err:?}");
+        }
+
+        task::sleep(PURGE_INTERVAL).await;
+    }
+}
+
+/// Periodically drop message revisions which have fallen out of retention
+async fn purge_message_revisions(db: Database) {
+    loop {
+        if let Err(err) = MessageRevision::purge_expired(&db).await {
+            record_error(&err);
+            error!("Failed to purge message revisions: {err:?}");
+        }
+
+        task::sleep(PURGE_INTERVAL).await;
+    }
+}
+
+/// Persist a new job onto the given queue
+async fn enqueue<T: Serialize>(db: &Database, queue: &str, payload: &T) {
+    if let Err(err) = Job::enqueue(db, queue, payload).await {
+        record_error(&err);
+        error!("Failed to queue job for {queue}: {err:?}");
+    }
+}
+
+/// Claim the next job from the given queue, logging any failure
+async fn claim(db: &Database, queue: &str) -> Option<Job> {
+    match Job::claim(db, queue, VISIBILITY_TIMEOUT).await {
+        Ok(job) => job,
+        Err(err) => {
+            record_error(&err);
+            error!("Failed to claim job from {queue}: {err:?}");
+            None
+        }
     }
 }
 
 /// Task with additional information on when it should run
 pub struct DelayedTask<T> {
     pub data: T,
+    /// Jobs which were merged into this task
+    pub jobs: Vec<Job>,
     last_updated: Instant,
     first_seen: Instant,
 }
@@ -37,19 +135,28 @@ static SAVE_CONSTANT: u64 = 5;
 
 impl<T> DelayedTask<T> {
     /// Create a new delayed task
-    pub fn new(data: T) -> Self {
+    pub fn new(data: T, job: Job) -> Self {
         DelayedTask {
             data,
+            jobs: vec![job],
             last_updated: Instant::now(),
             first_seen: Instant::now(),
         }
     }
 
     /// Push a task further back in time
-    pub fn delay(&mut self) {
+    pub fn delay(&mut self, job: Job) {
+        self.jobs.push(job);
         self.last_updated = Instant::now()
     }
 
+    /// Complete or fail every job merged into this task
+    pub async fn finish(self, db: &Database, result: Result<()>) {
+        for job in self.jobs {
+            job.finish(db, result.clone()).await;
+        }
+    }
+
     /// Check if a task should run yet
     pub fn should_run(&self) -> bool {
         self.first_seen.elapsed().as_secs() > EXPIRE_CONSTANT
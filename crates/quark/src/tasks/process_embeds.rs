@@ -2,13 +2,18 @@ use crate::util::variables::delta::{JANUARY_URL, MAX_EMBED_COUNT};
 use crate::{
     models::{message::AppendMessage, Message},
     types::january::Embed,
-    Database,
+    Database, Result,
 };
 
-use deadqueue::limited::Queue;
+use serde::{Deserialize, Serialize};
+
+use super::{claim, enqueue, POLL_INTERVAL};
+
+/// Name of the job queue
+pub(super) static QUEUE: &str = "process_embeds";
 
 /// Task information
-#[derive(Debug)]
+#[derive(Serialize, Deserialize, Debug)]
 struct EmbedTask {
     /// Channel we're processing the event in
     channel: String,
@@ -18,39 +23,53 @@ struct EmbedTask {
     content: String,
 }
 
-lazy_static! {
-    static ref Q: Queue<EmbedTask> = Queue::new(10_000);
+/// Queue a new task for a worker
+pub async fn queue(db: &Database, channel: String, id: String, content: String) {
+    enqueue(
+        db,
+        QUEUE,
+        &EmbedTask {
+            channel,
+            id,
+            content,
+        },
+    )
+    .await;
 }
 
-/// Queue a new task for a worker
-pub async fn queue(channel: String, id: String, content: String) {
-    Q.try_push(EmbedTask {
-        channel,
-        id,
-        content,
-    })
-    .ok();
-
-    info!("Queue is using {} slots from {}.", Q.len(), Q.capacity());
+/// Generate embeds for a message and append them
+async fn run(db: &Database, task: EmbedTask) -> Result<()> {
+    if let Ok(embeds) = Embed::generate(task.content, &*JANUARY_URL, *MAX_EMBED_COUNT).await {
+        if let Err(err) = Message::append(
+            db,
+            task.id,
+            task.channel,
+            AppendMessage {
+                embeds: Some(embeds),
+            },
+        )
+        .await
+        {
+            error!("Encountered an error appending to message: {:?}", err);
+            return Err(err);
+        }
+    }
+
+    Ok(())
 }
 
 /// Start a new worker
 pub async fn worker(db: Database) {
     loop {
-        let task = Q.pop().await;
-        if let Ok(embeds) = Embed::generate(task.content, &*JANUARY_URL, *MAX_EMBED_COUNT).await {
-            if let Err(err) = Message::append(
-                &db,
-                task.id,
-                task.channel,
-                AppendMessage {
-                    embeds: Some(embeds),
-                },
-            )
-            .await
-            {
-                error!("Encountered an error appending to message: {:?}", err);
-            }
+        if let Some(job) = claim(&db, QUEUE).await {
+            let result = match job.payload() {
+                Ok(task) => run(&db, task).await,
+                Err(err) => Err(err),
+            };
+
+            job.finish(&db, result).await;
+        } else {
+            async_std::task::sleep(POLL_INTERVAL).await;
         }
     }
 }
@@ -0,0 +1,29 @@
+use crate::models::Job;
+use crate::Result;
+
+#[async_trait]
+pub trait AbstractJobQueue: Sync + Send {
+    /// Insert a new job into the database
+    async fn insert_job(&self, job: &Job) -> Result<()>;
+
+    /// Claim the next available job from a queue
+    ///
+    /// The job's attempt count is incremented and it is hidden from
+    /// other workers until `available_at` (milliseconds since epoch).
+    async fn claim_job(&self, queue: &str, now: i64, available_at: i64) -> Result<Option<Job>>;
+
+    /// Remove a job which completed successfully
+    async fn complete_job(&self, id: &str) -> Result<()>;
+
+    /// Release a failed job so it can be retried from the given time
+    async fn retry_job(&self, id: &str, available_at: i64, error: &str) -> Result<()>;
+
+    /// Move a failed job to the dead letter queue
+    async fn bury_job(&self, id: &str, error: &str) -> Result<()>;
+
+    /// Fetch all dead-lettered jobs in a queue
+    async fn fetch_dead_jobs(&self, queue: &str) -> Result<Vec<Job>>;
+
+    /// Count the jobs in a queue which haven't been dead-lettered
+    async fn count_jobs(&self, queue: &str) -> Result<usize>;
+}
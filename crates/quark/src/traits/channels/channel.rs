@@ -23,6 +23,17 @@ pub trait AbstractChannel: Sync + Send {
         remove: Vec<FieldsChannel>,
     ) -> Result<()>;
 
+    /// Move the last message id of a channel forward
+    ///
+    /// Ids older than the current one are ignored, as updates may arrive out of order.
+    /// Direct messages are also marked as active if `activate` is set.
+    async fn update_last_message_id(
+        &self,
+        id: &str,
+        message_id: &str,
+        activate: bool,
+    ) -> Result<()>;
+
     /// Delete a channel by its id
     ///
     /// This will also delete all associated messages and files.
@@ -39,6 +50,9 @@ pub trait AbstractChannel: Sync + Send {
     /// Find a saved message channel owned by a user
     async fn find_saved_messages_channel(&self, user_id: &str) -> Result<Channel>;
 
+    /// Fetch all threads started in any of the given channels
+    async fn fetch_threads(&self, parents: &[String]) -> Result<Vec<Channel>>;
+
     /// Add user to a group
     async fn add_user_to_group(&self, channel: &str, user: &str) -> Result<()>;
 
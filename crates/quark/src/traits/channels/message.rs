@@ -1,4 +1,5 @@
-use crate::models::message::{AppendMessage, Message, MessageSort, PartialMessage};
+use crate::models::message::{AppendMessage, Message, MessageFilter, MessageSort, PartialMessage};
+use crate::models::DeletedMessage;
 use crate::Result;
 
 #[async_trait]
@@ -15,11 +16,36 @@ pub trait AbstractMessage: Sync + Send {
     /// Append information to a given message
     async fn append_message(&self, id: &str, append: &AppendMessage) -> Result<()>;
 
-    /// Delete a message from the database by its id
-    async fn delete_message(&self, id: &str) -> Result<()>;
+    /// Delete a message by its id
+    ///
+    /// The message is kept as a tombstone until it is purged,
+    /// `deleted_at` is given in milliseconds since epoch.
+    async fn delete_message(&self, id: &str, deleted_at: i64) -> Result<()>;
 
     /// Delete messages from a channel by their ids and corresponding channel id
-    async fn delete_messages(&self, channel: &str, ids: Vec<String>) -> Result<()>;
+    ///
+    /// The messages are kept as tombstones until they are purged.
+    async fn delete_messages(&self, channel: &str, ids: Vec<String>, deleted_at: i64)
+        -> Result<()>;
+
+    /// Fetch messages deleted from a channel which have not been purged yet,
+    /// most recently deleted first
+    async fn fetch_deleted_messages(
+        &self,
+        channel: &str,
+        limit: i64,
+    ) -> Result<Vec<DeletedMessage>>;
+
+    /// Restore a deleted message in a channel which has not been purged yet
+    ///
+    /// Messages deleted before `deleted_after` (milliseconds since epoch) can no longer be restored.
+    async fn restore_message(&self, channel: &str, id: &str, deleted_after: i64)
+        -> Result<Message>;
+
+    /// Permanently remove messages deleted before the given time
+    ///
+    /// Attachments on these messages are marked as deleted and their revisions are dropped.
+    async fn purge_deleted_messages(&self, before: i64) -> Result<()>;
 
     /// Fetch multiple messages
     async fn fetch_messages(
@@ -32,14 +58,23 @@ pub trait AbstractMessage: Sync + Send {
         nearby: Option<String>,
     ) -> Result<Vec<Message>>;
 
-    /// Search for messages
+    /// Fetch all pinned messages in a channel, newest first
+    async fn fetch_pinned_messages(&self, channel: &str) -> Result<Vec<Message>>;
+
+    /// Pin a message unless its channel already has `max` pinned messages
+    ///
+    /// The limit must hold even when several messages are pinned at once,
+    /// without turning all of them away while there is still room.
+    async fn pin_message(&self, channel: &str, id: &str, max: usize) -> Result<()>;
+
+    /// Search for messages matching a filter in any of the given channels
+    ///
+    /// Relevance sort falls back to latest first if no text query is given.
     async fn search_messages(
         &self,
-        channel: &str,
-        query: &str,
+        channels: &[String],
+        filter: &MessageFilter,
         limit: Option<i64>,
-        before: Option<String>,
-        after: Option<String>,
         sort: MessageSort,
     ) -> Result<Vec<Message>>;
 
@@ -0,0 +1,24 @@
+use crate::models::MessageRevision;
+use crate::Result;
+
+#[async_trait]
+pub trait AbstractMessageRevision: Sync + Send {
+    /// Insert a new revision of a message
+    async fn insert_message_revision(&self, revision: &MessageRevision) -> Result<()>;
+
+    /// Fetch all stored revisions of a message, oldest first
+    async fn fetch_message_revisions(&self, message: &str) -> Result<Vec<MessageRevision>>;
+
+    /// Delete revisions of a message which were created before the given id
+    async fn delete_message_revisions_before(&self, message: &str, before: &str) -> Result<()>;
+
+    /// Fetch the ids of all channels which have stored revisions
+    async fn fetch_message_revision_channels(&self) -> Result<Vec<String>>;
+
+    /// Delete revisions of any message in a channel which were created before the given id
+    async fn delete_channel_message_revisions_before(
+        &self,
+        channel: &str,
+        before: &str,
+    ) -> Result<()>;
+}
@@ -0,0 +1,25 @@
+use crate::models::webhook::{FieldsWebhook, PartialWebhook, Webhook};
+use crate::Result;
+
+#[async_trait]
+pub trait AbstractWebhook: Sync + Send {
+    /// Insert a new webhook into the database
+    async fn insert_webhook(&self, webhook: &Webhook) -> Result<()>;
+
+    /// Fetch a webhook by its id
+    async fn fetch_webhook(&self, id: &str) -> Result<Webhook>;
+
+    /// Fetch all webhooks belonging to a channel
+    async fn fetch_webhooks_for_channel(&self, channel: &str) -> Result<Vec<Webhook>>;
+
+    /// Update a webhook with new information
+    async fn update_webhook(
+        &self,
+        id: &str,
+        partial: &PartialWebhook,
+        remove: Vec<FieldsWebhook>,
+    ) -> Result<()>;
+
+    /// Delete a webhook by its id
+    async fn delete_webhook(&self, id: &str) -> Result<()>;
+}
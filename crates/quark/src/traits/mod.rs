@@ -1,4 +1,5 @@
 mod admin {
+    pub mod job;
     pub mod migrations;
 }
 
@@ -12,9 +13,13 @@ mod channels {
     pub mod channel_invite;
     pub mod channel_unread;
     pub mod message;
+    pub mod message_revision;
+    pub mod webhook;
 }
 
 mod servers {
+    pub mod audit_log;
+    pub mod automod_rule;
     pub mod server;
     pub mod server_ban;
     pub mod server_member;
@@ -22,10 +27,12 @@ mod servers {
 
 mod users {
     pub mod bot;
+    pub mod bot_command;
     pub mod user;
     pub mod user_settings;
 }
 
+pub use admin::job::AbstractJobQueue;
 pub use admin::migrations::AbstractMigrations;
 
 pub use media::attachment::AbstractAttachment;
@@ -35,18 +42,24 @@ pub use channels::channel::AbstractChannel;
 pub use channels::channel_invite::AbstractChannelInvite;
 pub use channels::channel_unread::AbstractChannelUnread;
 pub use channels::message::AbstractMessage;
+pub use channels::message_revision::AbstractMessageRevision;
+pub use channels::webhook::AbstractWebhook;
 
+pub use servers::audit_log::AbstractAuditLog;
+pub use servers::automod_rule::AbstractAutomodRule;
 pub use servers::server::AbstractServer;
 pub use servers::server_ban::AbstractServerBan;
 pub use servers::server_member::AbstractServerMember;
 
 pub use users::bot::AbstractBot;
+pub use users::bot_command::AbstractBotCommand;
 pub use users::user::AbstractUser;
 pub use users::user_settings::AbstractUserSettings;
 
 pub trait AbstractDatabase:
     Sync
     + Send
+    + AbstractJobQueue
     + AbstractMigrations
     + AbstractAttachment
     + AbstractEmoji
@@ -54,10 +67,15 @@ pub trait AbstractDatabase:
     + AbstractChannelInvite
     + AbstractChannelUnread
     + AbstractMessage
+    + AbstractMessageRevision
+    + AbstractWebhook
+    + AbstractAuditLog
+    + AbstractAutomodRule
     + AbstractServer
     + AbstractServerBan
     + AbstractServerMember
     + AbstractBot
+    + AbstractBotCommand
     + AbstractUser
     + AbstractUserSettings
 {
@@ -0,0 +1,19 @@
+use crate::models::audit_log::AuditLogAction;
+use crate::models::AuditLogEntry;
+use crate::Result;
+
+#[async_trait]
+pub trait AbstractAuditLog: Sync + Send {
+    /// Insert a new entry into a server's audit log
+    async fn insert_audit_log_entry(&self, entry: &AuditLogEntry) -> Result<()>;
+
+    /// Fetch entries from a server's audit log, newest first
+    async fn fetch_audit_log(
+        &self,
+        server: &str,
+        limit: i64,
+        before: Option<String>,
+        actor: Option<String>,
+        action: Option<AuditLogAction>,
+    ) -> Result<Vec<AuditLogEntry>>;
+}
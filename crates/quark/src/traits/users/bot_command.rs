@@ -0,0 +1,20 @@
+use crate::models::BotCommand;
+use crate::Result;
+
+#[async_trait]
+pub trait AbstractBotCommand: Sync + Send {
+    /// Insert a new command into the database
+    async fn insert_command(&self, command: &BotCommand) -> Result<()>;
+
+    /// Fetch a command by its id
+    async fn fetch_command(&self, id: &str) -> Result<BotCommand>;
+
+    /// Fetch all commands registered by a bot
+    async fn fetch_commands(&self, bot: &str) -> Result<Vec<BotCommand>>;
+
+    /// Delete a command by its id
+    async fn delete_command(&self, id: &str) -> Result<()>;
+
+    /// Delete all commands registered by a bot
+    async fn delete_commands_for_bot(&self, bot: &str) -> Result<()>;
+}
@@ -1,11 +1,12 @@
-use futures::future::join;
 use rocket::request::FromParam;
 use schemars::schema::{InstanceType, Schema, SchemaObject, SingleOrVec};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::models::{Bot, Channel, Emoji, Invite, Member, Message, Server, ServerBan, User};
-use crate::presence::presence_is_online;
+use crate::models::{
+    Bot, BotCommand, Channel, Emoji, Invite, Member, Message, Server, ServerBan, User, Webhook,
+};
+use crate::presence::presence_populate;
 use crate::{Database, Error, Result};
 
 /// Reference to some object in the database
@@ -23,9 +24,8 @@ impl Ref {
 
     /// Fetch user from Ref
     pub async fn as_user(&self, db: &Database) -> Result<User> {
-        let (user, online) = join(db.fetch_user(&self.id), presence_is_online(&self.id)).await;
-        let mut user = user?;
-        user.online = Some(online);
+        let mut user = db.fetch_user(&self.id).await?;
+        presence_populate(&mut user).await;
         Ok(user)
     }
 
@@ -78,6 +78,16 @@ impl Ref {
     pub async fn as_emoji(&self, db: &Database) -> Result<Emoji> {
         db.fetch_emoji(&self.id).await
     }
+
+    /// Fetch command from Ref
+    pub async fn as_command(&self, db: &Database) -> Result<BotCommand> {
+        db.fetch_command(&self.id).await
+    }
+
+    /// Fetch webhook from Ref
+    pub async fn as_webhook(&self, db: &Database) -> Result<Webhook> {
+        db.fetch_webhook(&self.id).await
+    }
 }
 
 impl<'r> FromParam<'r> for Ref {
@@ -40,7 +40,16 @@ pub enum Error {
     CannotJoinCall,
     TooManyAttachments,
     TooManyReplies,
+    TooManyPins {
+        max: usize,
+    },
     EmptyMessage,
+    BlockedByAutomod {
+        rule: String,
+    },
+    InvalidAutomodPattern {
+        pattern: String,
+    },
     PayloadTooLarge,
     CannotRemoveYourself,
     GroupTooLarge {
@@ -62,6 +71,11 @@ pub enum Error {
     ReachedMaximumBots,
     IsBot,
     BotIsPrivate,
+    InvalidCommandOption {
+        option: String,
+    },
+    InteractionFailed,
+    InvalidInteractionsURL,
 
     // ? Permission errors.
     MissingPermission {
@@ -84,6 +98,9 @@ pub enum Error {
     InvalidCredentials,
     InvalidSession,
     DuplicateNonce,
+    TooManyConnections {
+        max: usize,
+    },
     VosoUnavailable,
     NotFound,
     NoEffect,
@@ -126,6 +143,8 @@ pub type Result<T, E = Error> = std::result::Result<T, E>;
 /// HTTP response builder for Error enum
 impl<'r> Responder<'r, 'static> for Error {
     fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        crate::metrics::record_error(&self);
+
         let status = match self {
             Error::LabelMe => Status::InternalServerError,
 
@@ -147,6 +166,9 @@ impl<'r> Responder<'r, 'static> for Error {
             Error::CannotJoinCall => Status::BadRequest,
             Error::TooManyAttachments => Status::BadRequest,
             Error::TooManyReplies => Status::BadRequest,
+            Error::TooManyPins { .. } => Status::BadRequest,
+            Error::BlockedByAutomod { .. } => Status::Forbidden,
+            Error::InvalidAutomodPattern { .. } => Status::BadRequest,
             Error::EmptyMessage => Status::UnprocessableEntity,
             Error::PayloadTooLarge => Status::UnprocessableEntity,
             Error::CannotRemoveYourself => Status::BadRequest,
@@ -163,6 +185,9 @@ impl<'r> Responder<'r, 'static> for Error {
             Error::ReachedMaximumBots => Status::BadRequest,
             Error::IsBot => Status::BadRequest,
             Error::BotIsPrivate => Status::Forbidden,
+            Error::InvalidCommandOption { .. } => Status::BadRequest,
+            Error::InteractionFailed => Status::BadGateway,
+            Error::InvalidInteractionsURL => Status::BadRequest,
 
             Error::MissingPermission { .. } => Status::Forbidden,
             Error::MissingUserPermission { .. } => Status::Forbidden,
@@ -176,6 +201,7 @@ impl<'r> Responder<'r, 'static> for Error {
             Error::InvalidCredentials => Status::Unauthorized,
             Error::InvalidSession => Status::Unauthorized,
             Error::DuplicateNonce => Status::Conflict,
+            Error::TooManyConnections { .. } => Status::TooManyRequests,
             Error::VosoUnavailable => Status::BadRequest,
             Error::NotFound => Status::NotFound,
             Error::NoEffect => Status::Ok,
@@ -42,6 +42,7 @@ lazy_static! {
     pub static ref USE_AUTUMN: bool = env::var("AUTUMN_PUBLIC_URL").is_ok();
     pub static ref USE_JANUARY: bool = env::var("JANUARY_PUBLIC_URL").is_ok();
     pub static ref USE_VOSO: bool = env::var("VOSO_PUBLIC_URL").is_ok() && env::var("VOSO_MANAGE_TOKEN").is_ok();
+    pub static ref USE_LOCAL_EVENTS: bool = env::var("REVOLT_EVENT_BUS").map_or(false, |v| v == "local");
 
     // SMTP Settings
     pub static ref SMTP_HOST: String =
@@ -59,8 +60,19 @@ lazy_static! {
         env::var("REVOLT_MAX_BOT_COUNT").unwrap_or_else(|_| "5".to_string()).parse().unwrap();
     pub static ref MAX_EMBED_COUNT: usize =
         env::var("REVOLT_MAX_EMBED_COUNT").unwrap_or_else(|_| "5".to_string()).parse().unwrap();
+    pub static ref MAX_PIN_COUNT: usize =
+        env::var("REVOLT_MAX_PIN_COUNT").unwrap_or_else(|_| "50".to_string()).parse().unwrap();
     pub static ref MAX_SERVER_COUNT: usize =
         env::var("REVOLT_MAX_SERVER_COUNT").unwrap_or_else(|_| "100".to_string()).parse().unwrap();
+    pub static ref MESSAGE_HISTORY_RETENTION: u32 =
+        env::var("REVOLT_MESSAGE_HISTORY_RETENTION").unwrap_or_else(|_| "30".to_string()).parse().unwrap();
+    pub static ref MESSAGE_RECOVERY_WINDOW: u64 =
+        env::var("REVOLT_MESSAGE_RECOVERY_WINDOW").unwrap_or_else(|_| "72".to_string()).parse().unwrap();
+    pub static ref MAX_JOB_ATTEMPTS: u32 =
+        env::var("REVOLT_MAX_JOB_ATTEMPTS").unwrap_or_else(|_| "8".to_string()).parse().unwrap();
+    pub static ref RATELIMIT_CONFIG: Option<String> = env::var("REVOLT_RATELIMIT_CONFIG").ok();
+    pub static ref METRICS_HOST: String =
+        env::var("REVOLT_METRICS_HOST").unwrap_or_else(|_| "127.0.0.1:8001".to_string());
     pub static ref EARLY_ADOPTER_BADGE: i64 =
         env::var("REVOLT_EARLY_ADOPTER_BADGE").unwrap_or_else(|_| "0".to_string()).parse().unwrap();
 }
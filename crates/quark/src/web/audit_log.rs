@@ -0,0 +1,71 @@
+use crate::Error;
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::okapi::openapi3::{Parameter, ParameterValue};
+use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+use schemars::schema::{InstanceType, SchemaObject, SingleOrVec};
+use validator::Validate;
+
+/// Reason given for an action through the `X-Audit-Log-Reason` header
+#[derive(Validate)]
+pub struct AuditLogReason {
+    #[validate(length(min = 1, max = 512))]
+    reason: Option<String>,
+}
+
+impl AuditLogReason {
+    pub fn into_inner(self) -> Option<String> {
+        self.reason
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for AuditLogReason {
+    fn from_request_input(
+        _gen: &mut OpenApiGenerator,
+        _name: String,
+        _required: bool,
+    ) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(RequestHeaderInput::Parameter(Parameter {
+            name: "X-Audit-Log-Reason".to_string(),
+            description: Some("Reason to record in the server's audit log".to_string()),
+            allow_empty_value: false,
+            required: false,
+            deprecated: false,
+            extensions: schemars::Map::new(),
+            location: "header".to_string(),
+            value: ParameterValue::Schema {
+                allow_reserved: false,
+                example: None,
+                examples: None,
+                explode: None,
+                style: None,
+                schema: SchemaObject {
+                    instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::String))),
+                    ..Default::default()
+                },
+            },
+        }))
+    }
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for AuditLogReason {
+    type Error = Error;
+
+    async fn from_request(request: &'r rocket::Request<'_>) -> Outcome<Self, Self::Error> {
+        let reason = AuditLogReason {
+            reason: request
+                .headers()
+                .get_one("X-Audit-Log-Reason")
+                .map(|reason| reason.to_string()),
+        };
+
+        if let Err(error) = reason.validate() {
+            return Outcome::Failure((Status::BadRequest, Error::FailedValidation { error }));
+        }
+
+        Outcome::Success(reason)
+    }
+}
@@ -0,0 +1,180 @@
+use std::future::Future;
+use std::time::Duration;
+
+use crate::{Error, Result};
+
+use rocket::http::Status;
+use rocket::request::{FromRequest, Outcome};
+use rocket_okapi::gen::OpenApiGenerator;
+use rocket_okapi::okapi::openapi3::{Parameter, ParameterValue};
+use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
+use schemars::schema::{InstanceType, SchemaObject, SingleOrVec};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use validator::Validate;
+
+mod store;
+
+pub use store::{
+    idempotency_store, set_idempotency_store, IdempotencyStore, MemoryIdempotencyStore,
+    RedisIdempotencyStore, Reservation,
+};
+
+/// How long responses are kept around for retries
+const IDEMPOTENCY_TTL: Duration = Duration::from_secs(60 * 60 * 24);
+
+/// How long a key stays reserved while its request is handled
+///
+/// Kept short so a node dying mid-request doesn't block retries for long.
+const RESERVATION_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Validate, Serialize, Deserialize)]
+pub struct IdempotencyKey {
+    #[validate(length(min = 1, max = 64))]
+    key: String,
+    /// Whether the key was given by the client rather than generated
+    #[serde(skip)]
+    provided: bool,
+}
+
+/// Response saved against an idempotency key
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "type", content = "data")]
+enum StoredResponse {
+    Success(serde_json::Value),
+    Failure(Error),
+}
+
+impl IdempotencyKey {
+    // Backwards compatibility.
+    // Issue #109
+    pub async fn consume_nonce(&mut self, v: Option<String>) -> Result<()> {
+        if let Some(v) = v {
+            self.key = v;
+            self.provided = true;
+        }
+
+        Ok(())
+    }
+
+    /// Get the key
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn into_key(self) -> String {
+        self.key
+    }
+
+    /// Handle a request at most once for this key and user
+    ///
+    /// Retries with the same key get the original response back, or a
+    /// conflict if the original request is still being handled. Requests
+    /// which failed because of a server error aren't saved and can be retried.
+    pub async fn replay<T, F>(&self, user_id: &str, request: F) -> Result<T>
+    where
+        T: Serialize + DeserializeOwned,
+        F: Future<Output = Result<T>>,
+    {
+        if !self.provided {
+            return request.await;
+        }
+
+        let store = idempotency_store();
+        let key = format!("idempotency:{user_id}:{}", self.key);
+        match store.reserve(&key, RESERVATION_TTL).await {
+            Ok(Reservation::Reserved) => {}
+            Ok(Reservation::Pending) => return Err(Error::DuplicateNonce),
+            Ok(Reservation::Completed(response)) => {
+                return match serde_json::from_str(&response) {
+                    Ok(StoredResponse::Success(value)) => {
+                        serde_json::from_value(value).map_err(|_| Error::InternalError)
+                    }
+                    Ok(StoredResponse::Failure(error)) => Err(error),
+                    Err(_) => Err(Error::InternalError),
+                };
+            }
+            Err(err) => {
+                // Rather handle the request than turn it away when the store is unavailable.
+                error!("Failed to reserve idempotency key: {err:?}");
+                return request.await;
+            }
+        }
+
+        let result = request.await;
+        let response = match &result {
+            Ok(data) => serde_json::to_value(data).map(StoredResponse::Success).ok(),
+            Err(Error::DatabaseError { .. } | Error::InternalError) => None,
+            Err(error) => Some(StoredResponse::Failure(error.clone())),
+        };
+
+        let saved = match response.and_then(|response| serde_json::to_string(&response).ok()) {
+            Some(response) => store.complete(&key, response, IDEMPOTENCY_TTL).await,
+            None => store.release(&key).await,
+        };
+
+        if let Err(err) = saved {
+            error!("Failed to save idempotency key: {err:?}");
+        }
+
+        result
+    }
+}
+
+impl<'r> OpenApiFromRequest<'r> for IdempotencyKey {
+    fn from_request_input(
+        _gen: &mut OpenApiGenerator,
+        _name: String,
+        _required: bool,
+    ) -> rocket_okapi::Result<RequestHeaderInput> {
+        Ok(RequestHeaderInput::Parameter(Parameter {
+            name: "Idempotency-Key".to_string(),
+            description: Some("Unique key to prevent duplicate requests".to_string()),
+            allow_empty_value: false,
+            required: false,
+            deprecated: false,
+            extensions: schemars::Map::new(),
+            location: "header".to_string(),
+            value: ParameterValue::Schema {
+                allow_reserved: false,
+                example: None,
+                examples: None,
+                explode: None,
+                style: None,
+                schema: SchemaObject {
+                    instance_type: Some(SingleOrVec::Single(Box::new(InstanceType::String))),
+                    ..Default::default()
+                },
+            },
+        }))
+    }
+}
+
+#[async_trait]
+impl<'r> FromRequest<'r> for IdempotencyKey {
+    type Error = Error;
+
+    async fn from_request(request: &'r rocket::Request<'_>) -> Outcome<Self, Self::Error> {
+        if let Some(key) = request
+            .headers()
+            .get("Idempotency-Key")
+            .next()
+            .map(|k| k.to_string())
+        {
+            let idempotency = IdempotencyKey {
+                key,
+                provided: true,
+            };
+
+            if let Err(error) = idempotency.validate() {
+                return Outcome::Failure((Status::BadRequest, Error::FailedValidation { error }));
+            }
+
+            return Outcome::Success(idempotency);
+        }
+
+        Outcome::Success(IdempotencyKey {
+            key: ulid::Ulid::new().to_string(),
+            provided: false,
+        })
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use dashmap::{mapref::entry::Entry as MapEntry, DashMap};
+use once_cell::sync::OnceCell;
+use redis_kiss::{get_connection, redis, AsyncCommands};
+
+use crate::{metrics::REDIS_ERRORS, Error, Result};
+
+/// Outcome of trying to reserve an idempotency key
+pub enum Reservation {
+    /// Key was free and is now held by this request
+    Reserved,
+    /// Another request with this key is still being handled
+    Pending,
+    /// A request with this key already completed with the given response
+    Completed(String),
+}
+
+/// Storage for idempotency keys and the responses they produced
+#[async_trait]
+pub trait IdempotencyStore: Sync + Send {
+    /// Reserve a key for the given time, unless it is already taken
+    async fn reserve(&self, key: &str, ttl: Duration) -> Result<Reservation>;
+
+    /// Save the response for a reserved key, keeping it for the given time
+    async fn complete(&self, key: &str, response: String, ttl: Duration) -> Result<()>;
+
+    /// Free a reserved key so the request can be tried again
+    async fn release(&self, key: &str) -> Result<()>;
+}
+
+/// Idempotency store backed by Redis, shared between all nodes
+pub struct RedisIdempotencyStore;
+
+/// Error for a failed idempotency operation
+fn error(operation: &'static str) -> Error {
+    REDIS_ERRORS.with_label_values(&[operation]).inc();
+    Error::DatabaseError {
+        operation,
+        with: "idempotency",
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for RedisIdempotencyStore {
+    async fn reserve(&self, key: &str, ttl: Duration) -> Result<Reservation> {
+        let mut conn = get_connection().await.map_err(|_| error("connect"))?;
+
+        // Pending keys are stored with an empty response, setting the
+        // expiry in the same command so a reservation can't outlive it.
+        let reserved: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg("")
+            .arg("NX")
+            .arg("PX")
+            .arg(ttl.as_millis() as usize)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|_| error("set"))?;
+
+        if reserved.is_some() {
+            return Ok(Reservation::Reserved);
+        }
+
+        let response: Option<String> = conn.get(key).await.map_err(|_| error("get"))?;
+        match response {
+            Some(response) if !response.is_empty() => Ok(Reservation::Completed(response)),
+            Some(_) => Ok(Reservation::Pending),
+            // Key expired in the meantime, so just try again.
+            None => self.reserve(key, ttl).await,
+        }
+    }
+
+    async fn complete(&self, key: &str, response: String, ttl: Duration) -> Result<()> {
+        get_connection()
+            .await
+            .map_err(|_| error("connect"))?
+            .pset_ex(key, response, ttl.as_millis() as usize)
+            .await
+            .map_err(|_| error("pset_ex"))
+    }
+
+    async fn release(&self, key: &str) -> Result<()> {
+        get_connection()
+            .await
+            .map_err(|_| error("connect"))?
+            .del(key)
+            .await
+            .map_err(|_| error("del"))
+    }
+}
+
+/// How often expired keys are cleared out of the in-memory store
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Idempotency key held in memory
+struct Entry {
+    response: Option<String>,
+    expires: Instant,
+}
+
+/// Idempotency store which keeps keys in the current process
+///
+/// Keys are lost on restart and not seen by other nodes,
+/// so only suitable for single node deployments and tests.
+#[derive(Default)]
+pub struct MemoryIdempotencyStore {
+    entries: DashMap<String, Entry>,
+    last_sweep: Mutex<Option<Instant>>,
+}
+
+impl MemoryIdempotencyStore {
+    /// Clear out expired keys, at most once every sweep interval
+    ///
+    /// Expired keys are also replaced when reserved again,
+    /// this only stops keys which are never seen again piling up.
+    fn sweep(&self, now: Instant) {
+        {
+            let mut last_sweep = self.last_sweep.lock().unwrap();
+            if matches!(*last_sweep, Some(last) if now.duration_since(last) < SWEEP_INTERVAL) {
+                return;
+            }
+
+            *last_sweep = Some(now);
+        }
+
+        self.entries.retain(|_, entry| entry.expires > now);
+    }
+}
+
+#[async_trait]
+impl IdempotencyStore for MemoryIdempotencyStore {
+    async fn reserve(&self, key: &str, ttl: Duration) -> Result<Reservation> {
+        let now = Instant::now();
+        self.sweep(now);
+
+        let pending = Entry {
+            response: None,
+            expires: now + ttl,
+        };
+
+        // Hold the entry throughout so concurrent requests can't both reserve it.
+        Ok(match self.entries.entry(key.to_string()) {
+            MapEntry::Occupied(mut entry) => {
+                if entry.get().expires <= now {
+                    entry.insert(pending);
+                    Reservation::Reserved
+                } else {
+                    match &entry.get().response {
+                        Some(response) => Reservation::Completed(response.clone()),
+                        None => Reservation::Pending,
+                    }
+                }
+            }
+            MapEntry::Vacant(entry) => {
+                entry.insert(pending);
+                Reservation::Reserved
+            }
+        })
+    }
+
+    async fn complete(&self, key: &str, response: String, ttl: Duration) -> Result<()> {
+        self.entries.insert(
+            key.to_string(),
+            Entry {
+                response: Some(response),
+                expires: Instant::now() + ttl,
+            },
+        );
+
+        Ok(())
+    }
+
+    async fn release(&self, key: &str) -> Result<()> {
+        self.entries.remove(key);
+        Ok(())
+    }
+}
+
+static IDEMPOTENCY_STORE: OnceCell<Box<dyn IdempotencyStore>> = OnceCell::new();
+
+/// Use a different idempotency store for this process
+///
+/// Must be called before any requests are handled.
+pub fn set_idempotency_store<S: IdempotencyStore + 'static>(store: S) {
+    if IDEMPOTENCY_STORE.set(Box::new(store)).is_err() {
+        panic!("Idempotency store was already initialised.");
+    }
+}
+
+/// Get the idempotency store for this process, Redis unless configured otherwise
+pub fn idempotency_store() -> &'static dyn IdempotencyStore {
+    IDEMPOTENCY_STORE
+        .get_or_init(|| Box::new(RedisIdempotencyStore))
+        .as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use futures::future::join_all;
+
+    use super::*;
+
+    #[async_std::test]
+    async fn memory_store() {
+        let store = MemoryIdempotencyStore::default();
+        let ttl = Duration::from_secs(10);
+
+        assert!(matches!(
+            store.reserve("key", ttl).await.unwrap(),
+            Reservation::Reserved
+        ));
+        assert!(matches!(
+            store.reserve("key", ttl).await.unwrap(),
+            Reservation::Pending
+        ));
+
+        store.complete("key", "response".into(), ttl).await.unwrap();
+        assert!(matches!(
+            store.reserve("key", ttl).await.unwrap(),
+            Reservation::Completed(response) if response == "response"
+        ));
+
+        store.release("key").await.unwrap();
+        assert!(matches!(
+            store.reserve("key", ttl).await.unwrap(),
+            Reservation::Reserved
+        ));
+    }
+
+    #[async_std::test]
+    async fn memory_store_expires() {
+        let store = MemoryIdempotencyStore::default();
+
+        store
+            .reserve("key", Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        async_std::task::sleep(Duration::from_millis(20)).await;
+        assert!(matches!(
+            store.reserve("key", Duration::from_secs(10)).await.unwrap(),
+            Reservation::Reserved
+        ));
+    }
+
+    #[async_std::test]
+    async fn concurrent_reservations() {
+        let store = Arc::new(MemoryIdempotencyStore::default());
+        let results = join_all((0..16).map(|_| {
+            let store = store.clone();
+            async_std::task::spawn(async move {
+                store.reserve("key", Duration::from_secs(10)).await.unwrap()
+            })
+        }))
+        .await;
+
+        let reserved = results
+            .iter()
+            .filter(|result| matches!(result, Reservation::Reserved))
+            .count();
+
+        assert_eq!(reserved, 1);
+    }
+}
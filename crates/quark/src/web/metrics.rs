@@ -0,0 +1,80 @@
+use std::net::SocketAddr;
+use std::time::Instant;
+
+use rocket::config::LogLevel;
+use rocket::fairing::{Fairing, Info, Kind};
+use rocket::http::ContentType;
+use rocket::{Data, Request, Response};
+
+use crate::metrics::{render, HTTP_REQUESTS, HTTP_REQUEST_DURATION};
+use crate::variables::delta::METRICS_HOST;
+
+/// Time at which Rocket started handling a request
+struct RequestStart(Instant);
+
+/// Record request counts and latency per route
+pub struct MetricsFairing;
+
+#[rocket::async_trait]
+impl Fairing for MetricsFairing {
+    fn info(&self) -> Info {
+        Info {
+            name: "Metrics",
+            kind: Kind::Request | Kind::Response,
+        }
+    }
+
+    async fn on_request(&self, request: &mut Request<'_>, _: &mut Data<'_>) {
+        request.local_cache(|| RequestStart(Instant::now()));
+    }
+
+    async fn on_response<'r>(&self, request: &'r Request<'_>, response: &mut Response<'r>) {
+        let RequestStart(start) = request.local_cache(|| RequestStart(Instant::now()));
+
+        // Use the route's template rather than the actual path,
+        // otherwise every resource id would get its own series.
+        let route = request
+            .route()
+            .map(|route| route.uri.to_string())
+            .unwrap_or_else(|| "unmatched".to_string());
+
+        let method = request.method().as_str();
+        HTTP_REQUESTS
+            .with_label_values(&[method, &route, &response.status().code.to_string()])
+            .inc();
+
+        HTTP_REQUEST_DURATION
+            .with_label_values(&[method, &route])
+            .observe(start.elapsed().as_secs_f64());
+    }
+}
+
+#[rocket::get("/metrics")]
+fn metrics() -> (ContentType, String) {
+    (ContentType::Plain, render())
+}
+
+/// Serve metrics on their own listener, away from the public API
+///
+/// Binds to `REVOLT_METRICS_HOST`, which should not be publicly reachable.
+pub async fn serve() {
+    let address: SocketAddr = METRICS_HOST
+        .parse()
+        .expect("Invalid REVOLT_METRICS_HOST, expected an address such as 127.0.0.1:8001");
+
+    let config = rocket::Config {
+        address: address.ip(),
+        port: address.port(),
+        log_level: LogLevel::Critical,
+        ..rocket::Config::default()
+    };
+
+    info!("Serving metrics on host {address}");
+    if let Err(err) = rocket::custom(config)
+        .mount("/", rocket::routes![metrics])
+        .launch()
+        .await
+    {
+        error!("Failed to serve metrics: {err}");
+    }
+}
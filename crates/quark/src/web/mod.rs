@@ -1,8 +1,10 @@
 use crate::Database;
 use rocket::State;
 
+pub mod audit_log;
 pub mod cors;
 pub mod idempotency;
+pub mod metrics;
 pub mod ratelimiter;
 pub mod swagger;
 
@@ -0,0 +1,234 @@
+use std::{collections::HashMap, fs, time::Duration};
+
+use rocket::http::Method;
+use serde::Deserialize;
+
+use crate::variables::delta::RATELIMIT_CONFIG;
+
+/// Rule mapping requests onto a bucket
+#[derive(Deserialize, Debug, Clone)]
+pub struct BucketRule {
+    /// Bucket which matching requests count against
+    pub bucket: String,
+    /// HTTP method to match, any method if not given
+    #[serde(default)]
+    pub method: Option<String>,
+    /// Leading path segments to match, `*` matches any single segment
+    pub path: Vec<String>,
+    /// Index of the path segment identifying a resource
+    ///
+    /// Each resource gets its own counter within the bucket.
+    #[serde(default)]
+    pub resource: Option<usize>,
+}
+
+/// Requests allowed within each window
+#[derive(Deserialize, Debug, Clone)]
+pub struct BucketLimit {
+    /// Limit for users and unauthenticated clients
+    pub users: u32,
+    /// Limit for bots
+    pub bots: u32,
+    /// Length of the window in seconds
+    #[serde(default = "default_window")]
+    pub window: u64,
+}
+
+/// Rate limiter configuration
+#[derive(Deserialize, Debug, Clone)]
+pub struct RatelimitConfig {
+    /// Rules to resolve buckets with, the first matching rule wins
+    pub rules: Vec<BucketRule>,
+    /// Limits for each bucket
+    pub limits: HashMap<String, BucketLimit>,
+    /// Limits for requests not matching any rule or for buckets without limits
+    pub default: BucketLimit,
+}
+
+/// Default length of the window in seconds
+fn default_window() -> u64 {
+    10
+}
+
+impl BucketRule {
+    /// Create a new rule
+    fn new(bucket: &str, method: Option<Method>, path: &[&str], resource: Option<usize>) -> Self {
+        BucketRule {
+            bucket: bucket.to_string(),
+            method: method.map(|method| method.as_str().to_string()),
+            path: path.iter().map(|segment| segment.to_string()).collect(),
+            resource,
+        }
+    }
+
+    /// Check whether a request matches this rule
+    pub fn matches<'r>(&self, method: Method, segment: impl Fn(usize) -> Option<&'r str>) -> bool {
+        if let Some(expected) = &self.method {
+            if !expected.eq_ignore_ascii_case(method.as_str()) {
+                return false;
+            }
+        }
+
+        self.path
+            .iter()
+            .enumerate()
+            .all(|(index, expected)| match segment(index) {
+                Some(actual) => expected == "*" || expected == actual,
+                None => false,
+            })
+    }
+}
+
+impl BucketLimit {
+    /// Create a new limit with the default window
+    fn new(users: u32, bots: u32) -> Self {
+        BucketLimit {
+            users,
+            bots,
+            window: default_window(),
+        }
+    }
+
+    /// Get the limit applying to a client
+    pub fn get(&self, is_bot: bool) -> u32 {
+        if is_bot {
+            self.bots
+        } else {
+            self.users
+        }
+    }
+
+    /// Get the length of the window
+    pub fn window(&self) -> Duration {
+        Duration::from_secs(self.window)
+    }
+}
+
+impl Default for RatelimitConfig {
+    fn default() -> Self {
+        RatelimitConfig {
+            rules: vec![
+                BucketRule::new(
+                    "default_avatar",
+                    None,
+                    &["users", "*", "default_avatar"],
+                    None,
+                ),
+                BucketRule::new("users", None, &["users"], None),
+                BucketRule::new("bots", None, &["bots"], None),
+                BucketRule::new(
+                    "messaging",
+                    Some(Method::Post),
+                    &["channels", "*", "messages"],
+                    Some(1),
+                ),
+                BucketRule::new("channels", None, &["channels", "*"], Some(1)),
+                BucketRule::new("servers", None, &["servers", "*"], Some(1)),
+                BucketRule::new("webhooks", None, &["webhooks", "*"], Some(1)),
+                BucketRule::new("auth_delete", Some(Method::Delete), &["auth"], None),
+                BucketRule::new("auth", None, &["auth"], None),
+                BucketRule::new("swagger", None, &["swagger"], None),
+            ],
+            limits: [
+                ("users", BucketLimit::new(20, 20)),
+                ("bots", BucketLimit::new(10, 10)),
+                ("messaging", BucketLimit::new(10, 10)),
+                ("channels", BucketLimit::new(15, 15)),
+                ("servers", BucketLimit::new(5, 5)),
+                ("webhooks", BucketLimit::new(10, 10)),
+                ("auth", BucketLimit::new(15, 15)),
+                ("auth_delete", BucketLimit::new(255, 255)),
+                ("default_avatar", BucketLimit::new(255, 255)),
+                ("swagger", BucketLimit::new(100, 100)),
+            ]
+            .into_iter()
+            .map(|(bucket, limit)| (bucket.to_string(), limit))
+            .collect(),
+            default: BucketLimit::new(20, 20),
+        }
+    }
+}
+
+impl RatelimitConfig {
+    /// Load the configuration file if one was given, otherwise use the defaults
+    fn load() -> Self {
+        match &*RATELIMIT_CONFIG {
+            Some(path) => {
+                let data = fs::read_to_string(path).expect("Failed to read rate limit config.");
+                serde_json::from_str(&data).expect("Failed to parse rate limit config.")
+            }
+            None => RatelimitConfig::default(),
+        }
+    }
+
+    /// Get the limits for a bucket
+    pub fn limit(&self, bucket: &str) -> &BucketLimit {
+        self.limits.get(bucket).unwrap_or(&self.default)
+    }
+}
+
+lazy_static! {
+    pub static ref CONFIG: RatelimitConfig = RatelimitConfig::load();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments<'a>(path: &'a [&'a str]) -> impl Fn(usize) -> Option<&'a str> {
+        move |index| path.get(index).copied()
+    }
+
+    #[test]
+    fn matches_path() {
+        let rule = BucketRule::new("channels", None, &["channels", "*"], Some(1));
+
+        assert!(rule.matches(Method::Get, segments(&["channels", "01A"])));
+        assert!(rule.matches(Method::Post, segments(&["channels", "01A", "messages"])));
+        assert!(!rule.matches(Method::Get, segments(&["channels"])));
+        assert!(!rule.matches(Method::Get, segments(&["servers", "01A"])));
+    }
+
+    #[test]
+    fn matches_method() {
+        let rule = BucketRule::new("auth_delete", Some(Method::Delete), &["auth"], None);
+
+        assert!(rule.matches(Method::Delete, segments(&["auth", "session"])));
+        assert!(!rule.matches(Method::Get, segments(&["auth", "session"])));
+
+        // Methods from configuration files may be written in any case.
+        let rule = BucketRule {
+            method: Some("delete".to_string()),
+            ..rule
+        };
+
+        assert!(rule.matches(Method::Delete, segments(&["auth"])));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let config = RatelimitConfig::default();
+        let bucket = |method, path: &[&str]| {
+            config
+                .rules
+                .iter()
+                .find(|rule| rule.matches(method, segments(path)))
+                .map(|rule| rule.bucket.as_str())
+        };
+
+        assert_eq!(
+            bucket(Method::Get, &["users", "01A", "default_avatar"]),
+            Some("default_avatar")
+        );
+        assert_eq!(bucket(Method::Get, &["users", "01A"]), Some("users"));
+        assert_eq!(
+            bucket(Method::Post, &["channels", "01A", "messages"]),
+            Some("messaging")
+        );
+        assert_eq!(
+            bucket(Method::Get, &["channels", "01A", "messages"]),
+            Some("channels")
+        );
+        assert_eq!(bucket(Method::Get, &["onboard"]), None);
+    }
+}
@@ -3,11 +3,10 @@
 //! This will be replaced again in the near future since
 //! I don't want duplication between two different projects.
 
-use std::collections::hash_map::DefaultHasher;
-use std::hash::Hasher;
-use std::ops::Add;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use once_cell::sync::Lazy;
 
+use crate::metrics::RATELIMIT_REJECTIONS;
+use crate::models::User;
 use crate::rauth::models::Session;
 use rocket::fairing::{Fairing, Info, Kind};
 use rocket::http::uri::Origin;
@@ -21,132 +20,41 @@ use rocket_okapi::request::{OpenApiFromRequest, RequestHeaderInput};
 
 use serde::Serialize;
 
-use dashmap::DashMap;
+mod buckets;
+mod store;
 
-/// Ratelimit Bucket
-#[derive(Clone, Copy)]
-struct Entry {
-    used: u8,
-    reset: u128,
-}
-
-lazy_static! {
-    static ref MAP: DashMap<u64, Entry> = DashMap::new();
-}
+pub use buckets::{BucketLimit, BucketRule, RatelimitConfig};
+pub use store::{
+    ratelimit_store, set_ratelimit_store, MemoryRatelimitStore, RatelimitStore, RedisRatelimitStore,
+};
 
-/// Get the current time from Unix Epoch as a Duration
-fn now() -> Duration {
-    SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .expect("Time went backwards...")
-}
-
-impl Entry {
-    /// Find bucket by its key
-    pub fn from(key: u64) -> Entry {
-        MAP.get(&key).map(|x| *x).unwrap_or_else(|| Entry {
-            used: 0,
-            reset: now().add(Duration::from_secs(10)).as_millis(),
-        })
-    }
-
-    /// Deduct one unit from the bucket and save
-    pub fn deduct(mut self, key: u64) {
-        let current_time = now().as_millis();
-        if current_time > self.reset {
-            self.used = 1;
-            self.reset = now().add(Duration::from_secs(10)).as_millis();
-        } else {
-            self.used += 1;
-        }
-
-        MAP.insert(key, self);
-    }
-
-    /// Get remaining units in the bucket
-    pub fn get_remaining(&self, limit: u8) -> u8 {
-        if now().as_millis() > self.reset {
-            limit
-        } else {
-            limit - self.used
-        }
-    }
+use buckets::CONFIG;
 
-    /// Get how long bucket has until reset
-    pub fn left_until_reset(&self) -> u128 {
-        let current_time = now().as_millis();
-        if current_time > self.reset {
-            0
-        } else {
-            self.reset - current_time
-        }
-    }
-}
+/// Counters used on this node while the configured store is unavailable
+static FALLBACK_STORE: Lazy<MemoryRatelimitStore> = Lazy::new(MemoryRatelimitStore::default);
 
 /// Ratelimit Guard
 #[derive(Serialize, Clone, Copy, Debug)]
 #[allow(dead_code)]
 pub struct Ratelimiter {
     key: u64,
-    limit: u8,
-    remaining: u8,
+    limit: u32,
+    remaining: u32,
     reset: u128,
 }
 
 /// Find bucket from given request
 ///
 /// Optionally, include a resource id to hash against.
-fn resolve_bucket<'r>(request: &'r rocket::Request<'_>) -> (&'r str, Option<&'r str>) {
-    if let Some(segment) = request.routed_segment(0) {
-        let resource = request.routed_segment(1);
-        match (segment, resource) {
-            ("users", _) => {
-                if let Some("default_avatar") = request.routed_segment(2) {
-                    return ("default_avatar", None);
-                }
-
-                ("users", None)
-            }
-            ("bots", _) => ("bots", None),
-            ("channels", Some(id)) => {
-                if request.method() == Method::Post {
-                    if let Some("messages") = request.routed_segment(2) {
-                        return ("messaging", Some(id));
-                    }
-                }
-
-                ("channels", Some(id))
-            }
-            ("servers", Some(id)) => ("servers", Some(id)),
-            ("auth", _) => {
-                if request.method() == Method::Delete {
-                    ("auth_delete", None)
-                } else {
-                    ("auth", None)
-                }
-            }
-            ("swagger", _) => ("swagger", None),
-            _ => ("any", None),
+fn resolve_bucket<'r>(request: &'r rocket::Request<'_>) -> (&'static str, Option<&'r str>) {
+    let segment = |index| request.routed_segment(index);
+    for rule in &CONFIG.rules {
+        if rule.matches(request.method(), segment) {
+            return (rule.bucket.as_str(), rule.resource.and_then(segment));
         }
-    } else {
-        ("any", None)
     }
-}
 
-/// Resolve per-bucket limits
-fn resolve_bucket_limit(bucket: &str) -> u8 {
-    match bucket {
-        "users" => 20,
-        "bots" => 10,
-        "messaging" => 10,
-        "channels" => 15,
-        "servers" => 5,
-        "auth" => 15,
-        "auth_delete" => 255,
-        "default_avatar" => 255,
-        "swagger" => 100,
-        _ => 20,
-    }
+    ("any", None)
 }
 
 /// Find the remote IP of the client
@@ -170,33 +78,64 @@ fn to_real_ip(request: &'_ rocket::Request<'_>) -> String {
     }
 }
 
+/// Hash the parts of a rate limit key using FNV-1a
+///
+/// Keys are shared between nodes, so unlike `DefaultHasher`
+/// the result must not change between builds.
+fn hash_key(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for (index, part) in parts.iter().enumerate() {
+        if index > 0 {
+            hash ^= b':' as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+
+        for byte in part.bytes() {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x100000001b3);
+        }
+    }
+
+    hash
+}
+
+/// Convert milliseconds until reset into a value for the `Retry-After` header
+fn to_retry_after(reset: u128) -> u128 {
+    (reset + 999) / 1000
+}
+
 impl Ratelimiter {
     /// Generate guard from identifier and target bucket
-    pub fn from(
+    pub async fn from(
         identifier: &str,
         (bucket, resource): (&str, Option<&str>),
+        is_bot: bool,
     ) -> Result<Ratelimiter, u128> {
-        let mut key = DefaultHasher::new();
-        key.write(identifier.as_bytes());
-        key.write(bucket.as_bytes());
-
-        if let Some(id) = resource {
-            key.write(id.as_bytes());
-        }
-
-        let key = key.finish();
-        let limit = resolve_bucket_limit(bucket);
-        let entry = Entry::from(key);
-
-        let remaining = entry.get_remaining(limit);
-        let reset = entry.left_until_reset();
+        let key = match resource {
+            Some(id) => hash_key(&[identifier, bucket, id]),
+            None => hash_key(&[identifier, bucket]),
+        };
+
+        let limits = CONFIG.limit(bucket);
+        let limit = limits.get(is_bot);
+
+        let (used, reset) = match ratelimit_store().hit(key, limits.window()).await {
+            Ok(hit) => hit,
+            Err(err) => {
+                // Keep limiting each node on its own rather than take the API down with the store.
+                error!("Failed to check rate limit for {bucket}: {err:?}");
+                FALLBACK_STORE
+                    .hit(key, limits.window())
+                    .await
+                    .unwrap_or_default()
+            }
+        };
 
-        if remaining > 0 {
-            entry.deduct(key);
+        if used <= limit {
             Ok(Ratelimiter {
                 key,
                 limit,
-                remaining: remaining - 1,
+                remaining: limit - used,
                 reset,
             })
         } else {
@@ -213,14 +152,28 @@ impl<'r> FromRequest<'r> for Ratelimiter {
         let ratelimiter = request
             .local_cache_async(async {
                 use rocket::outcome::Outcome;
-                let identifier = if let Outcome::Success(session) = request.guard::<Session>().await
-                {
-                    session.id
+
+                // Users are limited per session, which only needs the session lookup.
+                // Bots have no session, so their token is resolved to tell them apart
+                // and give them the bot limits, the route reuses the cached user.
+                let (identifier, is_bot) = if request.headers().contains("x-bot-token") {
+                    match request.guard::<User>().await {
+                        Outcome::Success(user) if user.bot.is_some() => (user.id, true),
+                        _ => (to_real_ip(request), false),
+                    }
+                } else if let Outcome::Success(session) = request.guard::<Session>().await {
+                    (session.id, false)
                 } else {
-                    to_real_ip(request)
+                    (to_real_ip(request), false)
                 };
 
-                Ratelimiter::from(&identifier, resolve_bucket(request))
+                let bucket = resolve_bucket(request);
+                let ratelimiter = Ratelimiter::from(&identifier, bucket, is_bot).await;
+                if ratelimiter.is_err() {
+                    RATELIMIT_REJECTIONS.with_label_values(&[bucket.0]).inc();
+                }
+
+                ratelimiter
             })
             .await;
 
@@ -283,7 +236,11 @@ impl Fairing for RatelimitFairing {
                 response.set_raw_header("X-RateLimit-Remaining", remaining.to_string());
                 response.set_raw_header("X-RateLimit-Reset-After", reset.to_string());
             }
-            Outcome::Failure(_) => response.set_status(Status::TooManyRequests),
+            Outcome::Failure((_, reset)) => {
+                response.set_status(Status::TooManyRequests);
+                response.set_raw_header("Retry-After", to_retry_after(reset).to_string());
+                response.set_raw_header("X-RateLimit-Reset-After", reset.to_string());
+            }
             Outcome::Forward(_) => unreachable!(),
         }
     }
@@ -317,3 +274,27 @@ fn ratelimit_info(info: RatelimitInformation) -> Json<RatelimitInformation> {
 pub fn routes() -> Vec<rocket::Route> {
     rocket::routes![ratelimit_info]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_key_is_stable() {
+        // Nodes running different builds must agree on these.
+        assert_eq!(hash_key(&[]), 14695981039346656037);
+        assert_eq!(hash_key(&["01ABC", "messaging"]), 9852485590070375836);
+        assert_eq!(
+            hash_key(&["01ABC", "messaging", "01CHANNEL"]),
+            13231128811714586932
+        );
+    }
+
+    #[test]
+    fn retry_after_rounds_up() {
+        assert_eq!(to_retry_after(0), 0);
+        assert_eq!(to_retry_after(1), 1);
+        assert_eq!(to_retry_after(1000), 1);
+        assert_eq!(to_retry_after(1001), 2);
+    }
+}
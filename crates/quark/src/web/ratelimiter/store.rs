@@ -0,0 +1,156 @@
+use std::{
+    ops::Add,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use dashmap::DashMap;
+use once_cell::sync::OnceCell;
+use redis_kiss::{get_connection, redis};
+
+use crate::{metrics::REDIS_ERRORS, Error, Result};
+
+/// Storage for rate limit counters
+#[async_trait]
+pub trait RatelimitStore: Sync + Send {
+    /// Count a request against a key
+    ///
+    /// Returns the number of requests made within the current window,
+    /// including this one, and how many milliseconds are left until it resets.
+    async fn hit(&self, key: u64, window: Duration) -> Result<(u32, u128)>;
+}
+
+/// Rate limit store backed by Redis, shared between all nodes
+pub struct RedisRatelimitStore;
+
+/// Error for a failed rate limit operation
+fn error(operation: &'static str) -> Error {
+    REDIS_ERRORS.with_label_values(&[operation]).inc();
+    Error::DatabaseError {
+        operation,
+        with: "ratelimit",
+    }
+}
+
+/// Count a hit and start the window on the first one, in a single step
+///
+/// Setting the expiry here also recovers any counter which was left without one.
+const HIT_SCRIPT: &str = r#"
+local used = redis.call("INCR", KEYS[1])
+local reset = redis.call("PTTL", KEYS[1])
+if reset < 0 then
+    redis.call("PEXPIRE", KEYS[1], ARGV[1])
+    reset = tonumber(ARGV[1])
+end
+return { used, reset }
+"#;
+
+#[async_trait]
+impl RatelimitStore for RedisRatelimitStore {
+    async fn hit(&self, key: u64, window: Duration) -> Result<(u32, u128)> {
+        let mut conn = get_connection().await.map_err(|_| error("connect"))?;
+
+        let (used, reset): (u32, i64) = redis::cmd("EVAL")
+            .arg(HIT_SCRIPT)
+            .arg(1)
+            .arg(format!("ratelimit:{key}"))
+            .arg(window.as_millis() as usize)
+            .query_async(&mut *conn)
+            .await
+            .map_err(|_| error("eval"))?;
+
+        Ok((used, reset.max(0) as u128))
+    }
+}
+
+/// Rate limit counter held in memory
+#[derive(Clone, Copy)]
+struct Entry {
+    used: u32,
+    reset: u128,
+}
+
+/// Rate limit store which keeps counters in the current process
+///
+/// Limits apply per node, so only suitable for single node deployments.
+#[derive(Default)]
+pub struct MemoryRatelimitStore {
+    entries: DashMap<u64, Entry>,
+}
+
+/// Get the current time from Unix Epoch as a Duration
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards...")
+}
+
+#[async_trait]
+impl RatelimitStore for MemoryRatelimitStore {
+    async fn hit(&self, key: u64, window: Duration) -> Result<(u32, u128)> {
+        let current_time = now().as_millis();
+        let mut entry = self
+            .entries
+            .entry(key)
+            .or_insert(Entry { used: 0, reset: 0 });
+
+        if current_time > entry.reset {
+            entry.used = 1;
+            entry.reset = now().add(window).as_millis();
+        } else {
+            entry.used += 1;
+        }
+
+        Ok((entry.used, entry.reset - current_time))
+    }
+}
+
+static RATELIMIT_STORE: OnceCell<Box<dyn RatelimitStore>> = OnceCell::new();
+
+/// Use a different rate limit store for this process
+///
+/// Must be called before any requests are handled.
+pub fn set_ratelimit_store<S: RatelimitStore + 'static>(store: S) {
+    if RATELIMIT_STORE.set(Box::new(store)).is_err() {
+        panic!("Rate limit store was already initialised.");
+    }
+}
+
+/// Get the rate limit store for this process, Redis unless configured otherwise
+pub fn ratelimit_store() -> &'static dyn RatelimitStore {
+    RATELIMIT_STORE
+        .get_or_init(|| Box::new(RedisRatelimitStore))
+        .as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[async_std::test]
+    async fn memory_store() {
+        let store = MemoryRatelimitStore::default();
+        let window = Duration::from_secs(10);
+
+        let (used, reset) = store.hit(1, window).await.unwrap();
+        assert_eq!(used, 1);
+        assert!(reset > 0);
+
+        assert_eq!(store.hit(1, window).await.unwrap().0, 2);
+        assert_eq!(store.hit(1, window).await.unwrap().0, 3);
+
+        // Other keys are counted separately.
+        assert_eq!(store.hit(2, window).await.unwrap().0, 1);
+    }
+
+    #[async_std::test]
+    async fn memory_store_resets() {
+        let store = MemoryRatelimitStore::default();
+        let window = Duration::from_millis(20);
+
+        store.hit(1, window).await.unwrap();
+        assert_eq!(store.hit(1, window).await.unwrap().0, 2);
+
+        async_std::task::sleep(Duration::from_millis(40)).await;
+        assert_eq!(store.hit(1, window).await.unwrap().0, 1);
+    }
+}
@@ -0,0 +1,71 @@
+//! Runs the conformance suite against every available database implementation.
+//!
+//! MongoDB and PostgreSQL are only tested if `MONGODB` or `POSTGRES`
+//! point at a running instance respectively.
+//!
+//! The suite itself requires the `conformance` feature:
+//! `cargo test -p revolt-quark --features conformance`
+#[cfg(feature = "conformance")]
+use std::env;
+
+use revolt_quark::models::user::UserHint;
+use revolt_quark::models::User;
+#[cfg(feature = "conformance")]
+use revolt_quark::r#impl::conformance;
+use revolt_quark::*;
+
+#[cfg(feature = "conformance")]
+#[async_std::test]
+async fn reference() {
+    let db = DatabaseInfo::Reference.connect().await.unwrap();
+    conformance::run(&db).await;
+}
+
+#[async_std::test]
+async fn reference_sessions() {
+    let db = DatabaseInfo::Reference.connect().await.unwrap();
+    let user = User {
+        id: "01FD58YK5W7QRV5H3D64NA6ZSA".to_string(),
+        username: "session".to_string(),
+        ..Default::default()
+    };
+
+    db.insert_user(&user).await.unwrap();
+
+    assert!(matches!(
+        User::from_token(&db, "token", UserHint::User).await,
+        Err(Error::InvalidSession)
+    ));
+
+    if let Database::Reference(reference) = &db {
+        reference.insert_session("token", &user.id).await;
+    }
+
+    assert_eq!(
+        User::from_token(&db, "token", UserHint::User)
+            .await
+            .unwrap()
+            .id,
+        user.id
+    );
+}
+
+#[cfg(feature = "conformance")]
+#[async_std::test]
+async fn mongo() {
+    if let Ok(uri) = env::var("MONGODB") {
+        let db = DatabaseInfo::MongoDb(uri).connect().await.unwrap();
+        db.migrate_database().await.unwrap();
+        conformance::run(&db).await;
+    }
+}
+
+#[cfg(all(feature = "conformance", feature = "postgres"))]
+#[async_std::test]
+async fn postgres() {
+    if let Ok(uri) = env::var("POSTGRES") {
+        let db = DatabaseInfo::Postgres(uri).connect().await.unwrap();
+        db.migrate_database().await.unwrap();
+        conformance::run(&db).await;
+    }
+}
@@ -0,0 +1,57 @@
+//! Checks that the in-process event bus delivers events to subscribed topics only.
+use revolt_quark::events::bus::{EventBus, LocalEventBus};
+use revolt_quark::events::client::{EventV1, Ping};
+
+fn pong(n: usize) -> EventV1 {
+    EventV1::Pong {
+        data: Ping::Number(n),
+    }
+}
+
+fn number(event: EventV1) -> usize {
+    match event {
+        EventV1::Pong {
+            data: Ping::Number(n),
+        } => n,
+        _ => panic!("Unexpected event."),
+    }
+}
+
+#[async_std::test]
+async fn local_event_bus() {
+    let bus = LocalEventBus::default();
+    let mut first = bus.subscriber().await.unwrap();
+    let mut second = bus.subscriber().await.unwrap();
+
+    first.subscribe("a").await;
+    first.subscribe("b").await;
+    second.subscribe("b").await;
+
+    // Only subscribers of a topic receive its events.
+    bus.publish("a".to_string(), pong(1)).await;
+    bus.publish("b".to_string(), pong(2)).await;
+
+    let (topic, event) = first.next().await.unwrap();
+    assert_eq!((topic.as_str(), number(event)), ("a", 1));
+    let (topic, event) = first.next().await.unwrap();
+    assert_eq!((topic.as_str(), number(event)), ("b", 2));
+    let (topic, event) = second.next().await.unwrap();
+    assert_eq!((topic.as_str(), number(event)), ("b", 2));
+
+    // Unsubscribed topics are no longer delivered.
+    first.unsubscribe("a").await;
+    bus.publish("a".to_string(), pong(3)).await;
+    bus.publish("b".to_string(), pong(4)).await;
+
+    let (topic, event) = first.next().await.unwrap();
+    assert_eq!((topic.as_str(), number(event)), ("b", 4));
+
+    // Dropped subscribers are removed from their topics.
+    drop(first);
+    bus.publish("b".to_string(), pong(5)).await;
+
+    let (_, event) = second.next().await.unwrap();
+    assert_eq!(number(event), 4);
+    let (_, event) = second.next().await.unwrap();
+    assert_eq!(number(event), 5);
+}